@@ -0,0 +1,61 @@
+import { test, expect, Page } from '@playwright/test';
+import { mockTauri, tauriCalls, openNewProject } from './tauriMock';
+
+/** Selects an auth type in the open request editor */
+async function selectAuthType(page: Page, type: string) {
+    await page.locator('select').filter({ has: page.locator(`option[value="${type}"]`) }).first().selectOption(type);
+}
+
+test.describe('Auth Editor', () => {
+    test.beforeEach(async ({ page }) => {
+        await mockTauri(page, {
+            oauth2_get_token: { access_token: 'abc', token_type: 'Bearer', expires_at: null }
+        });
+        await openNewProject(page);
+
+        // Create a collection with a request and open its Auth tab
+        await page.getByTitle('New', { exact: true }).click();
+        await page.keyboard.press('Enter');
+        await page.getByText('New Collection', { exact: true }).click({ button: 'right' });
+        await page.getByText('New Request', { exact: true }).click();
+        await page.keyboard.press('Enter');
+        await page.getByText('Auth', { exact: true }).click();
+    });
+
+    test('shows the fields of the selected OAuth 2.0 grant', async ({ page }) => {
+        await selectAuthType(page, 'oauth2');
+
+        await expect(page.getByText('Grant Type')).toBeVisible();
+        await expect(page.getByText('Device Authorization URL')).not.toBeVisible();
+
+        await page.locator('select').filter({ has: page.locator('option[value="device_code"]') }).selectOption('device_code');
+        await expect(page.getByText('Device Authorization URL')).toBeVisible();
+    });
+
+    test('fetches an OAuth 2.0 token on demand', async ({ page }) => {
+        await selectAuthType(page, 'oauth2');
+        await page.getByPlaceholder('https://auth.example.com/oauth/token').fill('https://auth.example.com/oauth/token');
+        await page.getByRole('button', { name: 'Get Token' }).click();
+
+        await expect(page.getByText('Got a Bearer token')).toBeVisible();
+        const calls = await tauriCalls(page, 'oauth2_get_token');
+        expect(calls[0].config.token_url).toBe('https://auth.example.com/oauth/token');
+        expect(calls[0].config.grant_type).toBe('client_credentials');
+    });
+
+    test('shows the editors of the signing auth types', async ({ page }) => {
+        await selectAuthType(page, 'aws_sigv4');
+        await expect(page.getByText('Secret Access Key')).toBeVisible();
+        await expect(page.getByPlaceholder('e.g. eu-west-1')).toBeVisible();
+
+        await selectAuthType(page, 'api_key');
+        await expect(page.getByPlaceholder('e.g. X-API-Key')).toBeVisible();
+
+        await selectAuthType(page, 'hmac');
+        await expect(page.getByText('String to Sign')).toBeVisible();
+        await expect(page.getByPlaceholder('Default: X-Signature')).toBeVisible();
+
+        await selectAuthType(page, 'digest');
+        await expect(page.getByText('Username')).toBeVisible();
+    });
+});
@@ -0,0 +1,82 @@
+import { test, expect } from '@playwright/test';
+import { mockTauri, emitTauriEvent, tauriCalls, openNewProject } from './tauriMock';
+
+test.describe('Execution Tools', () => {
+    test.beforeEach(async ({ page }) => {
+        await mockTauri(page, {
+            ws_connect: { status: 101, headers: [['upgrade', 'websocket']], request_raw: 'GET / HTTP/1.1', request_curl: 'curl' },
+            sse_request: { status: 200, body: '', closed_by: 'server', error: null },
+            run_batch: { total: 1, passed: 1, failed: 0, skipped: 0, cancelled: false, duration_ms: 1200 }
+        });
+        await openNewProject(page);
+
+        // Create a collection with a request and open its default execution
+        await page.getByTitle('New', { exact: true }).click();
+        await page.keyboard.press('Enter');
+        await page.getByText('New Collection', { exact: true }).click({ button: 'right' });
+        await page.getByText('New Request', { exact: true }).click();
+        await page.keyboard.press('Enter');
+        await page.getByText('New Request (default)').click();
+    });
+
+    test('opens an SSE stream with the execution as edited', async ({ page }) => {
+        await page.getByRole('button', { name: 'SSE Stream' }).click();
+        await page.getByRole('button', { name: 'Open Stream' }).click();
+
+        await expect(page.getByText('Stream ended (status 200, closed by server)')).toBeVisible();
+        const [call] = await tauriCalls(page, 'sse_request');
+        expect(call.args.url).toBe('https://api.example.com');
+        expect(call.args.method).toBe('GET');
+    });
+
+    test('keeps the WebSocket transcript sent with ws-closed', async ({ page }) => {
+        await page.getByRole('button', { name: 'WebSocket' }).click();
+        await expect(page.getByPlaceholder('wss://example.com/socket')).toHaveValue('wss://api.example.com');
+
+        await page.getByRole('button', { name: 'Connect' }).click();
+        await expect(page.getByText('Connected (status 101)')).toBeVisible();
+
+        const [connect] = await tauriCalls(page, 'ws_connect');
+        await emitTauriEvent(page, 'ws-closed', {
+            session_id: connect.args.session_id,
+            code: 1000,
+            reason: 'bye',
+            error: null,
+            transcript: [{ direction: 'received', kind: 'text', data: 'hello from server', close_code: null, elapsed_ms: 12 }]
+        });
+
+        await expect(page.getByText('Connection closed (1000) bye')).toBeVisible();
+        await expect(page.getByText('hello from server')).toBeVisible();
+        // The server already dropped the session, so nothing asks it to close again
+        expect(await tauriCalls(page, 'ws_close')).toHaveLength(0);
+    });
+
+    test('runs all executions of a collection in the backend', async ({ page }) => {
+        await page.getByText('New Collection', { exact: true }).click({ button: 'right' });
+        await page.getByText('Run All Executions', { exact: true }).click();
+        await expect(page.getByText('Run New Collection')).toBeVisible();
+
+        await page.getByRole('button', { name: 'Run 1 executions' }).click();
+
+        await expect(page.getByText('1 passed, 0 failed, 0 skipped in 1.2 s')).toBeVisible();
+        const [call] = await tauriCalls(page, 'run_batch');
+        expect(call.args.items).toHaveLength(1);
+        expect(call.args.items[0].request.url).toBe('https://api.example.com');
+        expect(call.args.stop_on_failure).toBe(false);
+    });
+
+    test('edits the retry policy of an execution', async ({ page }) => {
+        // The default execution is read-only, so add a custom one
+        await page.getByText('New Request', { exact: true }).first().click({ button: 'right' });
+        await page.getByText('New Execution', { exact: true }).click();
+        await page.keyboard.press('Enter');
+
+        await page.getByText('Retry', { exact: true }).click();
+        await expect(page.getByText('Max attempts')).not.toBeVisible();
+
+        await page.getByLabel('Retry failed requests').check();
+
+        await expect(page.getByText('Max attempts')).toBeVisible();
+        await expect(page.getByPlaceholder('429,502,503,504')).toBeVisible();
+    });
+});
@@ -0,0 +1,70 @@
+import { test, expect, Page } from '@playwright/test';
+import { mockTauri, emitTauriEvent, tauriCalls, openNewProject } from './tauriMock';
+
+const unmatched = {
+    id: 1,
+    timestamp: Date.now(),
+    method: 'POST',
+    uri: '/orders?dry_run=true',
+    headers: [['content-type', 'application/json']],
+    body: '{}',
+    matched: null,
+    status: 404,
+    fault: null,
+    latency_ms: 3
+};
+
+test.describe('Mock Request Journal', () => {
+    test.beforeEach(async ({ page }) => {
+        await mockTauri(page, {
+            start_mock_server: 3000,
+            get_mock_journal: [],
+            promote_mock_request: {
+                method: 'POST',
+                path: '/orders',
+                response: { status_code: 200, headers: [], body: '', templated: false }
+            }
+        });
+        await openNewProject(page);
+
+        // Create an external mock from scratch and open it
+        await page.getByTitle('New External Mock').click();
+        await page.getByRole('button', { name: /From Scratch/i }).click();
+        await page.getByText('External Mocks', { exact: true }).click();
+        await page.getByText('New External Mock', { exact: true }).click();
+        await page.getByRole('button', { name: /Start Server/i }).click();
+    });
+
+    test('shows requests streamed by the running server', async ({ page }) => {
+        await expect(page.getByText('Request Journal (0)')).toBeVisible();
+
+        await emitTauriEvent(page, 'mock-request', { ...unmatched, collection_id: await mockId(page) });
+
+        await expect(page.getByText('Request Journal (1)')).toBeVisible();
+        await expect(page.getByText('/orders?dry_run=true')).toBeVisible();
+    });
+
+    test('ignores requests of other mock servers', async ({ page }) => {
+        await emitTauriEvent(page, 'mock-request', { ...unmatched, collection_id: 'another-mock' });
+
+        await expect(page.getByText('Request Journal (0)')).toBeVisible();
+    });
+
+    test('promotes an unmatched request to an endpoint', async ({ page }) => {
+        await emitTauriEvent(page, 'mock-request', { ...unmatched, collection_id: await mockId(page) });
+        await page.getByRole('button', { name: /Promote/i }).click();
+
+        // The new endpoint is added to the list with the path of the request, query string dropped
+        await expect.poll(() => page.locator('input[type="text"]').evaluateAll(
+            inputs => inputs.map(i => (i as HTMLInputElement).value)
+        )).toContain('/orders');
+        const calls = await tauriCalls(page, 'promote_mock_request');
+        expect(calls[0].entryId).toBe(1);
+    });
+});
+
+/** Id the mock server was started with */
+async function mockId(page: Page): Promise<string> {
+    const calls = await tauriCalls(page, 'start_mock_server');
+    return calls[0].args.collection_id;
+}
@@ -0,0 +1,65 @@
+import { Page, expect } from '@playwright/test';
+
+/**
+ * Replaces the Tauri IPC with canned command results so the UI can run against the
+ * Vite dev server. Commands missing from `results` resolve to an empty object.
+ */
+export async function mockTauri(page: Page, results: Record<string, unknown> = {}) {
+    await page.addInitScript((canned) => {
+        const callbacks = new Map<number, (payload: unknown) => void>();
+        const listeners: Record<string, number[]> = {};
+        let nextId = 1;
+
+        (window as any).__TAURI_TEST__ = {
+            calls: [] as { cmd: string, args: any }[],
+            emit: (event: string, payload: unknown) => {
+                (listeners[event] || []).forEach(id => callbacks.get(id)?.({ event, id, payload }));
+            }
+        };
+        (window as any).__TAURI_EVENT_PLUGIN_INTERNALS__ = { unregisterListener: () => { } };
+        (window as any).__TAURI_INTERNALS__ = {
+            metadata: {
+                currentWindow: { label: 'main' },
+                currentWebview: { windowLabel: 'main', label: 'main' }
+            },
+            transformCallback: (callback: (payload: unknown) => void) => {
+                const id = nextId++;
+                callbacks.set(id, callback);
+                return id;
+            },
+            invoke: async (cmd: string, args: any) => {
+                (window as any).__TAURI_TEST__.calls.push({ cmd, args });
+                if (cmd === 'plugin:event|listen') {
+                    (listeners[args.event] ||= []).push(args.handler);
+                    return args.handler;
+                }
+                if (cmd === 'check_for_updates') {
+                    return { is_available: false, latest_version: '', release_url: '', release_date: '', body: '' };
+                }
+                if (cmd === 'get_project_manifest') {
+                    return { name: args.name, collections: [], open_tabs: [], active_tab_id: null };
+                }
+                return cmd in canned ? canned[cmd] : {};
+            }
+        };
+    }, results);
+}
+
+/** Emits a backend event to the listeners registered by the page. */
+export async function emitTauriEvent(page: Page, event: string, payload: unknown) {
+    await page.evaluate(([e, p]) => (window as any).__TAURI_TEST__.emit(e, p), [event, payload] as const);
+}
+
+/** Arguments of every call of `cmd` so far. */
+export async function tauriCalls(page: Page, cmd: string): Promise<any[]> {
+    return page.evaluate((c) => (window as any).__TAURI_TEST__.calls.filter((x: any) => x.cmd === c).map((x: any) => x.args), cmd);
+}
+
+/** Creates a project from the welcome screen and waits for the main layout. */
+export async function openNewProject(page: Page, name = 'Test Project') {
+    await page.goto('http://localhost:1420');
+    await page.getByRole('button', { name: /New Project/i }).click();
+    await page.getByPlaceholder(/Enter value.../i).fill(name);
+    await page.keyboard.press('Enter');
+    await expect(page.getByText('Env:')).toBeVisible();
+}
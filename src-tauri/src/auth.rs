@@ -0,0 +1,357 @@
+use crate::commands::HttpRequestArgs;
+use crate::oauth::{self, OAuth2Config, TokenCache};
+use crate::redact::Redactor;
+use crate::signing::{self, AwsSigV4Config, DigestConfig, HmacConfig};
+use serde::{Deserialize, Serialize};
+
+/// Auth settings for the types resolved by the backend. Mirrors the frontend `AuthConfig`
+/// (`type` plus one sub-object per type); "basic" and "bearer" are still turned into
+/// headers by the frontend and never reach this module.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuthConfig {
+    pub r#type: String,
+    #[serde(default)]
+    pub oauth2: Option<OAuth2Config>,
+    #[serde(default)]
+    pub digest: Option<DigestConfig>,
+    #[serde(default)]
+    pub aws_sigv4: Option<AwsSigV4Config>,
+    #[serde(default)]
+    pub api_key: Option<ApiKeyConfig>,
+    #[serde(default)]
+    pub hmac: Option<HmacConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKeyConfig {
+    pub key: String,
+    pub value: String,
+    /// "header" (default) or "query"
+    #[serde(default = "default_api_key_location")]
+    pub location: String,
+}
+
+fn default_api_key_location() -> String {
+    "header".to_string()
+}
+
+/// Replace (or add) a header, matching the name case-insensitively.
+pub fn set_header(headers: &mut Vec<Vec<String>>, name: &str, value: String) {
+    headers.retain(|pair| pair.len() != 2 || !pair[0].eq_ignore_ascii_case(name));
+    headers.push(vec![name.to_string(), value]);
+}
+
+/// The redactor extended with the name of the request's API key, so that a key sent under a
+/// custom header or query param name is masked like the configured secrets.
+pub fn with_api_key_name(redactor: &Redactor, args: &HttpRequestArgs) -> Option<Redactor> {
+    let auth = args.auth.as_ref().filter(|auth| auth.r#type == "api_key")?;
+    let config = auth.api_key.as_ref()?;
+    Some(match config.location.as_str() {
+        "query" => redactor.with_query_param(&config.key),
+        _ => redactor.with_header_name(&config.key),
+    })
+}
+
+fn missing_config(auth_type: &str) -> String {
+    format!(
+        "{} auth selected but no {} configuration was provided",
+        auth_type, auth_type
+    )
+}
+
+/// Inject the credentials for `args.auth` into the outgoing headers. Runs once the body is
+/// final, right before the request is built, so signatures cover what is actually sent.
+/// Digest is challenge-based and handled after the first response by `digest_retry`.
+pub async fn apply<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    client: &reqwest::Client,
+    tokens: &TokenCache,
+    project_name: &str,
+    args: &mut HttpRequestArgs,
+) -> Result<(), String> {
+    let Some(auth) = args.auth.clone() else {
+        return Ok(());
+    };
+
+    match auth.r#type.as_str() {
+        "oauth2" => {
+            let config = auth
+                .oauth2
+                .as_ref()
+                .ok_or_else(|| missing_config("OAuth2"))?;
+            let token = oauth::access_token(app_handle, client, tokens, project_name, config)
+                .await
+                .map_err(|msg| {
+                    if !args.quiet {
+                        crate::rust_error!(app_handle, "{}", msg);
+                    }
+                    msg
+                })?;
+            set_header(&mut args.headers, "Authorization", token.header_value());
+        }
+        "aws_sigv4" => {
+            let config = auth
+                .aws_sigv4
+                .as_ref()
+                .ok_or_else(|| missing_config("AWS SigV4"))?;
+            let headers: Vec<(String, String)> = args
+                .headers
+                .iter()
+                .filter(|pair| pair.len() == 2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect();
+            // Multipart bodies are streamed by reqwest and cannot be hashed up front.
+            let body = match &args.form_data {
+                Some(_) => None,
+                None => Some(args.body.as_deref().unwrap_or_default().as_bytes()),
+            };
+            let signed = signing::sigv4_headers(
+                config,
+                &args.method,
+                &args.url,
+                &headers,
+                body,
+                chrono::Utc::now(),
+            )?;
+            for (name, value) in signed {
+                set_header(&mut args.headers, &name, value);
+            }
+        }
+        "api_key" => {
+            let config = auth
+                .api_key
+                .as_ref()
+                .ok_or_else(|| missing_config("API Key"))?;
+            if config.location == "query" {
+                let mut url =
+                    reqwest::Url::parse(&args.url).map_err(|e| format!("Invalid URL: {}", e))?;
+                url.query_pairs_mut()
+                    .append_pair(&config.key, &config.value);
+                args.url = url.to_string();
+            } else {
+                set_header(&mut args.headers, &config.key, config.value.clone());
+            }
+        }
+        "hmac" => {
+            let config = auth.hmac.as_ref().ok_or_else(|| missing_config("HMAC"))?;
+            // Multipart bodies are streamed by reqwest and cannot be signed: refuse templates
+            // covering the body, others sign an empty one.
+            let body = match &args.form_data {
+                Some(_) => {
+                    let covers_body = |template: &str| template.contains("{body");
+                    if covers_body(&config.template)
+                        || config.header_value.as_deref().is_some_and(covers_body)
+                    {
+                        return Err("HMAC auth cannot sign a multipart body: remove {body} and \
+                                    {body_sha256} from the signature template"
+                            .to_string());
+                    }
+                    ""
+                }
+                None => args.body.as_deref().unwrap_or_default(),
+            };
+            let signed = signing::hmac_headers(
+                config,
+                &args.method,
+                &args.url,
+                body.as_bytes(),
+                chrono::Utc::now(),
+                &oauth::random_string(16),
+            )?;
+            for (name, value) in signed {
+                set_header(&mut args.headers, &name, value);
+            }
+        }
+        // none / inherit / basic / bearer are handled in the frontend, digest after the 401
+        _ => {}
+    }
+    Ok(())
+}
+
+/// For Digest auth, answer the server's 401 challenge: returns the Authorization header the
+/// request has to be replayed with, or None when no retry is needed.
+pub fn digest_retry(
+    args: &HttpRequestArgs,
+    response: &reqwest::Response,
+) -> Result<Option<String>, String> {
+    let Some(auth) = &args.auth else {
+        return Ok(None);
+    };
+    if auth.r#type != "digest" || response.status() != reqwest::StatusCode::UNAUTHORIZED {
+        return Ok(None);
+    }
+    let config = auth
+        .digest
+        .as_ref()
+        .ok_or_else(|| missing_config("Digest"))?;
+
+    let challenge = signing::select_digest_challenge(
+        response
+            .headers()
+            .get_all(reqwest::header::WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|v| v.to_str().ok()),
+    );
+    let Some(challenge) = challenge else {
+        return Ok(None);
+    };
+
+    let url = reqwest::Url::parse(&args.url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let uri = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let authorization = signing::digest_authorization(
+        &challenge,
+        config,
+        &args.method.to_uppercase(),
+        &uri,
+        args.body.as_deref().unwrap_or_default().as_bytes(),
+        &oauth::random_string(32),
+    )?;
+    Ok(Some(authorization))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{header, HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use md5::{Digest, Md5};
+
+    const REALM: &str = "test@example.org";
+    const NONCE: &str = "dcd98b7102dd2f0e8b11d0f600bfb0c093";
+
+    fn md5_hex(data: String) -> String {
+        hex::encode(Md5::digest(data.as_bytes()))
+    }
+
+    /// Answers with a Digest challenge until the request carries valid credentials for
+    /// user / secret, checked the way a server would.
+    async fn protected(uri: axum::http::Uri, headers: HeaderMap) -> axum::response::Response {
+        let challenge = format!(
+            "Digest realm=\"{}\", qop=\"auth\", nonce=\"{}\", opaque=\"xyz\"",
+            REALM, NONCE
+        );
+        let unauthorized = || {
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, challenge.clone())],
+            )
+                .into_response()
+        };
+        let Some(authorization) = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        else {
+            return unauthorized();
+        };
+        let param = |name: &str| {
+            authorization
+                .split(", ")
+                .find_map(|part| part.strip_prefix(&format!("{}=", name)))
+                .map(|v| v.trim_matches('"').to_string())
+                .unwrap_or_default()
+        };
+        let ha1 = md5_hex(format!("user:{}:secret", REALM));
+        let ha2 = md5_hex(format!("GET:{}", uri));
+        let expected = md5_hex(format!(
+            "{}:{}:{}:{}:auth:{}",
+            ha1,
+            NONCE,
+            param("nc"),
+            param("cnonce"),
+            ha2
+        ));
+        if param("uri") == uri.to_string() && param("response") == expected {
+            "welcome".into_response()
+        } else {
+            unauthorized()
+        }
+    }
+
+    fn digest_args(url: String, password: &str) -> HttpRequestArgs {
+        HttpRequestArgs {
+            method: "get".to_string(),
+            url,
+            auth: Some(AuthConfig {
+                r#type: "digest".to_string(),
+                oauth2: None,
+                digest: Some(DigestConfig {
+                    username: "user".to_string(),
+                    password: password.to_string(),
+                }),
+                aws_sigv4: None,
+                api_key: None,
+                hmac: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Send `args`, then replay it with the Digest answer like `send_request` does.
+    async fn round_trip(args: HttpRequestArgs) -> reqwest::Response {
+        let client = reqwest::Client::new();
+        let response = client.get(&args.url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let authorization = digest_retry(&args, &response).unwrap().unwrap();
+        assert!(digest_retry(&HttpRequestArgs::default(), &response)
+            .unwrap()
+            .is_none());
+        client
+            .get(&args.url)
+            .header(header::AUTHORIZATION, authorization)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn custom_api_key_names_are_redacted() {
+        let args = |location: &str| HttpRequestArgs {
+            auth: Some(AuthConfig {
+                r#type: "api_key".to_string(),
+                oauth2: None,
+                digest: None,
+                aws_sigv4: None,
+                api_key: Some(ApiKeyConfig {
+                    key: "sig".to_string(),
+                    value: "s3cr3t".to_string(),
+                    location: location.to_string(),
+                }),
+                hmac: None,
+            }),
+            ..Default::default()
+        };
+        let redactor = Redactor::default();
+        assert_eq!(
+            redactor.url("https://x.io/?sig=s3cr3t"),
+            "https://x.io/?sig=s3cr3t"
+        );
+
+        let extended = with_api_key_name(&redactor, &args("query")).unwrap();
+        assert_eq!(
+            extended.url("https://x.io/?a=1&sig=s3cr3t"),
+            "https://x.io/?a=1&sig=[REDACTED]"
+        );
+        let extended = with_api_key_name(&redactor, &args("header")).unwrap();
+        assert_eq!(extended.header_value("Sig", "s3cr3t"), "[REDACTED]");
+        assert!(with_api_key_name(&redactor, &HttpRequestArgs::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn digest_retry_answers_the_challenge() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = axum::Router::new().fallback(protected);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let url = format!("http://{}/dir/index.html?page=2", address);
+        let response = round_trip(digest_args(url.clone(), "secret")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "welcome");
+
+        let response = round_trip(digest_args(url, "wrong")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,522 @@
+use crate::commands::{
+    active_redactor, execute_request, project_client, ClientOptions, HttpRequestArgs, HttpResponse,
+};
+use crate::redact::Redactor;
+use crate::retry::status_matches;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{command, Emitter};
+use tokio::sync::oneshot;
+use tokio::task::JoinSet;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchItem {
+    /// Execution id, echoed back in progress events and the report
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// The execution, resolved by the frontend as for `http_request`
+    pub request: HttpRequestArgs,
+    /// Same syntax as use-case steps, e.g. "2xx,304"
+    #[serde(default = "default_success_codes")]
+    pub success_codes: String,
+    #[serde(default)]
+    pub assertions: Vec<Assertion>,
+}
+
+fn default_success_codes() -> String {
+    "2xx".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Assertion {
+    /// "status", "time" (ms), "body" or "header:Name"
+    pub source: String,
+    /// Select a field of a JSON body
+    #[serde(default)]
+    pub json_path: Option<String>,
+    /// "equals", "not_equals", "contains", "matches", "exists", "not_exists", "less_than"
+    /// or "greater_than"
+    pub operator: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionResult {
+    source: String,
+    operator: String,
+    expected: String,
+    actual: Option<String>,
+    passed: bool,
+    /// Why the assertion could not be evaluated, e.g. an invalid regex
+    message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchArgs {
+    pub items: Vec<BatchItem>,
+    /// Items run at the same time, 1 (the default) runs them in order
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// Skip the remaining items once one fails
+    #[serde(default)]
+    pub stop_on_failure: bool,
+    /// Registry id, so the run can be stopped with `cancel_http_request`
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    id: String,
+    name: String,
+    /// "passed", "failed" (assertions), "error" (no response) or "skipped"
+    state: String,
+    status: Option<u16>,
+    time_ms: u64,
+    error: Option<String>,
+    assertions: Vec<AssertionResult>,
+    response: Option<HttpResponse>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct BatchProgress {
+    pub request_id: String,
+    pub item_id: String,
+    pub index: usize,
+    pub total: usize,
+    /// "running" when the item is sent, then its final state
+    pub state: String,
+    pub status: Option<u16>,
+    pub time_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchReport {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    cancelled: bool,
+    duration_ms: u64,
+    items: Vec<BatchItemResult>,
+}
+
+fn header_value(response: &HttpResponse, name: &str) -> Option<String> {
+    response
+        .headers
+        .iter()
+        .find(|h| h.len() >= 2 && h[0].eq_ignore_ascii_case(name))
+        .map(|h| h[1].clone())
+}
+
+fn evaluate(assertion: &Assertion, response: &HttpResponse, time_ms: u64) -> AssertionResult {
+    let mut result = AssertionResult {
+        source: assertion.source.clone(),
+        operator: assertion.operator.clone(),
+        expected: assertion.value.clone(),
+        actual: None,
+        passed: false,
+        message: None,
+    };
+
+    let actual = match assertion.source.as_str() {
+        "status" => Some(response.status.to_string()),
+        "time" => Some(time_ms.to_string()),
+        "body" => match &assertion.json_path {
+            Some(path) => match serde_json::from_str::<serde_json::Value>(&response.body) {
+                Ok(json) => crate::jsonpath::select(&json, path).map(crate::jsonpath::to_text),
+                Err(_) => {
+                    result.message = Some("Body is not valid JSON".to_string());
+                    return result;
+                }
+            },
+            None => Some(response.body.clone()),
+        },
+        source => match source.strip_prefix("header:") {
+            Some(name) => header_value(response, name.trim()),
+            None => {
+                result.message = Some(format!("Unknown assertion source \"{}\"", source));
+                return result;
+            }
+        },
+    };
+
+    let number = |s: &str| s.trim().parse::<f64>().ok();
+    result.passed = match (assertion.operator.as_str(), actual.as_deref()) {
+        ("exists", actual) => actual.is_some(),
+        ("not_exists", actual) => actual.is_none(),
+        (_, None) => false,
+        ("equals", Some(actual)) => actual == assertion.value,
+        ("not_equals", Some(actual)) => actual != assertion.value,
+        ("contains", Some(actual)) => actual.contains(&assertion.value),
+        ("matches", Some(actual)) => match regex::Regex::new(&assertion.value) {
+            Ok(re) => re.is_match(actual),
+            Err(e) => {
+                result.message = Some(format!("Invalid regex: {}", e));
+                false
+            }
+        },
+        ("less_than", Some(actual)) => {
+            matches!((number(actual), number(&assertion.value)), (Some(a), Some(b)) if a < b)
+        }
+        ("greater_than", Some(actual)) => {
+            matches!((number(actual), number(&assertion.value)), (Some(a), Some(b)) if a > b)
+        }
+        (operator, Some(_)) => {
+            result.message = Some(format!("Unknown assertion operator \"{}\"", operator));
+            false
+        }
+    };
+    result.actual = actual;
+    result
+}
+
+async fn run_item(
+    app_handle: tauri::AppHandle,
+    client: reqwest::Client,
+    jar: Arc<reqwest::cookie::Jar>,
+    tokens: Arc<crate::oauth::TokenCache>,
+    project_name: String,
+    redactor: Option<Redactor>,
+    item: BatchItem,
+) -> BatchItemResult {
+    let started = Instant::now();
+    let response = execute_request(
+        &app_handle,
+        &client,
+        &jar,
+        &tokens,
+        &project_name,
+        redactor.as_ref(),
+        item.request,
+    )
+    .await;
+    let time_ms = started.elapsed().as_millis() as u64;
+
+    let mut result = BatchItemResult {
+        id: item.id,
+        name: item.name,
+        state: "error".to_string(),
+        status: None,
+        time_ms,
+        error: None,
+        assertions: Vec::new(),
+        response: None,
+    };
+    let response = match response {
+        Ok(response) => response,
+        Err(msg) => {
+            result.error = Some(msg);
+            return result;
+        }
+    };
+
+    result.assertions.push(AssertionResult {
+        source: "status".to_string(),
+        operator: "success_codes".to_string(),
+        expected: item.success_codes.clone(),
+        actual: Some(response.status.to_string()),
+        passed: status_matches(&item.success_codes, response.status),
+        message: None,
+    });
+    for assertion in &item.assertions {
+        result
+            .assertions
+            .push(evaluate(assertion, &response, time_ms));
+    }
+    let passed = result.assertions.iter().all(|a| a.passed);
+    result.state = if passed { "passed" } else { "failed" }.to_string();
+    result.status = Some(response.status);
+    result.response = Some(response);
+    result
+}
+
+fn emit_progress(
+    app_handle: &tauri::AppHandle,
+    request_id: Option<&String>,
+    index: usize,
+    total: usize,
+    item_id: &str,
+    state: &str,
+    result: Option<&BatchItemResult>,
+) {
+    if let Some(id) = request_id {
+        let _ = app_handle.emit(
+            "batch-progress",
+            BatchProgress {
+                request_id: id.clone(),
+                item_id: item_id.to_string(),
+                index,
+                total,
+                state: state.to_string(),
+                status: result.and_then(|r| r.status),
+                time_ms: result.map_or(0, |r| r.time_ms),
+            },
+        );
+    }
+}
+
+/// Run a folder or collection of executions in the backend, in order or with bounded
+/// parallelism. Each item is reported as a `batch-progress` event; the command resolves with
+/// the status, timing and assertion results of every item.
+#[command]
+pub async fn run_batch(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::HttpRequestState>,
+    redaction: tauri::State<'_, crate::RedactionState>,
+    args: BatchArgs,
+) -> Result<BatchReport, String> {
+    let (tx, mut cancel_rx) = oneshot::channel::<()>();
+    if let Some(id) = &args.request_id {
+        let mut handles = state.handles.lock().await;
+        handles.insert(id.clone(), tx);
+    }
+
+    let started = Instant::now();
+    let total = args.items.len();
+    let concurrency = args.concurrency.unwrap_or(1).max(1);
+    let request_id = args.request_id.as_ref();
+    let labels: Vec<(String, String)> = args
+        .items
+        .iter()
+        .map(|item| (item.id.clone(), item.name.clone()))
+        .collect();
+    let mut results: Vec<Option<BatchItemResult>> = (0..total).map(|_| None).collect();
+    let mut pending = args.items.into_iter().enumerate();
+    let mut running = JoinSet::new();
+    let mut stopped = false;
+    let mut cancelled = false;
+
+    crate::rust_info!(&app_handle, "Batch run started: {} items", total);
+
+    loop {
+        while !stopped && running.len() < concurrency {
+            let Some((index, mut item)) = pending.next() else {
+                break;
+            };
+            let p_name = item
+                .request
+                .project_name
+                .clone()
+                .unwrap_or_else(|| "default".to_string());
+            item.request.request_id = None;
+            // Failures are reported per item
+            item.request.quiet = true;
+            crate::dns::merge_project(&state, &p_name, &mut item.request).await;
+            let redactor = active_redactor(&redaction, item.request.reveal_secrets);
+            let options = ClientOptions::from_args(&item.request);
+
+            match project_client(&state, &p_name, &options).await {
+                Ok((client, jar)) => {
+                    emit_progress(
+                        &app_handle,
+                        request_id,
+                        index,
+                        total,
+                        &item.id,
+                        "running",
+                        None,
+                    );
+                    let task = run_item(
+                        app_handle.clone(),
+                        client,
+                        jar,
+                        Arc::clone(&state.tokens),
+                        p_name,
+                        redactor,
+                        item,
+                    );
+                    running.spawn(async move { (index, task.await) });
+                }
+                Err(msg) => {
+                    let result = BatchItemResult {
+                        id: item.id,
+                        name: item.name,
+                        state: "error".to_string(),
+                        status: None,
+                        time_ms: 0,
+                        error: Some(msg),
+                        assertions: Vec::new(),
+                        response: None,
+                    };
+                    emit_progress(
+                        &app_handle,
+                        request_id,
+                        index,
+                        total,
+                        &result.id,
+                        "error",
+                        Some(&result),
+                    );
+                    stopped |= args.stop_on_failure;
+                    results[index] = Some(result);
+                }
+            }
+        }
+        if running.is_empty() {
+            break;
+        }
+
+        tokio::select! {
+            joined = running.join_next() => {
+                // Aborted tasks come back as join errors and are reported as skipped
+                if let Some(Ok((index, result))) = joined {
+                    emit_progress(&app_handle, request_id, index, total, &result.id, &result.state, Some(&result));
+                    stopped |= args.stop_on_failure && result.state != "passed";
+                    results[index] = Some(result);
+                }
+            }
+            _ = &mut cancel_rx, if !cancelled => {
+                cancelled = true;
+                stopped = true;
+                running.abort_all();
+            }
+        }
+    }
+
+    if let Some(id) = &args.request_id {
+        let mut handles = state.handles.lock().await;
+        handles.remove(id);
+    }
+
+    let items: Vec<BatchItemResult> = results
+        .into_iter()
+        .zip(labels)
+        .map(|(result, (id, name))| {
+            result.unwrap_or(BatchItemResult {
+                id,
+                name,
+                state: "skipped".to_string(),
+                status: None,
+                time_ms: 0,
+                error: None,
+                assertions: Vec::new(),
+                response: None,
+            })
+        })
+        .collect();
+    let count = |state: &str| items.iter().filter(|i| i.state == state).count();
+    let report = BatchReport {
+        total,
+        passed: count("passed"),
+        failed: count("failed") + count("error"),
+        skipped: count("skipped"),
+        cancelled,
+        duration_ms: started.elapsed().as_millis() as u64,
+        items,
+    };
+
+    crate::rust_info!(
+        &app_handle,
+        "Batch run finished: {} passed, {} failed, {} skipped",
+        report.passed,
+        report.failed,
+        report.skipped
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, body: &str) -> HttpResponse {
+        serde_json::from_value(serde_json::json!({
+            "status": status,
+            "headers": [["Content-Type", "application/json"]],
+            "body": body,
+            "request_raw": "",
+            "request_curl": "",
+            "wire_size": body.len(),
+            "charset": "utf-8",
+            "http_version": "HTTP/1.1",
+        }))
+        .unwrap()
+    }
+
+    fn assertion(source: &str, json_path: Option<&str>, operator: &str, value: &str) -> Assertion {
+        Assertion {
+            source: source.to_string(),
+            json_path: json_path.map(String::from),
+            operator: operator.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn passes(assertion: &Assertion) -> bool {
+        let response = response(201, r#"{"user": {"id": 42, "name": "Ada"}}"#);
+        evaluate(assertion, &response, 150).passed
+    }
+
+    #[test]
+    fn status_and_time_compare_as_numbers() {
+        assert!(passes(&assertion("status", None, "equals", "201")));
+        assert!(!passes(&assertion("status", None, "not_equals", "201")));
+        assert!(passes(&assertion("time", None, "less_than", "200")));
+        assert!(!passes(&assertion("time", None, "greater_than", "200")));
+        assert!(!passes(&assertion("time", None, "less_than", "soon")));
+    }
+
+    #[test]
+    fn body_assertions_select_json_paths() {
+        assert!(passes(&assertion(
+            "body",
+            Some("$.user.id"),
+            "equals",
+            "42"
+        )));
+        assert!(passes(&assertion(
+            "body",
+            Some("$.user.name"),
+            "matches",
+            "^A"
+        )));
+        assert!(passes(&assertion(
+            "body",
+            Some("$.user.email"),
+            "not_exists",
+            ""
+        )));
+        assert!(!passes(&assertion(
+            "body",
+            Some("$.user.email"),
+            "equals",
+            ""
+        )));
+        assert!(passes(&assertion("body", None, "contains", "Ada")));
+
+        let not_json = response(200, "<html>");
+        let result = evaluate(&assertion("body", Some("$.id"), "exists", ""), &not_json, 0);
+        assert!(!result.passed);
+        assert_eq!(result.message.as_deref(), Some("Body is not valid JSON"));
+    }
+
+    #[test]
+    fn header_assertions_ignore_name_case() {
+        let check = assertion("header:content-type", None, "contains", "json");
+        assert!(passes(&check));
+        assert!(passes(&assertion(
+            "header:X-Missing",
+            None,
+            "not_exists",
+            ""
+        )));
+    }
+
+    #[test]
+    fn unknown_sources_operators_and_bad_regexes_fail_with_a_message() {
+        let response = response(200, "{}");
+        for check in [
+            assertion("cookie", None, "exists", ""),
+            assertion("status", None, "between", "200"),
+            assertion("status", None, "matches", "("),
+        ] {
+            let result = evaluate(&check, &response, 0);
+            assert!(!result.passed);
+            assert!(result.message.is_some(), "{:?}", check);
+        }
+    }
+}
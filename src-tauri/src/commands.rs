@@ -1,10 +1,10 @@
-use crate::MockServerState;
-use axum::{
-    http::{HeaderMap, Method as HttpMethod, StatusCode},
-    response::IntoResponse,
-    routing::any,
-    Router,
-};
+use crate::auth::AuthConfig;
+use crate::dns::DnsOverrides;
+use crate::graphql::GraphQLBody;
+use crate::oauth::{OAuth2Config, OAuth2Token, TokenCache};
+use crate::redact::{RedactionConfig, Redactor};
+use crate::retry::{RetryAttempt, RetryPolicy, SendError};
+use base64::Engine;
 use git2::{IndexAddOption, Repository, Signature, StatusOptions};
 use reqwest::cookie::CookieStore;
 use reqwest::{multipart, Method};
@@ -13,19 +13,39 @@ use std::str::FromStr;
 use std::sync::Arc;
 use tauri::{command, path::BaseDirectory, Manager};
 use tokio::fs;
-use tokio::net::TcpListener;
 use tokio::sync::oneshot;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HttpResponse {
-    status: u16,
-    headers: Vec<Vec<String>>,
-    body: String,
+    pub status: u16,
+    pub headers: Vec<Vec<String>>,
+    pub body: String,
     request_raw: String,
     request_curl: String,
+    /// Content-Encoding as received on the wire (e.g. "gzip"), None when uncompressed
+    content_encoding: Option<String>,
+    /// Body size as transferred, before decompression
+    wire_size: usize,
+    /// Body size after decompression, None when the body was kept as received (`raw_body`,
+    /// or an encoding that could not be decoded)
+    decoded_size: Option<usize>,
+    /// Charset used to decode the body into text
+    charset: String,
+    /// Undecoded body bytes, only filled when `raw_body` was requested
+    body_base64: Option<String>,
+    /// Why the body could not be decompressed, in which case it is shown as received
+    decode_warning: Option<String>,
+    /// Protocol version actually used, e.g. "HTTP/2"
+    http_version: String,
+    /// Address actually connected to, None over a Unix socket
+    remote_ip: Option<String>,
+    remote_port: Option<u16>,
+    /// Every try when the request had a retry policy, empty otherwise
+    #[serde(default)]
+    attempts: Vec<RetryAttempt>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FormDataItem {
     key: String,
     value: String,
@@ -34,7 +54,7 @@ pub struct FormDataItem {
     pub content_type: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HttpRequestArgs {
     pub method: String,
     pub url: String,
@@ -47,6 +67,46 @@ pub struct HttpRequestArgs {
     pub request_id: Option<String>,
     #[serde(default)]
     pub project_name: Option<String>,
+    /// Skip secret redaction in `request_raw`/`request_curl` for the local view.
+    #[serde(default)]
+    pub reveal_secrets: bool,
+    /// Auth types resolved by the backend (e.g. OAuth2); basic/bearer arrive as plain headers.
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    /// Overrides the response charset announced in Content-Type.
+    #[serde(default)]
+    pub charset: Option<String>,
+    /// Keep the body exactly as received (no decompression) for debugging.
+    #[serde(default)]
+    pub raw_body: bool,
+    /// Protocol: "http1.0", "http1.1", "http2" (prefer HTTP/2 via ALPN, falling back to
+    /// HTTP/1.1 like curl's `--http2`) or "h2c" (prior knowledge). None lets reqwest negotiate,
+    /// which already offers HTTP/2 over TLS.
+    #[serde(default)]
+    pub http_version: Option<String>,
+    /// Send the request over this Unix domain socket instead of TCP (curl's `--unix-socket`).
+    /// The URL still provides the Host header and path.
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// `--resolve` / `--connect-to` style overrides for this request, on top of the project's.
+    #[serde(default)]
+    pub dns: Option<DnsOverrides>,
+    /// GraphQL operation, encoded into the body (POST) or the query string (GET) before sending.
+    #[serde(default)]
+    pub graphql: Option<GraphQLBody>,
+    /// Send the request again on matching statuses or transport errors.
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    /// Keep request failures out of the log, for callers that report them in a summary of
+    /// their own (load tests, batch runs)
+    #[serde(skip)]
+    pub quiet: bool,
+}
+
+pub fn has_header(args: &HttpRequestArgs, name: &str) -> bool {
+    args.headers
+        .iter()
+        .any(|pair| pair.len() == 2 && pair[0].eq_ignore_ascii_case(name))
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -61,15 +121,55 @@ pub struct GitCommitArgs {
     message: String,
 }
 
-fn generate_request_data(args: &HttpRequestArgs, jar: &reqwest::cookie::Jar) -> (String, String) {
+pub fn generate_request_data(
+    args: &HttpRequestArgs,
+    jar: &reqwest::cookie::Jar,
+    redactor: Option<&Redactor>,
+) -> (String, String) {
+    // Without a redactor (secrets revealed) every value is passed through untouched.
+    let header = |name: &str, value: &str| match redactor {
+        Some(r) => r.header_value(name, value),
+        None => value.to_string(),
+    };
+    let text = |value: &str| match redactor {
+        Some(r) => r.text(value),
+        None => value.to_string(),
+    };
+    let url = match redactor {
+        Some(r) => r.url(&args.url),
+        None => args.url.clone(),
+    };
+
     let method = args.method.to_uppercase();
-    let mut request_raw = format!("{} {} HTTP/1.1\r\n", method, args.url);
-    let mut request_curl = format!("curl -X {} \"{}\"", method, args.url);
+    let mut request_raw = format!(
+        "{} {} {}\r\n",
+        method,
+        url,
+        requested_version_label(args.http_version.as_deref())
+    );
+    let mut request_curl = format!("curl -X {} \"{}\"", method, url);
+    match args.http_version.as_deref() {
+        Some("http1.0") => request_curl.push_str(" --http1.0"),
+        Some("http1.1") => request_curl.push_str(" --http1.1"),
+        Some("http2") => request_curl.push_str(" --http2"),
+        Some("h2c") => request_curl.push_str(" --http2-prior-knowledge"),
+        _ => {}
+    }
+    if !has_header(args, "accept-encoding") {
+        request_curl.push_str(" --compressed");
+    }
+    if let Some(socket) = &args.unix_socket {
+        request_curl.push_str(&format!(" --unix-socket \"{}\"", socket));
+    }
+    if let Some(dns) = &args.dns {
+        request_curl.push_str(&dns.curl_flags());
+    }
 
     for pair in &args.headers {
         if pair.len() == 2 {
-            request_raw.push_str(&format!("{}: {}\r\n", pair[0], pair[1]));
-            request_curl.push_str(&format!(" -H \"{}: {}\"", pair[0], pair[1]));
+            let value = header(&pair[0], &pair[1]);
+            request_raw.push_str(&format!("{}: {}\r\n", pair[0], value));
+            request_curl.push_str(&format!(" -H \"{}: {}\"", pair[0], value));
         }
     }
 
@@ -78,8 +178,9 @@ fn generate_request_data(args: &HttpRequestArgs, jar: &reqwest::cookie::Jar) ->
         if let Some(cookie_header) = jar.cookies(&url_parsed) {
             if let Ok(header_val) = cookie_header.to_str() {
                 if !header_val.is_empty() {
-                    request_raw.push_str(&format!("Cookie: {}\r\n", header_val));
-                    request_curl.push_str(&format!(" -H \"Cookie: {}\"", header_val));
+                    let value = header("Cookie", header_val);
+                    request_raw.push_str(&format!("Cookie: {}\r\n", value));
+                    request_curl.push_str(&format!(" -H \"Cookie: {}\"", value));
                 }
             }
         }
@@ -87,7 +188,7 @@ fn generate_request_data(args: &HttpRequestArgs, jar: &reqwest::cookie::Jar) ->
 
     if let Some(form_data) = &args.form_data {
         for item in form_data {
-            let mut f_arg = format!("{}={}", item.key, item.value);
+            let mut f_arg = format!("{}={}", item.key, text(&item.value));
             if let Some(ct) = &item.content_type {
                 f_arg.push_str(&format!(";type={}", ct));
             }
@@ -97,7 +198,8 @@ fn generate_request_data(args: &HttpRequestArgs, jar: &reqwest::cookie::Jar) ->
             .push_str("Content-Type: multipart/form-data; boundary=...\r\n\r\n[Multipart Body]");
     } else if let Some(body_content) = &args.body {
         request_raw.push_str(&format!("Content-Length: {}\r\n\r\n", body_content.len()));
-        request_raw.push_str(body_content);
+        let body_content = text(body_content);
+        request_raw.push_str(&body_content);
         request_curl.push_str(&format!(" -d '{}'", body_content.replace('\'', "'\\''")));
     } else {
         request_raw.push_str("\r\n");
@@ -106,9 +208,509 @@ fn generate_request_data(args: &HttpRequestArgs, jar: &reqwest::cookie::Jar) ->
     (request_raw, request_curl)
 }
 
+/// Per-request settings that need a dedicated `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    pub http_version: Option<String>,
+    pub unix_socket: Option<String>,
+    pub dns: DnsOverrides,
+    /// URL of the request, which DNS override entries are matched against
+    pub url: String,
+}
+
+impl ClientOptions {
+    pub fn from_args(args: &HttpRequestArgs) -> Self {
+        ClientOptions {
+            http_version: args.http_version.clone(),
+            unix_socket: args.unix_socket.clone(),
+            dns: args.dns.clone().unwrap_or_default(),
+            url: args.url.clone(),
+        }
+    }
+
+    /// Key of the cached client for these options, or None when the client is built for the
+    /// request alone. Socket paths and DNS overrides vary freely, so caching their clients
+    /// would grow the cache without bound; HTTP versions are a short, fixed list.
+    fn cache_key(&self, project_name: &str) -> Option<String> {
+        if self.unix_socket.is_some() || !self.dns.is_empty() {
+            return None;
+        }
+        Some(match &self.http_version {
+            Some(version) => format!("{}|{}", project_name, version),
+            None => project_name.to_string(),
+        })
+    }
+}
+
+/// Returns the project's client and cookie jar, creating them on first use.
+/// Each set of client options gets its own client, all sharing the project's cookie jar.
+/// Clients with a Unix socket or DNS overrides are not cached.
+pub async fn project_client(
+    state: &crate::HttpRequestState,
+    project_name: &str,
+    options: &ClientOptions,
+) -> Result<(reqwest::Client, Arc<reqwest::cookie::Jar>), String> {
+    let mut clients = state.clients.lock().await;
+    let mut jars = state.jars.lock().await;
+
+    let jar = jars
+        .entry(project_name.to_string())
+        .or_insert_with(|| Arc::new(reqwest::cookie::Jar::default()))
+        .clone();
+
+    let key = options.cache_key(project_name);
+    if let Some(client) = key.as_ref().and_then(|key| clients.get(key)) {
+        return Ok((client.clone(), jar));
+    }
+
+    // Automatic decompression stays off: bodies are decoded in `http_request`
+    // so the wire encoding and sizes can be reported.
+    let mut builder = reqwest::Client::builder()
+        .cookie_provider(Arc::clone(&jar))
+        .no_gzip()
+        .no_brotli()
+        .no_deflate()
+        .no_zstd();
+    builder = match options.http_version.as_deref() {
+        // ALPN offers h2 by default; the server may still pick HTTP/1.1
+        None | Some("http2") => builder,
+        Some("http1.0") | Some("http1.1") => builder.http1_only(),
+        Some("h2c") => builder.http2_prior_knowledge(),
+        Some(other) => return Err(format!("Unsupported HTTP version: {}", other)),
+    };
+    if let Some(socket) = &options.unix_socket {
+        builder = unix_socket(builder, socket)?;
+    }
+    if !options.dns.is_empty() {
+        builder = crate::dns::apply(builder, &options.dns, &options.url).await?;
+    }
+    let client = builder
+        .build()
+        .map_err(|e| format!("Failed to create client with cookie store: {}", e))?;
+    if let Some(key) = key {
+        clients.insert(key, client.clone());
+    }
+    Ok((client, jar))
+}
+
+#[cfg(unix)]
+fn unix_socket(
+    builder: reqwest::ClientBuilder,
+    path: &str,
+) -> Result<reqwest::ClientBuilder, String> {
+    Ok(builder.unix_socket(path))
+}
+
+#[cfg(not(unix))]
+fn unix_socket(
+    _builder: reqwest::ClientBuilder,
+    _path: &str,
+) -> Result<reqwest::ClientBuilder, String> {
+    Err("Unix sockets are not supported on this platform".to_string())
+}
+
+/// Label used on the request line of `request_raw` before the real version is known.
+fn requested_version_label(http_version: Option<&str>) -> &'static str {
+    match http_version {
+        Some("http1.0") => "HTTP/1.0",
+        Some("http2") | Some("h2c") => "HTTP/2",
+        _ => "HTTP/1.1",
+    }
+}
+
+pub fn version_label(version: reqwest::Version) -> String {
+    match version {
+        reqwest::Version::HTTP_09 => "HTTP/0.9".to_string(),
+        reqwest::Version::HTTP_10 => "HTTP/1.0".to_string(),
+        reqwest::Version::HTTP_11 => "HTTP/1.1".to_string(),
+        reqwest::Version::HTTP_2 => "HTTP/2".to_string(),
+        reqwest::Version::HTTP_3 => "HTTP/3".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Rewrite the version on the request line of a raw request (`METHOD URL VERSION`).
+pub fn with_http_version(request_raw: &str, version: &str) -> String {
+    match request_raw.split_once("\r\n") {
+        Some((line, rest)) => match line.rsplit_once(' ') {
+            Some((start, _)) => format!("{} {}\r\n{}", start, version, rest),
+            None => request_raw.to_string(),
+        },
+        None => request_raw.to_string(),
+    }
+}
+
+/// Build the reqwest request (headers, multipart form or body) described by `args`.
+/// Borrows `args` so the request can be rebuilt, e.g. to replay it after an auth challenge.
+pub async fn build_request(
+    client: &reqwest::Client,
+    method: Method,
+    args: &HttpRequestArgs,
+) -> Result<reqwest::RequestBuilder, String> {
+    let mut request_builder = client.request(method, &args.url);
+    if args.http_version.as_deref() == Some("http1.0") {
+        request_builder = request_builder.version(reqwest::Version::HTTP_10);
+    }
+
+    let mut header_map = reqwest::header::HeaderMap::new();
+    // Decompression is done by hand (see `decode`), so advertise it ourselves.
+    if !has_header(args, "accept-encoding") {
+        header_map.insert(
+            reqwest::header::ACCEPT_ENCODING,
+            reqwest::header::HeaderValue::from_static(crate::decode::ACCEPT_ENCODING),
+        );
+    }
+    for pair in &args.headers {
+        if pair.len() == 2 {
+            if let (Ok(name), Ok(value)) = (
+                reqwest::header::HeaderName::from_bytes(pair[0].as_bytes()),
+                reqwest::header::HeaderValue::from_bytes(pair[1].as_bytes()),
+            ) {
+                header_map.append(name, value);
+            }
+        }
+    }
+    request_builder = request_builder.headers(header_map);
+
+    if let Some(form_data) = &args.form_data {
+        let mut form = multipart::Form::new();
+        for item in form_data {
+            if item.entry_type == "file" {
+                let mut part = multipart::Part::bytes(
+                    tokio::fs::read(&item.value)
+                        .await
+                        .map_err(|e| format!("Failed to read file {}: {}", item.value, e))?,
+                )
+                .file_name(
+                    std::path::Path::new(&item.value)
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                );
+                if let Some(ct) = &item.content_type {
+                    if ct.parse::<reqwest::header::HeaderValue>().is_ok() {
+                        part = part.mime_str(ct.as_str()).unwrap();
+                    }
+                }
+                form = form.part(item.key.clone(), part);
+            } else {
+                form = form.text(item.key.clone(), item.value.clone());
+            }
+        }
+        request_builder = request_builder.multipart(form);
+    } else if let Some(body) = &args.body {
+        request_builder = request_builder.body(body.clone());
+    }
+
+    Ok(request_builder)
+}
+
+/// Snapshot of the active redactor, or `None` when the caller asked to reveal secrets.
+pub fn active_redactor(state: &crate::RedactionState, reveal_secrets: bool) -> Option<Redactor> {
+    if reveal_secrets {
+        return None;
+    }
+    // Like the log, a poisoned lock does not turn redaction off
+    let redactor = state.redactor.read().unwrap_or_else(|e| e.into_inner());
+    Some(redactor.clone())
+}
+
+/// Run a request through the project client, encoding GraphQL bodies first and applying the
+/// retry policy. Shared by `http_request` and the commands built on it.
+pub async fn execute_request(
+    app_handle: &tauri::AppHandle,
+    client: &reqwest::Client,
+    jar: &Arc<reqwest::cookie::Jar>,
+    tokens: &TokenCache,
+    project_name: &str,
+    redactor: Option<&Redactor>,
+    args: HttpRequestArgs,
+) -> Result<HttpResponse, String> {
+    let Some(policy) = args.retry.clone() else {
+        return execute_once(
+            app_handle,
+            client,
+            jar,
+            tokens,
+            project_name,
+            redactor,
+            args,
+        )
+        .await
+        .map_err(|e| e.message);
+    };
+
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempts = Vec::new();
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let started = std::time::Instant::now();
+        let result = execute_once(
+            app_handle,
+            client,
+            jar,
+            tokens,
+            project_name,
+            redactor,
+            args.clone(),
+        )
+        .await;
+        let time_ms = started.elapsed().as_millis() as u64;
+
+        let (retry, retry_after) = match &result {
+            Ok(response) => (
+                policy.retries_status(response.status),
+                response
+                    .headers
+                    .iter()
+                    .find(|h| h.len() == 2 && h[0].eq_ignore_ascii_case("retry-after"))
+                    .map(|h| h[1].clone()),
+            ),
+            Err(e) => (policy.retries_error(&args.method, e.kind), None),
+        };
+        let last = !retry || attempt >= max_attempts;
+        let delay = if last {
+            std::time::Duration::ZERO
+        } else {
+            policy.delay(attempt, retry_after.as_deref())
+        };
+        attempts.push(RetryAttempt {
+            attempt,
+            status: result.as_ref().ok().map(|r| r.status),
+            error: result.as_ref().err().map(|e| e.message.clone()),
+            error_kind: result.as_ref().err().and_then(|e| e.kind).map(String::from),
+            time_ms,
+            delay_ms: delay.as_millis() as u64,
+        });
+
+        if last {
+            return match result {
+                Ok(mut response) => {
+                    response.attempts = attempts;
+                    Ok(response)
+                }
+                Err(e) if attempt > 1 => Err(format!("{} (after {} attempts)", e.message, attempt)),
+                Err(e) => Err(e.message),
+            };
+        }
+        if !args.quiet {
+            let outcome = match &result {
+                Ok(response) => format!("status {}", response.status),
+                Err(e) => e.message.clone(),
+            };
+            crate::rust_warn!(
+                app_handle,
+                "Attempt {}/{} for {} failed ({}), retrying in {}ms",
+                attempt,
+                max_attempts,
+                args.url,
+                outcome,
+                delay.as_millis()
+            );
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+async fn execute_once(
+    app_handle: &tauri::AppHandle,
+    client: &reqwest::Client,
+    jar: &Arc<reqwest::cookie::Jar>,
+    tokens: &TokenCache,
+    project_name: &str,
+    redactor: Option<&Redactor>,
+    args: HttpRequestArgs,
+) -> Result<HttpResponse, SendError> {
+    let Some(graphql) = args.graphql.clone() else {
+        return send_request(
+            app_handle,
+            client,
+            jar,
+            tokens,
+            project_name,
+            redactor,
+            args,
+        )
+        .await;
+    };
+
+    let mut first = args.clone();
+    crate::graphql::encode(&mut first, &graphql, !graphql.persisted)?;
+    let response = send_request(
+        app_handle,
+        client,
+        jar,
+        tokens,
+        project_name,
+        redactor,
+        first,
+    )
+    .await?;
+    if graphql.persisted && crate::graphql::persisted_query_not_found(&response.body) {
+        // Unknown hash: register the query by sending it along with the hash
+        let mut retry = args;
+        crate::graphql::encode(&mut retry, &graphql, true)?;
+        return send_request(
+            app_handle,
+            client,
+            jar,
+            tokens,
+            project_name,
+            redactor,
+            retry,
+        )
+        .await;
+    }
+    Ok(response)
+}
+
+/// Send one request: backend auth, the digest round-trip, decompression and charset decoding.
+async fn send_request(
+    app_handle: &tauri::AppHandle,
+    client: &reqwest::Client,
+    jar: &Arc<reqwest::cookie::Jar>,
+    tokens: &TokenCache,
+    project_name: &str,
+    redactor: Option<&Redactor>,
+    mut args: HttpRequestArgs,
+) -> Result<HttpResponse, SendError> {
+    let method = Method::from_str(&args.method.to_uppercase())
+        .map_err(|e| format!("Invalid method: {}", e))?;
+
+    crate::auth::apply(app_handle, client, tokens, project_name, &mut args).await?;
+    let with_api_key = redactor.and_then(|r| crate::auth::with_api_key_name(r, &args));
+    let redactor = with_api_key.as_ref().or(redactor);
+
+    // Generate Raw Request and Curl (Best effort)
+    let (mut request_raw, mut request_curl) = generate_request_data(&args, jar, redactor);
+
+    // Errors show the URL: the logged copy also masks the API key name, which only this
+    // request knows about
+    let log_redactor = app_handle
+        .try_state::<crate::RedactionState>()
+        .and_then(|state| active_redactor(&state, false))
+        .and_then(|r| crate::auth::with_api_key_name(&r, &args));
+    let quiet = args.quiet;
+    let send_error = |e: reqwest::Error| {
+        let msg = format!("Request failed: {}", e);
+        if !quiet {
+            let logged = match &log_redactor {
+                Some(r) => r.text(&msg),
+                None => msg.clone(),
+            };
+            crate::rust_error!(app_handle, "{}", logged);
+        }
+        SendError {
+            kind: Some(crate::retry::error_kind(&e)),
+            message: msg,
+        }
+    };
+
+    let mut response = build_request(client, method.clone(), &args)
+        .await?
+        .send()
+        .await
+        .map_err(send_error)?;
+
+    // Digest auth: answer the 401 challenge and replay the request once
+    if let Some(authorization) = crate::auth::digest_retry(&args, &response)? {
+        crate::auth::set_header(&mut args.headers, "Authorization", authorization);
+        (request_raw, request_curl) = generate_request_data(&args, jar, redactor);
+        response = build_request(client, method, &args)
+            .await?
+            .send()
+            .await
+            .map_err(send_error)?;
+    }
+
+    let status = response.status().as_u16();
+    let remote_addr = response.remote_addr();
+    let http_version = version_label(response.version());
+    let request_raw = with_http_version(&request_raw, &http_version);
+
+    let mut headers: Vec<Vec<String>> = Vec::new();
+    for (key, value) in response.headers() {
+        if let Ok(v) = value.to_str() {
+            headers.push(vec![key.to_string(), v.to_string()]);
+        }
+    }
+
+    let header_str = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+    };
+    let content_encoding = header_str(reqwest::header::CONTENT_ENCODING);
+    let content_type = header_str(reqwest::header::CONTENT_TYPE);
+
+    let wire = response
+        .bytes()
+        .await
+        .map_err(|e| SendError {
+            kind: Some(crate::retry::error_kind(&e)),
+            message: format!("Failed to read body: {}", e),
+        })?
+        .to_vec();
+    let wire_size = wire.len();
+
+    let mut decode_warning = None;
+    let (decoded, decoded_size, body_base64) = if args.raw_body {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&wire);
+        (wire, None, Some(encoded))
+    } else {
+        match &content_encoding {
+            // An unknown or corrupt encoding still shows the body, as it came over the wire
+            Some(encoding) => match crate::decode::decompress(encoding, &wire) {
+                Ok(decoded) => {
+                    let size = decoded.len();
+                    (decoded, Some(size), None)
+                }
+                Err(msg) => {
+                    if !args.quiet {
+                        crate::rust_warn!(app_handle, "{}", msg);
+                    }
+                    decode_warning = Some(msg);
+                    (wire, None, None)
+                }
+            },
+            None => (wire, Some(wire_size), None),
+        }
+    };
+
+    let charset = args.charset.clone().or_else(|| {
+        content_type
+            .as_deref()
+            .and_then(crate::decode::charset_from_content_type)
+    });
+    let (body, charset) = crate::decode::decode_text(&decoded, charset.as_deref());
+
+    Ok(HttpResponse {
+        status,
+        headers,
+        body,
+        request_raw,
+        request_curl,
+        content_encoding,
+        wire_size,
+        decoded_size,
+        charset,
+        body_base64,
+        decode_warning,
+        http_version,
+        remote_ip: remote_addr.map(|addr| addr.ip().to_string()),
+        remote_port: remote_addr.map(|addr| addr.port()),
+        attempts: Vec::new(),
+    })
+}
+
 #[command]
 pub async fn reconstruct_request(
     state: tauri::State<'_, crate::HttpRequestState>,
+    redaction: tauri::State<'_, crate::RedactionState>,
     args: HttpRequestArgs,
 ) -> Result<(String, String), String> {
     let jar = {
@@ -119,15 +721,28 @@ pub async fn reconstruct_request(
             .unwrap_or_else(|| Arc::new(reqwest::cookie::Jar::default()))
     };
 
-    Ok(generate_request_data(&args, &jar))
+    let mut args = args;
+    let p_name = args
+        .project_name
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    crate::dns::merge_project(&state, &p_name, &mut args).await;
+    if let Some(graphql) = args.graphql.clone() {
+        crate::graphql::encode(&mut args, &graphql, !graphql.persisted)?;
+    }
+
+    let redactor = active_redactor(&redaction, args.reveal_secrets);
+    Ok(generate_request_data(&args, &jar, redactor.as_ref()))
 }
 
 #[command]
 pub async fn http_request(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::HttpRequestState>,
+    redaction: tauri::State<'_, crate::RedactionState>,
     args: HttpRequestArgs,
 ) -> Result<HttpResponse, String> {
+    let redactor = active_redactor(&redaction, args.reveal_secrets);
     let (tx, rx) = oneshot::channel::<()>();
     let request_id = args.request_id.clone();
 
@@ -136,111 +751,26 @@ pub async fn http_request(
         handles.insert(id.clone(), tx);
     }
 
-    let (client, jar) = {
-        let mut clients = state.clients.lock().await;
-        let mut jars = state.jars.lock().await;
-        let p_name = args
-            .project_name
-            .clone()
-            .unwrap_or_else(|| "default".to_string());
-        if !clients.contains_key(&p_name) {
-            let jar = Arc::new(reqwest::cookie::Jar::default());
-            let new_client = reqwest::Client::builder()
-                .cookie_provider(Arc::clone(&jar))
-                .build()
-                .map_err(|e| format!("Failed to create client with cookie store: {}", e))?;
-            clients.insert(p_name.clone(), new_client);
-            jars.insert(p_name.clone(), jar);
-        }
-        (
-            clients.get(&p_name).unwrap().clone(),
-            jars.get(&p_name).unwrap().clone(),
-        )
-    };
+    let p_name = args
+        .project_name
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let tokens = Arc::clone(&state.tokens);
+    let mut args = args;
+    crate::dns::merge_project(&state, &p_name, &mut args).await;
+    let (client, jar) = project_client(&state, &p_name, &ClientOptions::from_args(&args)).await?;
 
     let request_future = async move {
-        let method = Method::from_str(&args.method.to_uppercase())
-            .map_err(|e| format!("Invalid method: {}", e))?;
-
-        // Generate Raw Request and Curl (Best effort) - Generate these before we move fields out of args
-        let (request_raw, request_curl) = generate_request_data(&args, &jar);
-
-        let mut request_builder = client.request(method, &args.url);
-
-        let mut header_map = reqwest::header::HeaderMap::new();
-        for pair in args.headers {
-            if pair.len() == 2 {
-                if let (Ok(name), Ok(value)) = (
-                    reqwest::header::HeaderName::from_bytes(pair[0].as_bytes()),
-                    reqwest::header::HeaderValue::from_bytes(pair[1].as_bytes()),
-                ) {
-                    header_map.append(name, value);
-                }
-            }
-        }
-        request_builder = request_builder.headers(header_map);
-
-        if let Some(form_data) = args.form_data {
-            let mut form = multipart::Form::new();
-            for item in form_data {
-                if item.entry_type == "file" {
-                    let mut part = multipart::Part::bytes(
-                        tokio::fs::read(&item.value)
-                            .await
-                            .map_err(|e| format!("Failed to read file {}: {}", item.value, e))?,
-                    )
-                    .file_name(
-                        std::path::Path::new(&item.value)
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .to_string(),
-                    );
-                    if let Some(ct) = item.content_type {
-                        if ct.parse::<reqwest::header::HeaderValue>().is_ok() {
-                            part = part.mime_str(ct.as_str()).unwrap();
-                        }
-                    }
-                    form = form.part(item.key, part);
-                } else {
-                    form = form.text(item.key, item.value);
-                }
-            }
-            request_builder = request_builder.multipart(form);
-        } else if let Some(body) = args.body {
-            request_builder = request_builder.body(body);
-        }
-
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| {
-                let msg = format!("Request failed: {}", e);
-                crate::rust_error!(&app_handle, "{}", msg);
-                msg
-            })?;
-
-        let status = response.status().as_u16();
-
-        let mut headers: Vec<Vec<String>> = Vec::new();
-        for (key, value) in response.headers() {
-            if let Ok(v) = value.to_str() {
-                headers.push(vec![key.to_string(), v.to_string()]);
-            }
-        }
-
-        let body = response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read body: {}", e))?;
-
-        Ok(HttpResponse {
-            status,
-            headers,
-            body,
-            request_raw,
-            request_curl,
-        })
+        execute_request(
+            &app_handle,
+            &client,
+            &jar,
+            &tokens,
+            &p_name,
+            redactor.as_ref(),
+            args,
+        )
+        .await
     };
 
     let result = tokio::select! {
@@ -268,6 +798,94 @@ pub async fn cancel_http_request(
     Ok(())
 }
 
+#[command]
+pub async fn get_redaction_config(
+    state: tauri::State<'_, crate::RedactionState>,
+) -> Result<RedactionConfig, String> {
+    let redactor = state.redactor.read().map_err(|e| e.to_string())?;
+    Ok(redactor.config().clone())
+}
+
+#[command]
+pub async fn set_redaction_config(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::RedactionState>,
+    config: RedactionConfig,
+) -> Result<(), String> {
+    let redactor = Redactor::new(config).map_err(|msg| {
+        crate::rust_error!(&app_handle, "{}", msg);
+        msg
+    })?;
+    let home_dir = app_handle.path().home_dir().map_err(|e| e.to_string())?;
+    let path = crate::redact::config_path(&home_dir);
+    crate::redact::save_config(&path, redactor.config()).map_err(|msg| {
+        crate::rust_error!(&app_handle, "{}", msg);
+        msg
+    })?;
+    *state.redactor.write().map_err(|e| e.to_string())? = redactor;
+    Ok(())
+}
+
+#[command]
+pub async fn oauth2_get_token(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::HttpRequestState>,
+    project_name: Option<String>,
+    config: OAuth2Config,
+) -> Result<OAuth2Token, String> {
+    let p_name = project_name.unwrap_or_else(|| "default".to_string());
+    let (client, _) = project_client(&state, &p_name, &ClientOptions::default()).await?;
+
+    // Explicit request from the UI: always run the grant instead of returning the cached token.
+    let token = crate::oauth::fetch_token(&app_handle, &client, &config)
+        .await
+        .map_err(|msg| {
+            crate::rust_error!(&app_handle, "{}", msg);
+            msg
+        })?;
+    state
+        .tokens
+        .insert(config.cache_key(&p_name), token.clone())
+        .await;
+    Ok(token)
+}
+
+#[command]
+pub async fn oauth2_clear_tokens(
+    state: tauri::State<'_, crate::HttpRequestState>,
+    project_name: Option<String>,
+) -> Result<(), String> {
+    state.tokens.clear(project_name.as_deref()).await;
+    Ok(())
+}
+
+#[command]
+pub async fn get_dns_overrides(
+    state: tauri::State<'_, crate::HttpRequestState>,
+    project_name: String,
+) -> Result<DnsOverrides, String> {
+    let dns = state.dns.lock().await;
+    Ok(dns.get(&project_name).cloned().unwrap_or_default())
+}
+
+/// Set the host resolution overrides applied to every request of a project.
+#[command]
+pub async fn set_dns_overrides(
+    state: tauri::State<'_, crate::HttpRequestState>,
+    project_name: String,
+    overrides: DnsOverrides,
+) -> Result<(), String> {
+    // Fail early on malformed entries rather than on the next request
+    overrides.validate()?;
+    let mut dns = state.dns.lock().await;
+    if overrides.is_empty() {
+        dns.remove(&project_name);
+    } else {
+        dns.insert(project_name, overrides);
+    }
+    Ok(())
+}
+
 #[command]
 pub fn git_init(app_handle: tauri::AppHandle, path: String) -> Result<String, String> {
     Repository::init(&path).map_err(|e| {
@@ -562,6 +1180,23 @@ pub async fn save_workspace(app_handle: tauri::AppHandle, path: String, data: St
     Ok(())
 }
 
+/// Save a collection file, masking secrets in the responses kept with its executions.
+#[command]
+pub async fn save_collection(
+    app_handle: tauri::AppHandle,
+    redaction: tauri::State<'_, crate::RedactionState>,
+    path: String,
+    data: String,
+) -> Result<(), String> {
+    let mut collection: serde_json::Value =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid collection data: {}", e))?;
+    if let Some(redactor) = active_redactor(&redaction, false) {
+        crate::redact::collection_history(&redactor, &mut collection);
+    }
+    let data = serde_json::to_string_pretty(&collection).map_err(|e| e.to_string())?;
+    save_workspace(app_handle, path, data).await
+}
+
 #[command]
 pub async fn load_workspace(app_handle: tauri::AppHandle, path: String) -> Result<String, String> {
     let data = fs::read_to_string(&path).await.map_err(|e| {
@@ -581,6 +1216,8 @@ pub struct UseCaseStep {
     pub success_codes: String,
     #[serde(default)]
     pub script: Option<String>,
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
 }
 
 fn default_success_codes() -> String {
@@ -794,158 +1431,6 @@ pub async fn delete_project(app_handle: tauri::AppHandle, name: String) -> Resul
     Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct MockResponseDefinition {
-    pub status_code: u16,
-    pub headers: Vec<Vec<String>>,
-    pub body: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct MockRequestDefinition {
-    pub method: String,
-    pub path: String,
-    pub response: MockResponseDefinition,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StartMockArgs {
-    pub collection_id: String,
-    pub port: u16,
-    pub requests: Vec<MockRequestDefinition>,
-}
-
-#[command]
-pub async fn start_mock_server(
-    state: tauri::State<'_, MockServerState>,
-    args: StartMockArgs,
-) -> Result<(), String> {
-    let mut handles = state.handles.lock().await;
-    if let Some(tx) = handles.remove(&args.collection_id) {
-        let _ = tx.send(());
-    }
-
-    let mock_data = Arc::new(args.requests);
-
-    let app = Router::new().fallback(any(move |method: HttpMethod, uri: axum::http::Uri| {
-        let mock_data = Arc::clone(&mock_data);
-        async move {
-            let target_path = uri.path();
-            let target_method = method.as_str().to_uppercase();
-            let target_full_uri = uri
-                .path_and_query()
-                .map(|pq| pq.as_str())
-                .unwrap_or(target_path);
-
-            // Debug logs
-            eprintln!(
-                "Mock Server: Received {} {}",
-                target_method, target_full_uri
-            );
-
-            // Helper: check if a mock path pattern matches a request path.
-            // Segments wrapped in `{…}` are treated as wildcards that match any value.
-            let path_matches = |pattern: &str, actual: &str| -> bool {
-                let pat_segments: Vec<&str> = pattern.split('/').collect();
-                let act_segments: Vec<&str> = actual.split('/').collect();
-                if pat_segments.len() != act_segments.len() {
-                    return false;
-                }
-                pat_segments
-                    .iter()
-                    .zip(act_segments.iter())
-                    .all(|(p, a)| (p.starts_with('{') && p.ends_with('}')) || p == a)
-            };
-
-            // Normalise a stored mock path so that it always starts with '/'.
-            let norm = |p: &str| -> String {
-                if p.starts_with('/') {
-                    p.to_string()
-                } else {
-                    format!("/{}", p)
-                }
-            };
-
-            // Pass 1: Try exact match (Method + full URI including query string)
-            let exact_match = mock_data.iter().find(|m| {
-                let m_path = norm(&m.path);
-                m.method.to_uppercase() == target_method && path_matches(&m_path, target_full_uri)
-            });
-
-            let matching = if let Some(m) = exact_match {
-                eprintln!("Mock Server: Matched Exact: {}", m.path);
-                Some(m)
-            } else {
-                // Pass 2: Fallback to path-only match (Method + Path), ignoring query params in request
-                // BUT only if the mock definition ITSELF doesn't have a query string (is generic)
-                let fuzzy_match = mock_data.iter().find(|m| {
-                    let m_path = norm(&m.path);
-                    !m_path.contains('?')
-                        && m.method.to_uppercase() == target_method
-                        && path_matches(&m_path, target_path)
-                });
-                if let Some(m) = fuzzy_match {
-                    eprintln!("Mock Server: Matched Generic: {}", m.path);
-                    Some(m)
-                } else {
-                    eprintln!("Mock Server: No match found");
-                    None
-                }
-            };
-
-            if let Some(m) = matching {
-                let mut hm = HeaderMap::new();
-                for pair in &m.response.headers {
-                    if pair.len() == 2 {
-                        if let (Ok(name), Ok(val)) = (
-                            axum::http::HeaderName::from_bytes(pair[0].as_bytes()),
-                            axum::http::HeaderValue::from_bytes(pair[1].as_bytes()),
-                        ) {
-                            hm.append(name, val);
-                        }
-                    }
-                }
-
-                let status = StatusCode::from_u16(m.response.status_code).unwrap_or(StatusCode::OK);
-                (status, hm, m.response.body.clone()).into_response()
-            } else {
-                StatusCode::NOT_FOUND.into_response()
-            }
-        }
-    }));
-
-    let (tx, rx) = oneshot::channel::<()>();
-    handles.insert(args.collection_id.clone(), tx);
-
-    let addr = format!("0.0.0.0:{}", args.port);
-    let listener = TcpListener::bind(&addr).await.map_err(|e| e.to_string())?;
-
-    tokio::spawn(async move {
-        let server = axum::serve(listener, app).with_graceful_shutdown(async move {
-            let _ = rx.await;
-        });
-        if let Err(e) = server.await {
-            eprintln!("Mock server error: {}", e);
-        }
-    });
-
-    Ok(())
-}
-
-#[command]
-pub async fn stop_mock_server(
-    state: tauri::State<'_, MockServerState>,
-    collection_id: String,
-) -> Result<(), String> {
-    let mut handles = state.handles.lock().await;
-    if let Some(tx) = handles.remove(&collection_id) {
-        let _ = tx.send(());
-        Ok(())
-    } else {
-        Err("No mock server running for this collection".into())
-    }
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateInfo {
     pub is_available: bool,
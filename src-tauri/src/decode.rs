@@ -0,0 +1,104 @@
+use std::io::Read;
+
+/// Value sent as `Accept-Encoding` when the request does not set one.
+pub const ACCEPT_ENCODING: &str = "gzip, deflate, br, zstd";
+
+/// Largest body `decompress` produces, so that a small compressed body cannot fill memory.
+pub const MAX_DECODED_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Undo the `Content-Encoding` of a response body. Encodings are applied in the order
+/// listed, so they are removed in reverse.
+pub fn decompress(content_encoding: &str, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    decompress_capped(content_encoding, bytes, MAX_DECODED_SIZE)
+}
+
+fn decompress_capped(content_encoding: &str, bytes: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+    let mut data = bytes.to_vec();
+    for encoding in content_encoding.split(',').rev() {
+        let encoding = encoding.trim().to_lowercase();
+        // One byte over the limit tells a body at the limit from a larger one
+        let read = |reader: &mut dyn Read| {
+            let mut out = Vec::new();
+            reader.take(limit + 1).read_to_end(&mut out).map(|_| out)
+        };
+        let result = match encoding.as_str() {
+            "" | "identity" => continue,
+            "gzip" | "x-gzip" => read(&mut flate2::read::MultiGzDecoder::new(&data[..])),
+            // "deflate" is supposed to be zlib-wrapped, but some servers send raw deflate.
+            "deflate" => read(&mut flate2::read::ZlibDecoder::new(&data[..]))
+                .or_else(|_| read(&mut flate2::read::DeflateDecoder::new(&data[..]))),
+            "br" => read(&mut brotli::Decompressor::new(&data[..], 4096)),
+            "zstd" => zstd::stream::read::Decoder::new(&data[..])
+                .and_then(|mut decoder| read(&mut decoder)),
+            other => return Err(format!("Unsupported Content-Encoding: {}", other)),
+        };
+        let out = result.map_err(|e| format!("Failed to decode {} body: {}", encoding, e))?;
+        if out.len() as u64 > limit {
+            return Err(format!(
+                "Failed to decode {} body: larger than {} bytes once decoded",
+                encoding, limit
+            ));
+        }
+        data = out;
+    }
+    Ok(data)
+}
+
+/// Extract the `charset` parameter from a Content-Type header value.
+pub fn charset_from_content_type(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case("charset") {
+            Some(value.trim().trim_matches('"').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Decode body bytes to text with the given charset label (falling back to UTF-8 when
+/// missing or unknown). A BOM takes precedence, as browsers do.
+/// Returns the text and the name of the encoding actually used.
+pub fn decode_text(bytes: &[u8], charset: Option<&str>) -> (String, String) {
+    let encoding = charset
+        .and_then(|label| encoding_rs::Encoding::for_label(label.trim().as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    let (text, used, _had_errors) = encoding.decode(bytes);
+    (text.into_owned(), used.name().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn removes_stacked_encodings_in_reverse() {
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(b"hello").unwrap();
+        let gzipped = gzip.finish().unwrap();
+        let both = zstd::encode_all(&gzipped[..], 0).unwrap();
+        assert_eq!(decompress("gzip, zstd", &both).unwrap(), b"hello");
+        assert_eq!(decompress("identity", b"plain").unwrap(), b"plain");
+    }
+
+    #[test]
+    fn unknown_or_corrupt_encodings_fail() {
+        assert_eq!(
+            decompress("compress", b"x").unwrap_err(),
+            "Unsupported Content-Encoding: compress"
+        );
+        assert!(decompress("gzip", b"not gzip")
+            .unwrap_err()
+            .starts_with("Failed to decode gzip body"));
+    }
+
+    #[test]
+    fn decoded_bodies_are_capped() {
+        let zeros = zstd::encode_all(&[0u8; 1000][..], 0).unwrap();
+        assert_eq!(decompress_capped("zstd", &zeros, 1000).unwrap().len(), 1000);
+        assert!(decompress_capped("zstd", &zeros, 999)
+            .unwrap_err()
+            .contains("larger than 999 bytes"));
+    }
+}
@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+
+/// Host resolution overrides, written like curl's flags. They apply to the requests sent
+/// through `project_client` (HTTP, SSE, GraphQL, load tests and batch runs); WebSocket and gRPC
+/// connections resolve hosts normally.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DnsOverrides {
+    /// `host:port:addr[,addr]...` as for `--resolve`
+    #[serde(default)]
+    pub resolve: Vec<String>,
+    /// `host:port:connect_host:connect_port` as for `--connect-to`
+    #[serde(default)]
+    pub connect_to: Vec<String>,
+}
+
+impl DnsOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.resolve.is_empty() && self.connect_to.is_empty()
+    }
+
+    /// Project entries first, so the request's own entries win for the same host.
+    pub fn merged(project: Option<&DnsOverrides>, request: Option<&DnsOverrides>) -> Self {
+        let mut merged = project.cloned().unwrap_or_default();
+        if let Some(request) = request {
+            merged.resolve.extend(request.resolve.iter().cloned());
+            merged.connect_to.extend(request.connect_to.iter().cloned());
+        }
+        merged
+    }
+
+    /// `--resolve` / `--connect-to` flags for the cURL export.
+    pub fn curl_flags(&self) -> String {
+        let mut flags = String::new();
+        for entry in &self.resolve {
+            flags.push_str(&format!(" --resolve \"{}\"", entry));
+        }
+        for entry in &self.connect_to {
+            flags.push_str(&format!(" --connect-to \"{}\"", entry));
+        }
+        flags
+    }
+}
+
+/// Split `a:b:c` on colons that are not inside `[...]` (IPv6 literals).
+fn split_fields(entry: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut bracket = false;
+    for c in entry.chars() {
+        match c {
+            '[' => bracket = true,
+            ']' => bracket = false,
+            ':' if !bracket => {
+                fields.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(field) = fields.last_mut() {
+            field.push(c);
+        }
+    }
+    fields
+}
+
+fn parse_port(value: &str, entry: &str) -> Result<u16, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid port \"{}\" in {}", value, entry))
+}
+
+fn parse_ip(value: &str, entry: &str) -> Result<IpAddr, String> {
+    value
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .map_err(|_| format!("Invalid address \"{}\" in {}", value, entry))
+}
+
+/// A `--resolve` entry: `host:port` goes to one of `addrs`.
+struct Resolve {
+    host: String,
+    port: u16,
+    addrs: Vec<IpAddr>,
+}
+
+fn parse_resolve(entry: &str) -> Result<Resolve, String> {
+    let fields = split_fields(entry);
+    if fields.len() != 3 || fields[0].is_empty() {
+        return Err(format!(
+            "Invalid resolve entry \"{}\", expected host:port:addr",
+            entry
+        ));
+    }
+    Ok(Resolve {
+        host: fields[0].clone(),
+        port: parse_port(&fields[1], entry)?,
+        addrs: fields[2]
+            .split(',')
+            .map(|addr| parse_ip(addr.trim(), entry))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// A `--connect-to` entry: `host:port`, any port when None, is reached through
+/// `target:target_port`, the URL's port when 0.
+struct ConnectTo {
+    host: String,
+    port: Option<u16>,
+    target: String,
+    target_port: u16,
+}
+
+fn parse_connect_to(entry: &str) -> Result<ConnectTo, String> {
+    let fields = split_fields(entry);
+    if fields.len() != 4 {
+        return Err(format!(
+            "Invalid connect-to entry \"{}\", expected host:port:connect_host:connect_port",
+            entry
+        ));
+    }
+    if fields[0].is_empty() {
+        return Err(format!(
+            "connect-to entry \"{}\" needs a source host",
+            entry
+        ));
+    }
+    let port = |value: &str| match value {
+        "" => Ok(None),
+        value => parse_port(value, entry).map(Some),
+    };
+    Ok(ConnectTo {
+        host: fields[0].clone(),
+        port: port(&fields[1])?,
+        target: if fields[2].is_empty() {
+            fields[0].clone()
+        } else {
+            fields[2].clone()
+        },
+        target_port: port(&fields[3])?.unwrap_or(0),
+    })
+}
+
+impl DnsOverrides {
+    /// Check every entry, as done when the project's overrides are saved.
+    pub fn validate(&self) -> Result<(), String> {
+        for entry in &self.resolve {
+            parse_resolve(entry)?;
+        }
+        for entry in &self.connect_to {
+            parse_connect_to(entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// Apply the entries matching the host and port of `url` to a client builder; later entries
+/// win. reqwest overrides resolution per host name and always connects to a port written in
+/// the URL, so a `--connect-to` moving such a port elsewhere is refused rather than ignored.
+pub async fn apply(
+    mut builder: reqwest::ClientBuilder,
+    overrides: &DnsOverrides,
+    url: &str,
+) -> Result<reqwest::ClientBuilder, String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let host = url.host_str().unwrap_or_default();
+    let port = url.port_or_known_default().unwrap_or(0);
+    let matches = |entry_host: &str| entry_host.eq_ignore_ascii_case(host);
+
+    for entry in &overrides.resolve {
+        let resolve = parse_resolve(entry)?;
+        if matches(&resolve.host) && resolve.port == port {
+            // Port 0 keeps the port of the URL, as curl does
+            let addrs: Vec<SocketAddr> = resolve
+                .addrs
+                .iter()
+                .map(|ip| SocketAddr::new(*ip, 0))
+                .collect();
+            builder = builder.resolve_to_addrs(host, &addrs);
+        }
+    }
+
+    for entry in &overrides.connect_to {
+        let connect_to = parse_connect_to(entry)?;
+        if !matches(&connect_to.host) || connect_to.port.is_some_and(|p| p != port) {
+            continue;
+        }
+        if url.port().is_some() && connect_to.target_port != 0 && connect_to.target_port != port {
+            return Err(format!(
+                "connect-to entry \"{}\" cannot move port {} written in the URL, \
+                 leave the port out of the URL or of the entry",
+                entry, port
+            ));
+        }
+        let target = connect_to.target.as_str();
+        let addrs: Vec<SocketAddr> = match parse_ip(target, entry) {
+            Ok(ip) => vec![SocketAddr::new(ip, connect_to.target_port)],
+            Err(_) => tokio::net::lookup_host((target, connect_to.target_port))
+                .await
+                .map_err(|e| format!("Failed to resolve {} for connect-to: {}", target, e))?
+                .collect(),
+        };
+        builder = builder.resolve_to_addrs(host, &addrs);
+    }
+
+    Ok(builder)
+}
+
+/// Fold the project's overrides into `args.dns` so the client and the cURL export see both.
+pub async fn merge_project(
+    state: &crate::HttpRequestState,
+    project_name: &str,
+    args: &mut crate::commands::HttpRequestArgs,
+) {
+    let dns = state.dns.lock().await;
+    let merged = DnsOverrides::merged(dns.get(project_name), args.dns.as_ref());
+    args.dns = (!merged.is_empty()).then_some(merged);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// A local server answering "ok" to every request, and its port.
+    async fn server() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                    )
+                    .await;
+            }
+        });
+        port
+    }
+
+    async fn get(overrides: DnsOverrides, url: &str) -> Result<String, String> {
+        let builder = apply(reqwest::Client::builder(), &overrides, url).await?;
+        let client = builder.build().map_err(|e| e.to_string())?;
+        let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+        response.text().await.map_err(|e| e.to_string())
+    }
+
+    fn resolve(entry: String) -> DnsOverrides {
+        DnsOverrides {
+            resolve: vec![entry],
+            ..Default::default()
+        }
+    }
+
+    fn connect_to(entry: String) -> DnsOverrides {
+        DnsOverrides {
+            connect_to: vec![entry],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_matches_host_and_port() {
+        let port = server().await;
+        let url = format!("http://api.invalid:{}/", port);
+        let entry = |p: u16| resolve(format!("API.invalid:{}:127.0.0.1", p));
+        assert_eq!(get(entry(port), &url).await.unwrap(), "ok");
+        assert!(get(entry(port + 1), &url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn connect_to_matches_host_and_port() {
+        let port = server().await;
+        let entry = |source: &str| connect_to(format!("{}:127.0.0.1:{}", source, port));
+        assert_eq!(
+            get(entry("api.invalid:"), "http://api.invalid/")
+                .await
+                .unwrap(),
+            "ok"
+        );
+        assert_eq!(
+            get(entry("api.invalid:80"), "http://api.invalid/")
+                .await
+                .unwrap(),
+            "ok"
+        );
+        assert!(get(entry("api.invalid:8080"), "http://api.invalid/")
+            .await
+            .is_err());
+        assert!(get(entry("other.invalid:"), "http://api.invalid/")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn connect_to_cannot_move_a_port_written_in_the_url() {
+        let port = server().await;
+        let url = format!("http://api.invalid:{}/", port);
+        // Same port: nothing to move
+        let same = connect_to(format!("api.invalid::127.0.0.1:{}", port));
+        assert_eq!(get(same, &url).await.unwrap(), "ok");
+        let keep = connect_to("api.invalid::127.0.0.1:".to_string());
+        assert_eq!(get(keep, &url).await.unwrap(), "ok");
+
+        let moved = connect_to(format!("api.invalid::127.0.0.1:{}", port));
+        let error = get(moved, "http://api.invalid:8080/").await.unwrap_err();
+        assert!(error.contains("cannot move port 8080"), "{}", error);
+    }
+
+    #[test]
+    fn validate_rejects_malformed_entries() {
+        assert!(resolve("api.invalid:80:127.0.0.1".to_string())
+            .validate()
+            .is_ok());
+        assert!(resolve("api.invalid:http:127.0.0.1".to_string())
+            .validate()
+            .is_err());
+        assert!(connect_to(":80:127.0.0.1:8080".to_string())
+            .validate()
+            .is_err());
+        assert!(connect_to("api.invalid:80:127.0.0.1".to_string())
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,391 @@
+use crate::commands::{
+    active_redactor, execute_request, project_client, ClientOptions, HttpRequestArgs,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tauri::command;
+
+/// Standard introspection query, as sent by GraphiQL.
+const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives { name description locations args { ...InputValue } }
+  }
+}
+fragment FullType on __Type {
+  kind name description
+  fields(includeDeprecated: true) {
+    name description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason }
+  possibleTypes { ...TypeRef }
+}
+fragment InputValue on __InputValue { name description type { ...TypeRef } defaultValue }
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name
+    ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+}"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphQLBody {
+    pub query: String,
+    /// JSON object, or the raw JSON text from the editor
+    #[serde(default)]
+    pub variables: Option<Value>,
+    #[serde(default)]
+    pub operation_name: Option<String>,
+    /// Send only the query's SHA-256 hash (Apollo automatic persisted queries), in the query
+    /// string of a GET or the body of a POST; the full query is sent only if the server does
+    /// not know the hash yet.
+    #[serde(default)]
+    pub persisted: bool,
+}
+
+fn variables(graphql: &GraphQLBody) -> Result<Option<Value>, String> {
+    match &graphql.variables {
+        Some(Value::String(text)) if text.trim().is_empty() => Ok(None),
+        Some(Value::String(text)) => serde_json::from_str(text)
+            .map(Some)
+            .map_err(|e| format!("GraphQL variables are not valid JSON: {}", e)),
+        Some(Value::Null) | None => Ok(None),
+        Some(value) => Ok(Some(value.clone())),
+    }
+}
+
+fn persisted_extension(query: &str) -> Value {
+    json!({
+        "persistedQuery": {
+            "version": 1,
+            "sha256Hash": hex::encode(Sha256::digest(query.as_bytes())),
+        }
+    })
+}
+
+/// Kind of the operation the request runs ("query", "mutation" or "subscription"): the one
+/// named `operation_name`, else the first one in the document.
+fn operation_kind(document: &str, operation_name: Option<&str>) -> String {
+    // Top-level keywords and names, skipping comments, strings and everything nested
+    let mut operations: Vec<(String, Option<String>)> = Vec::new();
+    let mut depth = 0usize;
+    let mut chars = document.chars().peekable();
+    let mut expecting_name = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '(' | '[' => {
+                if depth == 0 && c == '{' && !expecting_name && operations.is_empty() {
+                    // `{ ... }` shorthand for an anonymous query
+                    operations.push(("query".to_string(), None));
+                }
+                expecting_name = false;
+                depth += 1;
+            }
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            c if depth == 0 && (c.is_ascii_alphabetic() || c == '_') => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_ascii_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "query" | "mutation" | "subscription" | "fragment" => {
+                        operations.push((word, None));
+                        expecting_name = true;
+                    }
+                    _ if expecting_name => {
+                        if let Some(last) = operations.last_mut() {
+                            last.1 = Some(word);
+                        }
+                        expecting_name = false;
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut operations = operations
+        .into_iter()
+        .filter(|(kind, _)| kind != "fragment");
+    let operation = match operation_name {
+        Some(name) => operations.find(|(_, n)| n.as_deref() == Some(name)),
+        None => operations.next(),
+    };
+    operation
+        .map(|(kind, _)| kind)
+        .unwrap_or_else(|| "query".to_string())
+}
+
+/// Encode the operation into `args`. Queries sent with GET use the query string, anything else
+/// a JSON body, with mutations turned into POST requests. Persisted queries add their hash as
+/// the `persistedQuery` extension. `include_query` is false only for the first persisted attempt.
+pub fn encode(
+    args: &mut HttpRequestArgs,
+    graphql: &GraphQLBody,
+    include_query: bool,
+) -> Result<(), String> {
+    let variables = variables(graphql)?;
+    args.graphql = None;
+    args.form_data = None;
+
+    if !crate::commands::has_header(args, "accept") {
+        args.headers.push(vec![
+            "Accept".to_string(),
+            "application/graphql-response+json, application/json".to_string(),
+        ]);
+    }
+
+    // Mutations must not be sent with GET, which caches and proxies may replay
+    let use_get = args.method.eq_ignore_ascii_case("GET")
+        && operation_kind(&graphql.query, graphql.operation_name.as_deref()) != "mutation";
+    if use_get {
+        let mut url = reqwest::Url::parse(&args.url).map_err(|e| format!("Invalid URL: {}", e))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            if include_query {
+                pairs.append_pair("query", &graphql.query);
+            }
+            if let Some(name) = &graphql.operation_name {
+                pairs.append_pair("operationName", name);
+            }
+            if let Some(variables) = &variables {
+                pairs.append_pair("variables", &variables.to_string());
+            }
+            if graphql.persisted {
+                pairs.append_pair(
+                    "extensions",
+                    &persisted_extension(&graphql.query).to_string(),
+                );
+            }
+        }
+        args.method = "GET".to_string();
+        args.url = url.to_string();
+        args.body = None;
+        return Ok(());
+    }
+
+    let mut payload = json!({});
+    if include_query {
+        payload["query"] = json!(graphql.query);
+    }
+    if let Some(name) = &graphql.operation_name {
+        payload["operationName"] = json!(name);
+    }
+    if let Some(variables) = variables {
+        payload["variables"] = variables;
+    }
+    if graphql.persisted {
+        payload["extensions"] = persisted_extension(&graphql.query);
+    }
+    if args.method.eq_ignore_ascii_case("GET") {
+        args.method = "POST".to_string();
+    }
+    if !crate::commands::has_header(args, "content-type") {
+        args.headers.push(vec![
+            "Content-Type".to_string(),
+            "application/json".to_string(),
+        ]);
+    }
+    args.body = Some(payload.to_string());
+    Ok(())
+}
+
+/// Whether the server rejected a persisted query because it has not seen the hash yet.
+pub fn persisted_query_not_found(body: &str) -> bool {
+    let Ok(response) = serde_json::from_str::<Value>(body) else {
+        return false;
+    };
+    let Some(errors) = response.get("errors").and_then(Value::as_array) else {
+        return false;
+    };
+    errors.iter().any(|error| {
+        error.get("message").and_then(Value::as_str) == Some("PersistedQueryNotFound")
+            || error.pointer("/extensions/code").and_then(Value::as_str)
+                == Some("PERSISTED_QUERY_NOT_FOUND")
+    })
+}
+
+/// Fetch the schema of a GraphQL endpoint with an introspection query, using the same client,
+/// cookies and auth as `http_request`. Results are cached per project and URL until `refresh`.
+#[command]
+pub async fn graphql_introspect(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::HttpRequestState>,
+    redaction: tauri::State<'_, crate::RedactionState>,
+    args: HttpRequestArgs,
+    refresh: Option<bool>,
+) -> Result<Value, String> {
+    let p_name = args
+        .project_name
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let cache_key = format!("{}|{}", p_name, args.url);
+    if !refresh.unwrap_or(false) {
+        if let Some(schema) = state.schemas.lock().await.get(&cache_key) {
+            return Ok(schema.clone());
+        }
+    }
+
+    let redactor = active_redactor(&redaction, args.reveal_secrets);
+    let tokens = Arc::clone(&state.tokens);
+    let mut args = args;
+    crate::dns::merge_project(&state, &p_name, &mut args).await;
+    let (client, jar) = project_client(&state, &p_name, &ClientOptions::from_args(&args)).await?;
+
+    args.method = "POST".to_string();
+    args.body = None;
+    args.raw_body = false;
+    args.graphql = Some(GraphQLBody {
+        query: INTROSPECTION_QUERY.to_string(),
+        variables: None,
+        operation_name: Some("IntrospectionQuery".to_string()),
+        persisted: false,
+    });
+
+    let response = execute_request(
+        &app_handle,
+        &client,
+        &jar,
+        &tokens,
+        &p_name,
+        redactor.as_ref(),
+        args,
+    )
+    .await?;
+
+    let parsed: Value = serde_json::from_str(&response.body).map_err(|e| {
+        format!(
+            "Introspection returned HTTP {} with a non-JSON body: {}",
+            response.status, e
+        )
+    })?;
+    let Some(schema) = parsed.get("data").filter(|data| !data.is_null()).cloned() else {
+        let errors = parsed.get("errors").cloned().unwrap_or(Value::Null);
+        let msg = format!(
+            "Introspection failed (HTTP {}): {}",
+            response.status, errors
+        );
+        crate::rust_error!(&app_handle, "{}", msg);
+        return Err(msg);
+    };
+
+    crate::rust_info!(&app_handle, "Cached GraphQL schema for {}", cache_key);
+    state.schemas.lock().await.insert(cache_key, schema.clone());
+    Ok(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(method: &str) -> HttpRequestArgs {
+        HttpRequestArgs {
+            method: method.to_string(),
+            url: "https://api.example.com/graphql".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn body(query: &str, operation_name: Option<&str>, persisted: bool) -> GraphQLBody {
+        GraphQLBody {
+            query: query.to_string(),
+            variables: Some(json!("{\"id\": 1}")),
+            operation_name: operation_name.map(str::to_string),
+            persisted,
+        }
+    }
+
+    #[test]
+    fn operation_kind_skips_comments_strings_and_fragments() {
+        let document = r#"
+            # mutation in a comment
+            fragment F on User { name }
+            query Get($s: String = "mutation {") { user { ...F } }
+            mutation Save { save(input: { query: 1 }) { id } }
+        "#;
+        assert_eq!(operation_kind(document, None), "query");
+        assert_eq!(operation_kind(document, Some("Save")), "mutation");
+        assert_eq!(operation_kind(document, Some("Get")), "query");
+        assert_eq!(operation_kind("{ user { id } }", None), "query");
+        assert_eq!(
+            operation_kind("subscription { tick }", None),
+            "subscription"
+        );
+    }
+
+    #[test]
+    fn queries_with_get_use_the_query_string() {
+        let mut request = args("GET");
+        encode(&mut request, &body("{ user { id } }", None, false), true).unwrap();
+        assert_eq!(request.method, "GET");
+        assert!(request.body.is_none());
+        let url = reqwest::Url::parse(&request.url).unwrap();
+        let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert!(pairs.contains(&("query".to_string(), "{ user { id } }".to_string())));
+        assert!(pairs.contains(&("variables".to_string(), "{\"id\":1}".to_string())));
+    }
+
+    #[test]
+    fn persisted_mutations_keep_a_post_body() {
+        let query = "mutation Save { save { id } }";
+        let mut request = args("GET");
+        encode(&mut request, &body(query, None, true), false).unwrap();
+        assert_eq!(request.method, "POST");
+        assert!(!request.url.contains('?'));
+        let payload: Value = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+        assert!(payload.get("query").is_none());
+        assert_eq!(
+            payload["extensions"]["persistedQuery"]["sha256Hash"],
+            json!(hex::encode(Sha256::digest(query.as_bytes())))
+        );
+        assert_eq!(payload["variables"], json!({ "id": 1 }));
+    }
+
+    #[test]
+    fn persisted_queries_follow_the_method() {
+        let mut post = args("POST");
+        encode(&mut post, &body("{ user { id } }", None, true), true).unwrap();
+        assert_eq!(post.method, "POST");
+        let payload: Value = serde_json::from_str(post.body.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["query"], json!("{ user { id } }"));
+        assert!(payload["extensions"]["persistedQuery"].is_object());
+
+        let mut get = args("GET");
+        encode(&mut get, &body("{ user { id } }", None, true), false).unwrap();
+        assert_eq!(get.method, "GET");
+        assert!(get.url.contains("extensions="));
+        assert!(!get.url.contains("query="));
+    }
+}
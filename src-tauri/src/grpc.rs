@@ -0,0 +1,1160 @@
+use crate::commands::{project_client, ClientOptions};
+use prost::Message as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use tauri::{command, Emitter};
+use tokio::sync::{mpsc, oneshot};
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder, ProstCodec};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic::{Code, Status};
+
+/// Where service definitions come from: local `.proto` files, or server reflection when
+/// `proto_files` is empty.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrpcSchemaArgs {
+    /// Server address, e.g. "http://localhost:50051"
+    pub url: String,
+    #[serde(default)]
+    pub proto_files: Vec<String>,
+    /// Import roots for `proto_files`; the directory of each file is used when empty.
+    #[serde(default)]
+    pub import_paths: Vec<String>,
+    /// Metadata sent with reflection requests
+    #[serde(default)]
+    pub headers: Vec<Vec<String>>,
+    /// Reload the descriptors instead of using the cached ones
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrpcService {
+    name: String,
+    methods: Vec<GrpcMethod>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrpcMethod {
+    name: String,
+    input_type: String,
+    output_type: String,
+    client_streaming: bool,
+    server_streaming: bool,
+    /// Input message with every field at its default value, as a starting point for the editor
+    input_example: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrpcRequestArgs {
+    #[serde(flatten)]
+    pub schema: GrpcSchemaArgs,
+    /// Fully qualified service name, e.g. "helloworld.Greeter"
+    pub service: String,
+    pub method: String,
+    /// Request message as JSON
+    #[serde(default)]
+    pub message: String,
+    /// Use gRPC-Web (HTTP/1.1 or HTTP/2 through the project client) instead of native gRPC
+    #[serde(default)]
+    pub web: bool,
+    #[serde(default)]
+    pub request_id: Option<String>,
+    #[serde(default)]
+    pub project_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrpcResponse {
+    /// gRPC status code, 0 is OK
+    status_code: i32,
+    /// Canonical name of the status code, e.g. "NotFound"
+    status_name: String,
+    status_message: String,
+    headers: Vec<Vec<String>>,
+    trailers: Vec<Vec<String>>,
+    /// Decoded response messages, one for unary calls
+    messages: Vec<Value>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct GrpcMessagePayload {
+    pub request_id: String,
+    pub message: Value,
+}
+
+// --- Dynamic codec ---
+
+/// Encodes and decodes messages known only at runtime through their descriptors.
+struct DynamicCodec {
+    output: MessageDescriptor,
+}
+
+struct DynamicEncoder;
+
+struct DynamicDecoder(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.output.clone())
+    }
+}
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("Failed to encode message: {}", e)))
+    }
+}
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("Failed to decode message: {}", e)))
+    }
+}
+
+// --- Server reflection (grpc.reflection.v1 / v1alpha, identical messages) ---
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionRequest {
+    #[prost(string, tag = "1")]
+    host: String,
+    #[prost(oneof = "ReflectionQuery", tags = "3, 4, 7")]
+    query: Option<ReflectionQuery>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum ReflectionQuery {
+    #[prost(string, tag = "3")]
+    FileByFilename(String),
+    #[prost(string, tag = "4")]
+    FileContainingSymbol(String),
+    #[prost(string, tag = "7")]
+    ListServices(String),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ReflectionResponse {
+    #[prost(oneof = "ReflectionAnswer", tags = "4, 6, 7")]
+    answer: Option<ReflectionAnswer>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum ReflectionAnswer {
+    #[prost(message, tag = "4")]
+    Files(FileDescriptorResponse),
+    #[prost(message, tag = "6")]
+    Services(ListServiceResponse),
+    #[prost(message, tag = "7")]
+    Error(ErrorResponse),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FileDescriptorResponse {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    file_descriptor_proto: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ListServiceResponse {
+    #[prost(message, repeated, tag = "1")]
+    service: Vec<ServiceResponse>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ServiceResponse {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ErrorResponse {
+    #[prost(int32, tag = "1")]
+    error_code: i32,
+    #[prost(string, tag = "2")]
+    error_message: String,
+}
+
+const REFLECTION_SERVICES: [&str; 2] = [
+    "grpc.reflection.v1.ServerReflection",
+    "grpc.reflection.v1alpha.ServerReflection",
+];
+
+/// Metadata from header rows; `-bin` keys carry base64 values as the gRPC spec requires.
+fn metadata_map(headers: &[Vec<String>]) -> Result<MetadataMap, String> {
+    use base64::Engine;
+    let mut metadata = MetadataMap::new();
+    for pair in headers.iter().filter(|pair| pair.len() == 2) {
+        let name = pair[0].to_lowercase();
+        if name.ends_with("-bin") {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(pair[1].trim())
+                .map_err(|e| format!("Metadata {} must be base64: {}", name, e))?;
+            let key = tonic::metadata::BinaryMetadataKey::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid metadata key {}: {}", name, e))?;
+            metadata.append_bin(key, MetadataValue::from_bytes(&bytes));
+        } else {
+            let key = tonic::metadata::AsciiMetadataKey::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid metadata key {}: {}", name, e))?;
+            let value = MetadataValue::try_from(pair[1].as_str())
+                .map_err(|e| format!("Invalid metadata value for {}: {}", name, e))?;
+            metadata.append(key, value);
+        }
+    }
+    Ok(metadata)
+}
+
+fn metadata_rows(metadata: &MetadataMap) -> Vec<Vec<String>> {
+    let headers = metadata.clone().into_headers();
+    headers
+        .iter()
+        .filter_map(|(key, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|v| vec![key.to_string(), v.to_string()])
+        })
+        .collect()
+}
+
+fn code_name(code: i32) -> String {
+    format!("{:?}", Code::from_i32(code))
+}
+
+/// Channel for a native call. tonic resolves the host itself, so DNS overrides do not apply.
+async fn connect(url: &str) -> Result<Channel, String> {
+    let mut endpoint =
+        Endpoint::from_shared(url.to_string()).map_err(|e| format!("Invalid gRPC URL: {}", e))?;
+    if url.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| format!("Failed to configure TLS: {}", e))?;
+    }
+    endpoint
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", url, e))
+}
+
+fn path(service: &str, method: &str) -> Result<PathAndQuery, String> {
+    PathAndQuery::try_from(format!("/{}/{}", service, method))
+        .map_err(|e| format!("Invalid method path: {}", e))
+}
+
+/// Ask the server for its services and the files defining them, then for any missing imports.
+async fn reflect(channel: Channel, headers: &[Vec<String>]) -> Result<DescriptorPool, String> {
+    let mut last_error = String::new();
+    for service in REFLECTION_SERVICES {
+        match reflect_with(channel.clone(), service, headers).await {
+            Ok(pool) => return Ok(pool),
+            Err(status) if status.code() == Code::Unimplemented => {
+                last_error = status.message().to_string();
+            }
+            Err(status) => {
+                return Err(format!(
+                    "Server reflection failed ({:?}): {}",
+                    status.code(),
+                    status.message()
+                ))
+            }
+        }
+    }
+    Err(format!(
+        "The server does not support reflection, load .proto files instead ({})",
+        last_error
+    ))
+}
+
+// tonic::Status is large, but boxing it would only obscure the reflection code
+#[allow(clippy::result_large_err)]
+async fn reflect_with(
+    channel: Channel,
+    service: &str,
+    headers: &[Vec<String>],
+) -> Result<DescriptorPool, Status> {
+    let (tx, rx) = mpsc::unbounded_channel::<ReflectionRequest>();
+    let outbound = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|request| (request, rx))
+    });
+    let ask = |query: ReflectionQuery| {
+        tx.send(ReflectionRequest {
+            host: String::new(),
+            query: Some(query),
+        })
+        .map_err(|_| Status::internal("Reflection stream closed"))
+    };
+
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready()
+        .await
+        .map_err(|e| Status::unavailable(e.to_string()))?;
+    let mut request = tonic::Request::new(outbound);
+    *request.metadata_mut() = metadata_map(headers).map_err(Status::invalid_argument)?;
+
+    ask(ReflectionQuery::ListServices(String::new()))?;
+    let path = path(service, "ServerReflectionInfo").map_err(Status::internal)?;
+    let mut inbound = grpc
+        .streaming(
+            request,
+            path,
+            ProstCodec::<ReflectionRequest, ReflectionResponse>::default(),
+        )
+        .await?
+        .into_inner();
+
+    let services = match next_answer(&mut inbound).await? {
+        ReflectionAnswer::Services(list) => list.service,
+        _ => return Err(Status::internal("Unexpected reflection answer")),
+    };
+
+    let mut files: HashMap<String, prost_types::FileDescriptorProto> = HashMap::new();
+    for service in services
+        .iter()
+        .filter(|s| !s.name.starts_with("grpc.reflection."))
+    {
+        ask(ReflectionQuery::FileContainingSymbol(service.name.clone()))?;
+        add_files(next_answer(&mut inbound).await?, &mut files)?;
+    }
+
+    // Servers usually send dependencies along, fetch whatever is still missing
+    let mut requested = HashSet::new();
+    loop {
+        let missing: Vec<String> = files
+            .values()
+            .flat_map(|file| file.dependency.iter())
+            .filter(|dep| !files.contains_key(*dep) && !requested.contains(*dep))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            break;
+        }
+        for name in missing {
+            requested.insert(name.clone());
+            ask(ReflectionQuery::FileByFilename(name))?;
+            add_files(next_answer(&mut inbound).await?, &mut files)?;
+        }
+    }
+
+    let mut pool = DescriptorPool::new();
+    pool.add_file_descriptor_protos(dependency_order(files))
+        .map_err(|e| Status::internal(format!("Invalid descriptors from reflection: {}", e)))?;
+    Ok(pool)
+}
+
+async fn next_answer(
+    inbound: &mut tonic::Streaming<ReflectionResponse>,
+) -> Result<ReflectionAnswer, Status> {
+    match inbound.message().await? {
+        Some(ReflectionResponse {
+            answer: Some(ReflectionAnswer::Error(error)),
+        }) => Err(Status::new(
+            Code::from_i32(error.error_code),
+            error.error_message,
+        )),
+        Some(ReflectionResponse {
+            answer: Some(answer),
+        }) => Ok(answer),
+        _ => Err(Status::internal("Reflection stream ended unexpectedly")),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn add_files(
+    answer: ReflectionAnswer,
+    files: &mut HashMap<String, prost_types::FileDescriptorProto>,
+) -> Result<(), Status> {
+    let ReflectionAnswer::Files(response) = answer else {
+        return Err(Status::internal("Unexpected reflection answer"));
+    };
+    for bytes in response.file_descriptor_proto {
+        let file = prost_types::FileDescriptorProto::decode(bytes.as_slice())
+            .map_err(|e| Status::internal(format!("Invalid file descriptor: {}", e)))?;
+        files.insert(file.name().to_string(), file);
+    }
+    Ok(())
+}
+
+/// Sort files so every file comes after its imports.
+fn dependency_order(
+    mut files: HashMap<String, prost_types::FileDescriptorProto>,
+) -> Vec<prost_types::FileDescriptorProto> {
+    let mut ordered = Vec::new();
+    let mut done: HashSet<String> = HashSet::new();
+    while !files.is_empty() {
+        let ready: Vec<String> = files
+            .iter()
+            .filter(|(_, file)| {
+                file.dependency
+                    .iter()
+                    .all(|dep| done.contains(dep) || !files.contains_key(dep))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        // A cycle cannot be resolved, hand the rest over and let the pool report it
+        if ready.is_empty() {
+            ordered.extend(files.into_values());
+            break;
+        }
+        for name in ready {
+            if let Some(file) = files.remove(&name) {
+                done.insert(name);
+                ordered.push(file);
+            }
+        }
+    }
+    ordered
+}
+
+fn compile_protos(schema: &GrpcSchemaArgs) -> Result<DescriptorPool, String> {
+    let mut includes = schema.import_paths.clone();
+    if includes.is_empty() {
+        for file in &schema.proto_files {
+            if let Some(dir) = std::path::Path::new(file).parent() {
+                let dir = dir.to_string_lossy().to_string();
+                if !includes.contains(&dir) {
+                    includes.push(dir);
+                }
+            }
+        }
+    }
+    protox::Compiler::new(&includes)
+        .and_then(|mut compiler| {
+            compiler.include_imports(true);
+            compiler.open_files(&schema.proto_files)?;
+            Ok(compiler.descriptor_pool())
+        })
+        .map_err(|e| format!("Failed to compile .proto files: {}", e))
+}
+
+fn cache_key(schema: &GrpcSchemaArgs) -> String {
+    if schema.proto_files.is_empty() {
+        format!("reflection|{}", schema.url)
+    } else {
+        format!(
+            "protos|{}|{}",
+            schema.proto_files.join(","),
+            schema.import_paths.join(",")
+        )
+    }
+}
+
+async fn descriptor_pool(
+    state: &crate::GrpcState,
+    schema: &GrpcSchemaArgs,
+) -> Result<DescriptorPool, String> {
+    let key = cache_key(schema);
+    if !schema.refresh {
+        if let Some(pool) = state.pools.lock().await.get(&key) {
+            return Ok(pool.clone());
+        }
+    }
+    let pool = if schema.proto_files.is_empty() {
+        reflect(connect(&schema.url).await?, &schema.headers).await?
+    } else {
+        compile_protos(schema)?
+    };
+    state.pools.lock().await.insert(key, pool.clone());
+    Ok(pool)
+}
+
+fn find_method(
+    pool: &DescriptorPool,
+    service: &str,
+    method: &str,
+) -> Result<MethodDescriptor, String> {
+    let service = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| format!("Unknown service: {}", service))?;
+    let found = service.methods().find(|m| m.name() == method);
+    found.ok_or_else(|| format!("Unknown method: {}/{}", service.full_name(), method))
+}
+
+fn message_from_json(descriptor: MessageDescriptor, json: &str) -> Result<DynamicMessage, String> {
+    let json = if json.trim().is_empty() { "{}" } else { json };
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let message = DynamicMessage::deserialize(descriptor, &mut deserializer)
+        .map_err(|e| format!("Request does not match the input message: {}", e))?;
+    deserializer
+        .end()
+        .map_err(|e| format!("Invalid request JSON: {}", e))?;
+    Ok(message)
+}
+
+fn message_to_json(message: &DynamicMessage) -> Value {
+    serde_json::to_value(message).unwrap_or(Value::Null)
+}
+
+/// Load the schema (from `.proto` files or reflection) and list its services and methods.
+#[command]
+pub async fn grpc_list_services(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::GrpcState>,
+    args: GrpcSchemaArgs,
+) -> Result<Vec<GrpcService>, String> {
+    let pool = descriptor_pool(&state, &args).await.map_err(|msg| {
+        crate::rust_error!(&app_handle, "{}", msg);
+        msg
+    })?;
+
+    let services = pool
+        .services()
+        .filter(|service| !service.full_name().starts_with("grpc.reflection."))
+        .map(|service| GrpcService {
+            name: service.full_name().to_string(),
+            methods: service
+                .methods()
+                .map(|method| {
+                    let input = method.input();
+                    let options = prost_reflect::SerializeOptions::new().skip_default_fields(false);
+                    let input_example = DynamicMessage::new(input.clone())
+                        .serialize_with_options(serde_json::value::Serializer, &options)
+                        .unwrap_or(Value::Null);
+                    GrpcMethod {
+                        name: method.name().to_string(),
+                        input_type: input.full_name().to_string(),
+                        output_type: method.output().full_name().to_string(),
+                        client_streaming: method.is_client_streaming(),
+                        server_streaming: method.is_server_streaming(),
+                        input_example,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(services)
+}
+
+/// Perform a unary or server-streaming call. Streamed messages are emitted as `grpc-message`
+/// events as they arrive; the call can be cancelled with `cancel_http_request`.
+#[command]
+pub async fn grpc_request(
+    app_handle: tauri::AppHandle,
+    http_state: tauri::State<'_, crate::HttpRequestState>,
+    state: tauri::State<'_, crate::GrpcState>,
+    args: GrpcRequestArgs,
+) -> Result<GrpcResponse, String> {
+    let (tx, rx) = oneshot::channel::<()>();
+    let request_id = args.request_id.clone();
+
+    if let Some(id) = &request_id {
+        let mut handles = http_state.handles.lock().await;
+        handles.insert(id.clone(), tx);
+    }
+
+    let on_message = |message: &Value| emit_message(&app_handle, &request_id, message);
+    let call_future = async {
+        let pool = descriptor_pool(&state, &args.schema).await?;
+        let method = find_method(&pool, &args.service, &args.method)?;
+        if method.is_client_streaming() {
+            return Err("Client and bidirectional streaming methods are not supported".to_string());
+        }
+        let message = message_from_json(method.input(), &args.message)?;
+
+        if args.web {
+            let p_name = args
+                .project_name
+                .clone()
+                .unwrap_or_else(|| "default".to_string());
+            let (client, _jar) =
+                project_client(&http_state, &p_name, &ClientOptions::default()).await?;
+            grpc_web_call(&on_message, &client, &args, &method, message).await
+        } else {
+            native_call(&on_message, &args, &method, message).await
+        }
+    };
+
+    let result = tokio::select! {
+        res = call_future => res.map_err(|msg| {
+            crate::rust_error!(&app_handle, "{}", msg);
+            msg
+        }),
+        _ = rx => Err("Canceled".to_string()),
+    };
+
+    if let Some(id) = &request_id {
+        let mut handles = http_state.handles.lock().await;
+        handles.remove(id);
+    }
+
+    result
+}
+
+fn emit_message(app_handle: &tauri::AppHandle, request_id: &Option<String>, message: &Value) {
+    if let Some(id) = request_id {
+        let _ = app_handle.emit(
+            "grpc-message",
+            GrpcMessagePayload {
+                request_id: id.clone(),
+                message: message.clone(),
+            },
+        );
+    }
+}
+
+fn status_response(
+    status: &Status,
+    messages: Vec<Value>,
+    headers: Vec<Vec<String>>,
+) -> GrpcResponse {
+    GrpcResponse {
+        status_code: status.code() as i32,
+        status_name: format!("{:?}", status.code()),
+        status_message: status.message().to_string(),
+        headers,
+        trailers: metadata_rows(status.metadata()),
+        messages,
+    }
+}
+
+/// A native gRPC call. `on_message` sees each message of a stream as it arrives.
+async fn native_call(
+    on_message: &(dyn Fn(&Value) + Sync),
+    args: &GrpcRequestArgs,
+    method: &MethodDescriptor,
+    message: DynamicMessage,
+) -> Result<GrpcResponse, String> {
+    let mut grpc = tonic::client::Grpc::new(connect(&args.schema.url).await?);
+    grpc.ready()
+        .await
+        .map_err(|e| format!("gRPC channel not ready: {}", e))?;
+
+    let mut request = tonic::Request::new(message);
+    *request.metadata_mut() = metadata_map(&args.schema.headers)?;
+    let path = path(method.parent_service().full_name(), method.name())?;
+    let codec = DynamicCodec {
+        output: method.output(),
+    };
+
+    if !method.is_server_streaming() {
+        return Ok(match grpc.unary(request, path, codec).await {
+            Ok(response) => {
+                let message = message_to_json(response.get_ref());
+                GrpcResponse {
+                    status_code: 0,
+                    status_name: code_name(0),
+                    status_message: String::new(),
+                    headers: metadata_rows(response.metadata()),
+                    trailers: Vec::new(),
+                    messages: vec![message],
+                }
+            }
+            // Trailers-only responses carry everything in the status metadata
+            Err(status) => status_response(&status, Vec::new(), Vec::new()),
+        });
+    }
+
+    let response = match grpc.server_streaming(request, path, codec).await {
+        Ok(response) => response,
+        Err(status) => return Ok(status_response(&status, Vec::new(), Vec::new())),
+    };
+    let headers = metadata_rows(response.metadata());
+    let mut stream = response.into_inner();
+    let mut messages = Vec::new();
+    loop {
+        match stream.message().await {
+            Ok(Some(message)) => {
+                let message = message_to_json(&message);
+                on_message(&message);
+                messages.push(message);
+            }
+            Ok(None) => break,
+            Err(status) => return Ok(status_response(&status, messages, headers)),
+        }
+    }
+    let trailers = match stream.trailers().await {
+        Ok(Some(trailers)) => metadata_rows(&trailers),
+        _ => Vec::new(),
+    };
+    Ok(GrpcResponse {
+        status_code: 0,
+        status_name: code_name(0),
+        status_message: String::new(),
+        headers,
+        trailers,
+        messages,
+    })
+}
+
+/// Flag of gRPC-Web frames carrying trailers instead of a message.
+const WEB_TRAILERS_FLAG: u8 = 0x80;
+
+/// A gRPC-Web frame: 1 flag byte, 4 bytes big-endian length, payload.
+fn web_frame(flag: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 5);
+    frame.push(flag);
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Take the first complete frame out of `buffer`, with its flag.
+fn next_web_frame(buffer: &mut Vec<u8>) -> Option<(u8, Vec<u8>)> {
+    if buffer.len() < 5 {
+        return None;
+    }
+    let len = u32::from_be_bytes([buffer[1], buffer[2], buffer[3], buffer[4]]) as usize;
+    if buffer.len() < 5 + len {
+        return None;
+    }
+    let flag = buffer[0];
+    let payload = buffer.drain(..5 + len).skip(5).collect();
+    Some((flag, payload))
+}
+
+/// Parse the `name: value` lines of a gRPC-Web trailer frame.
+fn parse_web_trailers(block: &[u8]) -> Vec<Vec<String>> {
+    String::from_utf8_lossy(block)
+        .split("\r\n")
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some(vec![name.trim().to_lowercase(), value.trim().to_string()])
+        })
+        .collect()
+}
+
+async fn grpc_web_call(
+    on_message: &(dyn Fn(&Value) + Sync),
+    client: &reqwest::Client,
+    args: &GrpcRequestArgs,
+    method: &MethodDescriptor,
+    message: DynamicMessage,
+) -> Result<GrpcResponse, String> {
+    let body = web_frame(0, &message.encode_to_vec());
+
+    let url = format!(
+        "{}/{}/{}",
+        args.schema.url.trim_end_matches('/'),
+        method.parent_service().full_name(),
+        method.name()
+    );
+    let mut request = client
+        .post(&url)
+        .header("Content-Type", "application/grpc-web+proto")
+        .header("Accept", "application/grpc-web+proto")
+        .header("X-Grpc-Web", "1")
+        .body(body);
+    for pair in args.schema.headers.iter().filter(|pair| pair.len() == 2) {
+        request = request.header(&pair[0], &pair[1]);
+    }
+
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| format!("gRPC-Web request failed: {}", e))?;
+    let mut headers: Vec<Vec<String>> = Vec::new();
+    for (key, value) in response.headers() {
+        if let Ok(v) = value.to_str() {
+            headers.push(vec![key.to_string(), v.to_string()]);
+        }
+    }
+    let http_status = response.status();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut messages = Vec::new();
+    let mut trailers: Vec<Vec<String>> = Vec::new();
+    loop {
+        while let Some((flag, frame)) = next_web_frame(&mut buffer) {
+            if flag & WEB_TRAILERS_FLAG != 0 {
+                trailers.extend(parse_web_trailers(&frame));
+            } else if flag & 0x01 != 0 {
+                return Err("Compressed gRPC-Web frames are not supported".to_string());
+            } else {
+                let decoded = DynamicMessage::decode(method.output(), frame.as_slice())
+                    .map_err(|e| format!("Failed to decode message: {}", e))?;
+                let message = message_to_json(&decoded);
+                on_message(&message);
+                messages.push(message);
+            }
+        }
+        match response
+            .chunk()
+            .await
+            .map_err(|e| format!("gRPC-Web stream interrupted: {}", e))?
+        {
+            Some(chunk) => buffer.extend_from_slice(&chunk),
+            None => break,
+        }
+    }
+
+    // Trailers-only responses put the status in the HTTP headers
+    let lookup = |name: &str| {
+        trailers
+            .iter()
+            .chain(headers.iter())
+            .find(|pair| pair[0].eq_ignore_ascii_case(name))
+            .map(|pair| pair[1].clone())
+    };
+    let status_code = match lookup("grpc-status") {
+        Some(code) => code
+            .parse()
+            .map_err(|_| format!("Invalid grpc-status: {}", code))?,
+        None if http_status.is_success() => 0,
+        None => return Err(format!("gRPC-Web call failed with HTTP {}", http_status)),
+    };
+    let status_message = lookup("grpc-message")
+        .map(|m| percent_decode(&m))
+        .unwrap_or_default();
+
+    Ok(GrpcResponse {
+        status_code,
+        status_name: code_name(status_code),
+        status_message,
+        headers,
+        trailers,
+        messages,
+    })
+}
+
+/// grpc-message is percent-encoded UTF-8 on the wire. Malformed escapes are kept as they are.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| {
+            std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        });
+        match escaped {
+            Some(byte) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use tonic::codegen::{http, BoxFuture, BoxStream, Context, Poll, Service};
+    use tonic::server::NamedService;
+
+    const GREETER_PROTO: &str = r#"syntax = "proto3";
+package test.greeter;
+
+message HelloRequest {
+  string name = 1;
+  int32 count = 2;
+}
+
+message HelloReply {
+  string message = 1;
+}
+
+service Greeter {
+  rpc SayHello (HelloRequest) returns (HelloReply);
+  rpc SayHellos (HelloRequest) returns (stream HelloReply);
+}
+"#;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct HelloRequest {
+        #[prost(string, tag = "1")]
+        name: String,
+        #[prost(int32, tag = "2")]
+        count: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct HelloReply {
+        #[prost(string, tag = "1")]
+        message: String,
+    }
+
+    struct GreeterSource;
+
+    impl protox::file::FileResolver for GreeterSource {
+        fn open_file(&self, name: &str) -> Result<protox::file::File, protox::Error> {
+            match name {
+                "greeter.proto" => protox::file::File::from_source(name, GREETER_PROTO),
+                _ => Err(protox::Error::file_not_found(name)),
+            }
+        }
+    }
+
+    fn descriptor_set() -> prost_types::FileDescriptorSet {
+        let mut compiler = protox::Compiler::with_file_resolver(GreeterSource);
+        compiler.open_file("greeter.proto").unwrap();
+        compiler.file_descriptor_set()
+    }
+
+    /// The Greeter service as tonic-build would generate it.
+    #[derive(Clone)]
+    struct Greeter;
+
+    impl NamedService for Greeter {
+        const NAME: &'static str = "test.greeter.Greeter";
+    }
+
+    struct SayHello;
+
+    impl tonic::server::UnaryService<HelloRequest> for SayHello {
+        type Response = HelloReply;
+        type Future = BoxFuture<tonic::Response<HelloReply>, Status>;
+
+        fn call(&mut self, request: tonic::Request<HelloRequest>) -> Self::Future {
+            let name = request.into_inner().name;
+            Box::pin(async move {
+                if name.is_empty() {
+                    return Err(Status::invalid_argument("name is required"));
+                }
+                Ok(tonic::Response::new(HelloReply {
+                    message: format!("Hello {}", name),
+                }))
+            })
+        }
+    }
+
+    struct SayHellos;
+
+    impl tonic::server::ServerStreamingService<HelloRequest> for SayHellos {
+        type Response = HelloReply;
+        type ResponseStream = BoxStream<HelloReply>;
+        type Future = BoxFuture<tonic::Response<BoxStream<HelloReply>>, Status>;
+
+        fn call(&mut self, request: tonic::Request<HelloRequest>) -> Self::Future {
+            let request = request.into_inner();
+            let replies: Vec<HelloReply> = (1..=request.count)
+                .map(|i| HelloReply {
+                    message: format!("Hello {} #{}", request.name, i),
+                })
+                .collect();
+            Box::pin(async move {
+                let stream: BoxStream<HelloReply> =
+                    Box::pin(futures_util::stream::iter(replies.into_iter().map(Ok)));
+                Ok(tonic::Response::new(stream))
+            })
+        }
+    }
+
+    impl Service<http::Request<tonic::body::BoxBody>> for Greeter {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<tonic::body::BoxBody>) -> Self::Future {
+            let codec = ProstCodec::<HelloReply, HelloRequest>::default();
+            match request.uri().path() {
+                "/test.greeter.Greeter/SayHello" => Box::pin(async move {
+                    let mut grpc = tonic::server::Grpc::new(codec);
+                    Ok(grpc.unary(SayHello, request).await)
+                }),
+                "/test.greeter.Greeter/SayHellos" => Box::pin(async move {
+                    let mut grpc = tonic::server::Grpc::new(codec);
+                    Ok(grpc.server_streaming(SayHellos, request).await)
+                }),
+                _ => {
+                    Box::pin(async move { Ok(Status::unimplemented("Unknown method").into_http()) })
+                }
+            }
+        }
+    }
+
+    /// Serve the Greeter with the given reflection version, and return its URL.
+    async fn serve(reflection_v1: bool) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let reflection = tonic_reflection::server::Builder::configure()
+            .register_file_descriptor_set(descriptor_set());
+        let router = tonic::transport::Server::builder().add_service(Greeter);
+        let router = if reflection_v1 {
+            router.add_service(reflection.build_v1().unwrap())
+        } else {
+            router.add_service(reflection.build_v1alpha().unwrap())
+        };
+        let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener);
+        tokio::spawn(router.serve_with_incoming(incoming));
+        url
+    }
+
+    fn request_args(url: &str, method: &str, message: &str) -> GrpcRequestArgs {
+        GrpcRequestArgs {
+            schema: GrpcSchemaArgs {
+                url: url.to_string(),
+                proto_files: Vec::new(),
+                import_paths: Vec::new(),
+                headers: Vec::new(),
+                refresh: false,
+            },
+            service: "test.greeter.Greeter".to_string(),
+            method: method.to_string(),
+            message: message.to_string(),
+            web: false,
+            request_id: None,
+            project_name: None,
+        }
+    }
+
+    /// Reflect the server's schema, then call `method` natively.
+    async fn call(url: &str, method: &str, message: &str) -> (GrpcResponse, Vec<Value>) {
+        let pool = reflect(connect(url).await.unwrap(), &[]).await.unwrap();
+        let descriptor = find_method(&pool, "test.greeter.Greeter", method).unwrap();
+        let args = request_args(url, method, message);
+        let input = message_from_json(descriptor.input(), &args.message).unwrap();
+        let emitted = Mutex::new(Vec::new());
+        let on_message = |message: &Value| emitted.lock().unwrap().push(message.clone());
+        let response = native_call(&on_message, &args, &descriptor, input)
+            .await
+            .unwrap();
+        (response, emitted.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn reflection_v1_lists_the_services() {
+        let url = serve(true).await;
+        let pool = reflect(connect(&url).await.unwrap(), &[]).await.unwrap();
+        let method = find_method(&pool, "test.greeter.Greeter", "SayHellos").unwrap();
+        assert!(method.is_server_streaming());
+        assert_eq!(method.input().full_name(), "test.greeter.HelloRequest");
+    }
+
+    #[tokio::test]
+    async fn reflection_falls_back_to_v1alpha() {
+        let url = serve(false).await;
+        let pool = reflect(connect(&url).await.unwrap(), &[]).await.unwrap();
+        assert!(find_method(&pool, "test.greeter.Greeter", "SayHello").is_ok());
+    }
+
+    #[tokio::test]
+    async fn unary_call_returns_the_reply_or_the_status() {
+        let url = serve(true).await;
+        let (response, _) = call(&url, "SayHello", r#"{"name": "Ada"}"#).await;
+        assert_eq!(response.status_code, 0);
+        assert_eq!(response.messages, vec![json!({ "message": "Hello Ada" })]);
+
+        let (response, _) = call(&url, "SayHello", "{}").await;
+        assert_eq!(response.status_code, Code::InvalidArgument as i32);
+        assert_eq!(response.status_name, "InvalidArgument");
+        assert_eq!(response.status_message, "name is required");
+    }
+
+    #[tokio::test]
+    async fn server_streaming_emits_each_message() {
+        let url = serve(true).await;
+        let (response, emitted) = call(&url, "SayHellos", r#"{"name": "Ada", "count": 3}"#).await;
+        assert_eq!(response.status_code, 0);
+        assert_eq!(response.messages.len(), 3);
+        assert_eq!(response.messages[2], json!({ "message": "Hello Ada #3" }));
+        assert_eq!(emitted, response.messages);
+    }
+
+    #[test]
+    fn web_frames_round_trip() {
+        let mut buffer = web_frame(0, b"abc");
+        buffer.extend(web_frame(WEB_TRAILERS_FLAG, b"grpc-status: 0\r\n"));
+        // An incomplete frame stays in the buffer
+        buffer.extend(&web_frame(0, b"later")[..4]);
+
+        assert_eq!(next_web_frame(&mut buffer), Some((0, b"abc".to_vec())));
+        let (flag, trailers) = next_web_frame(&mut buffer).unwrap();
+        assert_eq!(flag, WEB_TRAILERS_FLAG);
+        assert_eq!(
+            parse_web_trailers(&trailers),
+            vec![vec!["grpc-status".to_string(), "0".to_string()]]
+        );
+        assert_eq!(next_web_frame(&mut buffer), None);
+        assert_eq!(buffer.len(), 4);
+    }
+
+    #[test]
+    fn percent_decode_handles_utf8_and_malformed_escapes() {
+        assert_eq!(percent_decode("caf%C3%A9%20100%25"), "café 100%");
+        assert_eq!(percent_decode("a+b"), "a+b");
+        assert_eq!(percent_decode("50% off %zz"), "50% off %zz");
+        assert_eq!(percent_decode("trailing %4"), "trailing %4");
+    }
+
+    /// A gRPC-Web SayHellos endpoint answering in chunks split mid-frame.
+    async fn grpc_web_server() -> String {
+        async fn say_hellos(body: axum::body::Bytes) -> axum::response::Response {
+            let mut buffer = body.to_vec();
+            let (_, payload) = next_web_frame(&mut buffer).unwrap();
+            let request = HelloRequest::decode(payload.as_slice()).unwrap();
+            let mut reply = Vec::new();
+            for i in 1..=request.count {
+                let message = HelloReply {
+                    message: format!("Hello {} #{}", request.name, i),
+                };
+                reply.extend(web_frame(0, &message.encode_to_vec()));
+            }
+            reply.extend(web_frame(
+                WEB_TRAILERS_FLAG,
+                b"grpc-status: 9\r\ngrpc-message: caf%C3%A9%20closed\r\n",
+            ));
+            let chunks: Vec<Result<Vec<u8>, std::io::Error>> =
+                reply.chunks(7).map(|chunk| Ok(chunk.to_vec())).collect();
+            axum::response::Response::builder()
+                .header("content-type", "application/grpc-web+proto")
+                .body(Body::from_stream(futures_util::stream::iter(chunks)))
+                .unwrap()
+        }
+
+        let app = axum::Router::new().route(
+            "/test.greeter.Greeter/SayHellos",
+            axum::routing::post(say_hellos),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        url
+    }
+
+    #[tokio::test]
+    async fn grpc_web_call_decodes_frames_and_trailers() {
+        let url = grpc_web_server().await;
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_set(descriptor_set()).unwrap();
+        let method = find_method(&pool, "test.greeter.Greeter", "SayHellos").unwrap();
+        let mut args = request_args(&url, "SayHellos", r#"{"name": "Ada", "count": 2}"#);
+        args.web = true;
+        let message = message_from_json(method.input(), &args.message).unwrap();
+
+        let response = grpc_web_call(&|_| {}, &reqwest::Client::new(), &args, &method, message)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.messages,
+            vec![
+                json!({ "message": "Hello Ada #1" }),
+                json!({ "message": "Hello Ada #2" })
+            ]
+        );
+        assert_eq!(response.status_code, 9);
+        assert_eq!(response.status_name, "FailedPrecondition");
+        assert_eq!(response.status_message, "café closed");
+    }
+}
@@ -0,0 +1,43 @@
+use serde_json::Value;
+
+/// Resolve a simple JSONPath (`$.a.b[0]`, `$['a b'].c`, `a.b`) against a value.
+/// Wildcards, filters and recursive descent are not supported.
+pub fn select<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            if !key.is_empty() {
+                current = current.get(key)?;
+            }
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            let segment = after[..end].trim();
+            current = match segment.strip_prefix(['\'', '"']) {
+                Some(quoted) => current.get(quoted.trim_end_matches(['\'', '"']))?,
+                None => current.get(segment.parse::<usize>().ok()?)?,
+            };
+            rest = &after[end + 1..];
+        } else {
+            // Leading bare key, e.g. "data.items"
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            current = current.get(&rest[..end])?;
+            rest = &rest[end..];
+        }
+    }
+    Some(current)
+}
+
+/// Text form used for comparisons: strings unquoted, everything else as JSON.
+pub fn to_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -1,5 +1,28 @@
+mod auth;
+mod batch;
 mod commands;
+mod decode;
+mod dns;
+mod graphql;
+mod grpc;
+mod jsonpath;
+mod load;
+mod mock;
+mod mock_fault;
+mod mock_journal;
+mod mock_match;
+mod mock_proxy;
+mod mock_resource;
+mod mock_tls;
 pub mod logging;
+mod oauth;
+mod raw;
+pub mod redact;
+mod retry;
+mod signing;
+mod sse;
+mod template;
+mod ws;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::AppHandle;
@@ -7,26 +30,62 @@ use tauri::Manager;
 use tokio::sync::oneshot;
 use tokio::sync::Mutex;
 
+/// Mock servers by collection id, until stopped. Crashed ones stay listed with their error.
 pub struct MockServerState {
-    pub handles: Arc<Mutex<HashMap<String, oneshot::Sender<()>>>>,
+    pub servers: Arc<Mutex<HashMap<String, Arc<mock::MockServer>>>>,
 }
 
 pub struct HttpRequestState {
     pub handles: Arc<Mutex<HashMap<String, oneshot::Sender<()>>>>,
     pub clients: Arc<Mutex<HashMap<String, reqwest::Client>>>,
     pub jars: Arc<Mutex<HashMap<String, Arc<reqwest::cookie::Jar>>>>,
+    pub tokens: Arc<oauth::TokenCache>,
+    /// Project-wide host resolution overrides by project name
+    pub dns: Arc<Mutex<HashMap<String, dns::DnsOverrides>>>,
+    /// Introspected GraphQL schemas by "project|url"
+    pub schemas: Arc<Mutex<HashMap<String, serde_json::Value>>>,
+    /// Open SSE streams by request id, closed through `close_sse_stream`
+    pub streams: Arc<Mutex<HashMap<String, oneshot::Sender<()>>>>,
+}
+
+/// Open WebSocket sessions by the id the frontend chose in `ws_connect`.
+pub struct WebSocketState {
+    pub sessions: ws::WsSessions,
+}
+
+/// Compiled gRPC descriptors, by .proto file set or by server URL for reflection.
+pub struct GrpcState {
+    pub pools: Arc<Mutex<HashMap<String, prost_reflect::DescriptorPool>>>,
+}
+
+/// Active secret redaction rules. Uses a std lock because logging reads it synchronously.
+pub struct RedactionState {
+    pub redactor: Arc<std::sync::RwLock<redact::Redactor>>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .manage(MockServerState {
-            handles: Arc::new(Mutex::new(HashMap::new())),
+            servers: Arc::new(Mutex::new(HashMap::new())),
         })
         .manage(HttpRequestState {
             handles: Arc::new(Mutex::new(HashMap::new())),
             clients: Arc::new(Mutex::new(HashMap::new())),
             jars: Arc::new(Mutex::new(HashMap::new())),
+            tokens: Arc::new(oauth::TokenCache::default()),
+            streams: Arc::new(Mutex::new(HashMap::new())),
+            schemas: Arc::new(Mutex::new(HashMap::new())),
+            dns: Arc::new(Mutex::new(HashMap::new())),
+        })
+        .manage(WebSocketState {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        })
+        .manage(GrpcState {
+            pools: Arc::new(Mutex::new(HashMap::new())),
+        })
+        .manage(RedactionState {
+            redactor: Arc::new(std::sync::RwLock::new(redact::Redactor::default())),
         })
         .setup(|app| {
             let handle = app.handle();
@@ -39,6 +98,19 @@ pub fn run() {
                         rust_info!(&handle, "Created config directory: {:?}", config_dir);
                     }
                 }
+
+                // Restore the redaction rules saved by set_redaction_config
+                let saved = redact::load_config(&redact::config_path(&home_dir))
+                    .and_then(|config| config.map(redact::Redactor::new).transpose());
+                match saved {
+                    Ok(Some(redactor)) => {
+                        if let Ok(mut active) = app.state::<RedactionState>().redactor.write() {
+                            *active = redactor;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => rust_warn!(&handle, "Using the default redaction rules: {}", e),
+                }
             }
 
             if let Some(window) = app.get_webview_window("main") {
@@ -60,11 +132,24 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::reconstruct_request,
             commands::http_request,
+            batch::run_batch,
+            graphql::graphql_introspect,
+            grpc::grpc_list_services,
+            grpc::grpc_request,
+            load::load_test,
+            raw::raw_request,
+            sse::sse_request,
+            sse::close_sse_stream,
+            ws::ws_connect,
+            ws::ws_send,
+            ws::ws_ping,
+            ws::ws_close,
             commands::git_init,
             commands::git_status,
             commands::git_add_all,
             commands::git_commit,
             commands::save_workspace,
+            commands::save_collection,
             commands::load_workspace,
             commands::sync_project_manifest,
             commands::list_projects,
@@ -76,14 +161,33 @@ pub fn run() {
             commands::git_push,
             commands::git_add_file,
             commands::git_reset,
-            commands::start_mock_server,
-            commands::stop_mock_server,
+            mock::start_mock_server,
+            mock::stop_mock_server,
+            mock::remove_mock_server,
+            mock::stop_all_mock_servers,
+            mock::list_mock_servers,
+            mock::get_mock_resources,
+            mock::reset_mock_resources,
+            mock::get_mock_scenarios,
+            mock::set_mock_scenario_state,
+            mock::reset_mock_scenarios,
+            mock::get_mock_journal,
+            mock::clear_mock_journal,
+            mock::promote_mock_request,
+            mock::save_mock_recordings,
+            mock::export_mock_ca,
             commands::cancel_http_request,
             commands::check_for_updates,
             commands::git_fetch,
             commands::git_pull,
             commands::get_conflicted_versions,
             commands::git_resolve_conflict,
+            commands::get_redaction_config,
+            commands::set_redaction_config,
+            commands::oauth2_get_token,
+            commands::oauth2_clear_tokens,
+            commands::get_dns_overrides,
+            commands::set_dns_overrides,
             list_recent_projects
         ])
         .on_window_event(|window, event| {
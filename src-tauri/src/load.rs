@@ -0,0 +1,316 @@
+use crate::commands::{execute_request, project_client, ClientOptions, HttpRequestArgs};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{command, Emitter};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinSet;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoadTestArgs {
+    /// The execution to fire, already resolved by the frontend as for `http_request`
+    pub request: HttpRequestArgs,
+    /// Number of concurrent workers
+    pub concurrency: u32,
+    /// Stop after this many requests...
+    #[serde(default)]
+    pub total_requests: Option<u64>,
+    /// ...or after this many seconds, whichever comes first
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+    /// Workers are started evenly over this period instead of all at once
+    #[serde(default)]
+    pub ramp_up_secs: Option<u64>,
+    /// Registry id, so the run can be stopped with `cancel_http_request`
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct LoadTestProgress {
+    pub request_id: String,
+    pub completed: u64,
+    pub failed: u64,
+    pub active_workers: u64,
+    pub elapsed_ms: u64,
+    /// Requests per second since the start
+    pub throughput: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LatencyStats {
+    min: f64,
+    mean: f64,
+    p50: f64,
+    p90: f64,
+    p99: f64,
+    max: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoadTestSummary {
+    total_requests: u64,
+    successful: u64,
+    /// Transport errors plus HTTP 4xx/5xx responses
+    failed: u64,
+    cancelled: bool,
+    duration_ms: u64,
+    /// Completed requests per second
+    throughput: f64,
+    /// Milliseconds
+    latency: LatencyStats,
+    status_histogram: BTreeMap<u16, u64>,
+    /// Transport errors by message
+    errors: BTreeMap<String, u64>,
+}
+
+struct Sample {
+    latency_ms: f64,
+    result: Result<u16, String>,
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn latency_stats(mut latencies: Vec<f64>) -> LatencyStats {
+    if latencies.is_empty() {
+        return LatencyStats::default();
+    }
+    latencies.sort_by(|a, b| a.total_cmp(b));
+    LatencyStats {
+        min: latencies[0],
+        mean: latencies.iter().sum::<f64>() / latencies.len() as f64,
+        p50: percentile(&latencies, 50.0),
+        p90: percentile(&latencies, 90.0),
+        p99: percentile(&latencies, 99.0),
+        max: latencies[latencies.len() - 1],
+    }
+}
+
+/// Fire one execution repeatedly with the project's client and report throughput, latency
+/// percentiles, a status histogram and errors. Progress is emitted as `load-test-progress`.
+#[command]
+pub async fn load_test(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::HttpRequestState>,
+    args: LoadTestArgs,
+) -> Result<LoadTestSummary, String> {
+    if args.concurrency == 0 {
+        return Err("Concurrency must be at least 1".to_string());
+    }
+    if args.total_requests.is_none() && args.duration_secs.is_none() {
+        return Err("Set a total number of requests or a duration".to_string());
+    }
+
+    let (tx, mut cancel_rx) = oneshot::channel::<()>();
+    if let Some(id) = &args.request_id {
+        let mut handles = state.handles.lock().await;
+        handles.insert(id.clone(), tx);
+    }
+
+    let mut request = args.request;
+    let p_name = request
+        .project_name
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    crate::dns::merge_project(&state, &p_name, &mut request).await;
+    // Run without a per-request cancel id, the whole test is registered under `request_id`
+    request.request_id = None;
+    // Failures are counted in the summary's error breakdown
+    request.quiet = true;
+    let client_result = project_client(&state, &p_name, &ClientOptions::from_args(&request)).await;
+    let (client, jar) = match client_result {
+        Ok(client) => client,
+        Err(msg) => {
+            if let Some(id) = &args.request_id {
+                state.handles.lock().await.remove(id);
+            }
+            return Err(msg);
+        }
+    };
+
+    crate::rust_info!(
+        &app_handle,
+        "Load test started: {} workers against {}",
+        args.concurrency,
+        request.url
+    );
+
+    let started = Instant::now();
+    let deadline = args
+        .duration_secs
+        .map(|secs| started + Duration::from_secs(secs));
+    let ramp_up = Duration::from_secs(args.ramp_up_secs.unwrap_or(0));
+    let issued = Arc::new(AtomicU64::new(0));
+    let active = Arc::new(AtomicU64::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+    let (sample_tx, mut samples) = mpsc::unbounded_channel::<Sample>();
+
+    let mut workers = JoinSet::new();
+    for worker in 0..args.concurrency {
+        let delay = ramp_up.mul_f64(worker as f64 / args.concurrency as f64);
+        let (app_handle, client, jar) = (app_handle.clone(), client.clone(), jar.clone());
+        let tokens = Arc::clone(&state.tokens);
+        let (issued, active, stop) = (issued.clone(), active.clone(), stop.clone());
+        let (request, sample_tx, p_name) = (request.clone(), sample_tx.clone(), p_name.clone());
+        let total = args.total_requests;
+
+        workers.spawn(async move {
+            tokio::time::sleep(delay).await;
+            active.fetch_add(1, Ordering::Relaxed);
+            loop {
+                if stop.load(Ordering::Relaxed) || deadline.is_some_and(|d| Instant::now() >= d) {
+                    break;
+                }
+                if total.is_some_and(|total| issued.fetch_add(1, Ordering::Relaxed) >= total) {
+                    break;
+                }
+                let sent = Instant::now();
+                let result = execute_request(
+                    &app_handle,
+                    &client,
+                    &jar,
+                    &tokens,
+                    &p_name,
+                    None,
+                    request.clone(),
+                )
+                .await
+                .map(|response| response.status);
+                let sample = Sample {
+                    latency_ms: sent.elapsed().as_secs_f64() * 1000.0,
+                    result,
+                };
+                if sample_tx.send(sample).is_err() {
+                    break;
+                }
+            }
+            active.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+    drop(sample_tx);
+
+    let mut latencies = Vec::new();
+    let mut status_histogram: BTreeMap<u16, u64> = BTreeMap::new();
+    let mut errors: BTreeMap<String, u64> = BTreeMap::new();
+    let mut failed = 0u64;
+    let mut cancelled = false;
+    let mut ticker = tokio::time::interval(PROGRESS_INTERVAL);
+
+    loop {
+        tokio::select! {
+            sample = samples.recv() => {
+                let Some(sample) = sample else { break };
+                latencies.push(sample.latency_ms);
+                match sample.result {
+                    Ok(status) => {
+                        *status_histogram.entry(status).or_default() += 1;
+                        if status >= 400 {
+                            failed += 1;
+                        }
+                    }
+                    Err(msg) => {
+                        *errors.entry(msg).or_default() += 1;
+                        failed += 1;
+                    }
+                }
+            }
+            _ = ticker.tick() => {
+                if let Some(id) = &args.request_id {
+                    let elapsed = started.elapsed();
+                    let _ = app_handle.emit("load-test-progress", LoadTestProgress {
+                        request_id: id.clone(),
+                        completed: latencies.len() as u64,
+                        failed,
+                        active_workers: active.load(Ordering::Relaxed),
+                        elapsed_ms: elapsed.as_millis() as u64,
+                        throughput: latencies.len() as f64 / elapsed.as_secs_f64().max(0.001),
+                    });
+                }
+            }
+            _ = &mut cancel_rx, if !cancelled => {
+                cancelled = true;
+                stop.store(true, Ordering::Relaxed);
+                workers.abort_all();
+            }
+        }
+    }
+    while workers.join_next().await.is_some() {}
+
+    if let Some(id) = &args.request_id {
+        let mut handles = state.handles.lock().await;
+        handles.remove(id);
+    }
+
+    let duration = started.elapsed();
+    let total_requests = latencies.len() as u64;
+    crate::rust_info!(
+        &app_handle,
+        "Load test finished: {} requests in {}ms, {} failed",
+        total_requests,
+        duration.as_millis(),
+        failed
+    );
+
+    Ok(LoadTestSummary {
+        total_requests,
+        successful: total_requests - failed,
+        failed,
+        cancelled,
+        duration_ms: duration.as_millis() as u64,
+        throughput: total_requests as f64 / duration.as_secs_f64().max(0.001),
+        latency: latency_stats(latencies),
+        status_histogram,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_uses_the_nearest_rank() {
+        assert_eq!(percentile(&[], 50.0), 0.0);
+        assert_eq!(percentile(&[7.0], 0.0), 7.0);
+        assert_eq!(percentile(&[7.0], 100.0), 7.0);
+        let sorted: Vec<f64> = (1..=10).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 50.0), 5.0);
+        assert_eq!(percentile(&sorted, 90.0), 9.0);
+        assert_eq!(percentile(&sorted, 99.0), 10.0);
+        assert_eq!(percentile(&sorted, 100.0), 10.0);
+    }
+
+    #[test]
+    fn latency_stats_of_no_samples_are_zero() {
+        let stats = latency_stats(Vec::new());
+        assert_eq!((stats.min, stats.mean, stats.max), (0.0, 0.0, 0.0));
+        assert_eq!((stats.p50, stats.p90, stats.p99), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn latency_stats_of_one_sample() {
+        let stats = latency_stats(vec![12.5]);
+        assert_eq!((stats.min, stats.mean, stats.max), (12.5, 12.5, 12.5));
+        assert_eq!((stats.p50, stats.p90, stats.p99), (12.5, 12.5, 12.5));
+    }
+
+    #[test]
+    fn latency_stats_sort_their_samples() {
+        let stats = latency_stats(vec![30.0, 10.0, 20.0, 40.0]);
+        assert_eq!((stats.min, stats.max), (10.0, 40.0));
+        assert_eq!(stats.mean, 25.0);
+        assert_eq!(stats.p50, 20.0);
+        assert_eq!(stats.p99, 40.0);
+    }
+}
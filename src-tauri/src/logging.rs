@@ -1,5 +1,5 @@
 use serde::Serialize;
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
 #[derive(Clone, Serialize)]
 pub struct RustLogPayload {
@@ -8,16 +8,25 @@ pub struct RustLogPayload {
 }
 
 /// Emit a log event to the frontend app console.
+/// Secrets are masked with the active redaction rules before leaving the backend.
 /// Falls back to eprintln if emitting fails.
 pub fn emit_log<R: tauri::Runtime>(app: &tauri::AppHandle<R>, level: &str, message: &str) {
+    let message = match app.try_state::<crate::RedactionState>() {
+        // A poisoned lock still holds the rules: redact rather than leak the message
+        Some(state) => match state.redactor.read() {
+            Ok(redactor) => redactor.text(message),
+            Err(poisoned) => poisoned.into_inner().text(message),
+        },
+        None => message.to_string(),
+    };
     let payload = RustLogPayload {
         level: level.to_string(),
-        message: message.to_string(),
+        message,
     };
     if let Err(e) = app.emit("rust-log", &payload) {
         eprintln!(
             "[rust-log emit error] {}: {} (emit err: {})",
-            level, message, e
+            level, payload.message, e
         );
     }
 }
@@ -0,0 +1,1412 @@
+use crate::mock_fault::{ConnectionControl, ConnectionFault, FaultStream, MockFaults};
+use crate::mock_journal::{JournalEntry, MockJournal, DEFAULT_JOURNAL_SIZE};
+use crate::mock_match::{query_contains, CompiledMatch, MockResponseRule};
+use crate::mock_proxy::{MockProxy, MockProxyConfig, DEFAULT_EXCLUDED_HEADERS};
+use crate::mock_resource::{MockResource, MockResourceDefinition};
+use crate::mock_tls::MockTlsConfig;
+use crate::template::{parse_query, render, RequestContext};
+use crate::MockServerState;
+use axum::{
+    body::{Body, Bytes},
+    extract::Extension,
+    http::{HeaderMap, Method as HttpMethod, StatusCode},
+    response::{IntoResponse, Response},
+    routing::any,
+    Router,
+};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Manager};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio::task::JoinSet;
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MockResponseDefinition {
+    pub status_code: u16,
+    pub headers: Vec<Vec<String>>,
+    pub body: String,
+    /// Render `{{...}}` templates in the body and header values, see `template::render`
+    #[serde(default)]
+    pub templated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MockRequestDefinition {
+    pub method: String,
+    /// `{name}` segments match any value; a query string (`/pets?status=sold`) only matches
+    /// requests carrying those parameters, in any order
+    pub path: String,
+    /// Default response, used when no rule matches
+    pub response: MockResponseDefinition,
+    /// Conditional responses for the same endpoint
+    #[serde(default)]
+    pub rules: Vec<MockResponseRule>,
+    /// Responses returned one per call instead of `response` when no rule matches
+    #[serde(default)]
+    pub sequence: Vec<MockResponseDefinition>,
+    /// What happens once the sequence is used up; `Stick` by default
+    #[serde(default)]
+    pub sequence_mode: Option<SequenceMode>,
+    /// Scenario this endpoint takes part in; every scenario starts in "Started"
+    #[serde(default)]
+    pub scenario: Option<String>,
+    /// Only match while the scenario is in this state
+    #[serde(default)]
+    pub required_state: Option<String>,
+    /// Move the scenario to this state once matched
+    #[serde(default)]
+    pub new_state: Option<String>,
+    /// Overrides the server's faults field by field
+    #[serde(default)]
+    pub faults: Option<MockFaults>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SequenceMode {
+    /// Keep returning the last response
+    Stick,
+    /// Start over from the first response
+    Cycle,
+}
+
+impl MockRequestDefinition {
+    /// A plain endpoint with a single response.
+    pub fn new(method: &str, path: &str, response: MockResponseDefinition) -> Self {
+        MockRequestDefinition {
+            method: method.to_string(),
+            path: path.to_string(),
+            response,
+            rules: Vec::new(),
+            sequence: Vec::new(),
+            sequence_mode: None,
+            scenario: None,
+            required_state: None,
+            new_state: None,
+            faults: None,
+        }
+    }
+}
+
+/// Initial state of every scenario.
+pub const SCENARIO_STARTED: &str = "Started";
+
+/// Accept errors in a row after which a server gives up and is reported as crashed.
+const MAX_ACCEPT_FAILURES: u32 = 50;
+
+/// How long an "empty" fault leaves an HTTP/2 connection to send its GOAWAY before closing.
+const GOAWAY_GRACE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StartMockArgs {
+    pub collection_id: String,
+    /// Shown in port conflict errors, defaults to the collection id
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Interface to listen on, 127.0.0.1 by default; 0.0.0.0 exposes the mock to the network
+    #[serde(default)]
+    pub bind_address: Option<String>,
+    /// 0 picks a free port, returned by `start_mock_server`
+    pub port: u16,
+    pub requests: Vec<MockRequestDefinition>,
+    /// In-memory CRUD collections, answered when no endpoint matches
+    #[serde(default)]
+    pub resources: Vec<MockResourceDefinition>,
+    /// Faults applied to every request, unless its endpoint overrides them
+    #[serde(default)]
+    pub faults: Option<MockFaults>,
+    /// Requests kept in the journal, 500 by default
+    #[serde(default)]
+    pub journal_size: Option<usize>,
+    /// Forward requests nothing matched to a real service, recording the exchanges
+    #[serde(default)]
+    pub proxy: Option<MockProxyConfig>,
+    /// Serve HTTPS instead of plain HTTP
+    #[serde(default)]
+    pub tls: Option<MockTlsConfig>,
+}
+
+/// Default interface of a mock server, reachable from this machine only.
+pub const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1";
+
+/// Match a mock path pattern against a request path and collect the values of its
+/// `{name}` segments, which match any value.
+fn path_params(pattern: &str, actual: &str) -> Option<HashMap<String, String>> {
+    let pat_segments: Vec<&str> = pattern.split('/').collect();
+    let act_segments: Vec<&str> = actual.split('/').collect();
+    if pat_segments.len() != act_segments.len() {
+        return None;
+    }
+    let mut params = HashMap::new();
+    for (p, a) in pat_segments.iter().zip(act_segments.iter()) {
+        if let Some(name) = p.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+            params.insert(name.to_string(), a.to_string());
+        } else if p != a {
+            return None;
+        }
+    }
+    Some(params)
+}
+
+/// Normalise a stored mock path so that it always starts with '/'.
+fn norm(p: &str) -> String {
+    if p.starts_with('/') {
+        p.to_string()
+    } else {
+        format!("/{}", p)
+    }
+}
+
+/// An endpoint prepared for matching: split path and query, rules compiled and ordered.
+struct Endpoint {
+    definition: MockRequestDefinition,
+    method: String,
+    path: String,
+    /// Parameters required by a query string in the endpoint path
+    query: Option<Vec<(String, String)>>,
+    rules: Vec<(CompiledMatch, MockResponseDefinition)>,
+    /// Calls answered from `sequence` so far
+    calls: AtomicUsize,
+}
+
+impl Endpoint {
+    fn new(definition: MockRequestDefinition) -> Result<Self, String> {
+        let full_path = norm(&definition.path);
+        let (path, query) = match full_path.split_once('?') {
+            Some((path, query)) => (path.to_string(), Some(parse_query(query))),
+            None => (full_path, None),
+        };
+
+        let mut ordered: Vec<&MockResponseRule> = definition.rules.iter().collect();
+        ordered.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        let rules = ordered
+            .into_iter()
+            .map(|rule| {
+                CompiledMatch::new(rule.when.clone())
+                    .map(|matcher| (matcher, rule.response.clone()))
+                    .map_err(|e| format!("{} {}: {}", definition.method, definition.path, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Endpoint {
+            method: definition.method.to_uppercase(),
+            path,
+            query,
+            rules,
+            definition,
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    /// The first matching rule's response, else the next one of the sequence, else the
+    /// endpoint's default one.
+    fn respond(&self, ctx: &RequestContext) -> &MockResponseDefinition {
+        if let Some((_, response)) = self.rules.iter().find(|(matcher, _)| matcher.matches(ctx)) {
+            return response;
+        }
+        let sequence = &self.definition.sequence;
+        if sequence.is_empty() {
+            return &self.definition.response;
+        }
+        let call = self.calls.fetch_add(1, Ordering::Relaxed);
+        let index = match self.definition.sequence_mode {
+            Some(SequenceMode::Cycle) => call % sequence.len(),
+            Some(SequenceMode::Stick) | None => call.min(sequence.len() - 1),
+        };
+        &sequence[index]
+    }
+}
+
+/// Current state by scenario name. A std lock, as matching reads it synchronously.
+struct Scenarios(std::sync::Mutex<HashMap<String, String>>);
+
+impl Scenarios {
+    /// Every scenario the endpoints take part in, in its initial state.
+    fn new<'d>(definitions: impl Iterator<Item = &'d MockRequestDefinition>) -> Self {
+        let states = definitions
+            .filter_map(|d| d.scenario.clone())
+            .map(|name| (name, SCENARIO_STARTED.to_string()))
+            .collect();
+        Scenarios(std::sync::Mutex::new(states))
+    }
+
+    fn states(&self) -> HashMap<String, String> {
+        self.0.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    fn set(&self, scenario: &str, new_state: &str) -> Result<(), String> {
+        let mut states = self.0.lock().map_err(|e| e.to_string())?;
+        let state = states
+            .get_mut(scenario)
+            .ok_or_else(|| format!("No mock scenario named {}", scenario))?;
+        *state = new_state.to_string();
+        Ok(())
+    }
+
+    fn reset(&self) {
+        if let Ok(mut states) = self.0.lock() {
+            states
+                .values_mut()
+                .for_each(|state| *state = SCENARIO_STARTED.to_string());
+        }
+    }
+
+    /// Whether the endpoint's scenario is in the state it requires.
+    fn allows(&self, definition: &MockRequestDefinition) -> bool {
+        let (Some(scenario), Some(required)) = (&definition.scenario, &definition.required_state)
+        else {
+            return true;
+        };
+        self.0
+            .lock()
+            .is_ok_and(|s| s.get(scenario) == Some(required))
+    }
+
+    /// Apply the endpoint's scenario transition after it matched.
+    fn transition(&self, definition: &MockRequestDefinition) {
+        if let (Some(scenario), Some(new_state)) = (&definition.scenario, &definition.new_state) {
+            if let Ok(mut states) = self.0.lock() {
+                states.insert(scenario.clone(), new_state.clone());
+            }
+        }
+    }
+}
+
+/// Where and for whom a mock server listens.
+#[derive(Debug, Serialize, Clone)]
+pub struct MockServerInfo {
+    pub collection_id: String,
+    pub name: Option<String>,
+    pub address: SocketAddr,
+    pub https: bool,
+    /// Milliseconds since the Unix epoch
+    pub started_at: i64,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MockServerStatus {
+    Running,
+    Stopped,
+    /// The server task ended without being asked to
+    Crashed,
+}
+
+struct Lifecycle {
+    status: MockServerStatus,
+    stopped: Option<Instant>,
+    last_error: Option<String>,
+}
+
+/// Requests answered so far, by outcome.
+#[derive(Default)]
+struct Counters {
+    requests: AtomicU64,
+    matched: AtomicU64,
+    unmatched: AtomicU64,
+    faults: AtomicU64,
+}
+
+/// A server as listed by `list_mock_servers`.
+#[derive(Debug, Serialize)]
+pub struct MockServerSummary {
+    #[serde(flatten)]
+    pub info: MockServerInfo,
+    pub status: MockServerStatus,
+    pub uptime_ms: u64,
+    pub requests: u64,
+    pub matched: u64,
+    /// Requests that got a 404
+    pub unmatched: u64,
+    pub faults: u64,
+    pub last_error: Option<String>,
+}
+
+/// Everything a running mock server needs to answer requests.
+pub struct MockServer {
+    pub info: MockServerInfo,
+    endpoints: Vec<Endpoint>,
+    pub resources: Vec<MockResource>,
+    scenarios: Scenarios,
+    faults: Option<MockFaults>,
+    pub journal: MockJournal,
+    pub proxy: Option<MockProxy>,
+    started: Instant,
+    lifecycle: std::sync::Mutex<Lifecycle>,
+    counters: Counters,
+    /// Ends the server task; taken by `stop`
+    shutdown: std::sync::Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl MockServer {
+    pub async fn new(
+        info: MockServerInfo,
+        requests: Vec<MockRequestDefinition>,
+        resources: Vec<MockResourceDefinition>,
+        faults: Option<MockFaults>,
+        journal: MockJournal,
+        proxy: Option<MockProxy>,
+    ) -> Result<Self, String> {
+        let endpoints = requests
+            .into_iter()
+            .map(Endpoint::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut loaded = Vec::new();
+        for resource in resources {
+            loaded.push(MockResource::new(resource).await?);
+        }
+        let scenarios = Scenarios::new(endpoints.iter().map(|e| &e.definition));
+        Ok(MockServer {
+            info,
+            endpoints,
+            resources: loaded,
+            scenarios,
+            faults,
+            journal,
+            proxy,
+            started: Instant::now(),
+            lifecycle: std::sync::Mutex::new(Lifecycle {
+                status: MockServerStatus::Running,
+                stopped: None,
+                last_error: None,
+            }),
+            counters: Counters::default(),
+            shutdown: std::sync::Mutex::new(None),
+        })
+    }
+
+    pub fn status(&self) -> MockServerStatus {
+        self.lifecycle
+            .lock()
+            .map(|l| l.status)
+            .unwrap_or(MockServerStatus::Crashed)
+    }
+
+    fn set_error(&self, error: String) {
+        if let Ok(mut lifecycle) = self.lifecycle.lock() {
+            lifecycle.last_error = Some(error);
+        }
+    }
+
+    /// Ask the server task to end. Its status changes once it has.
+    pub fn stop(&self) {
+        if let Some(tx) = self.shutdown.lock().ok().and_then(|mut s| s.take()) {
+            let _ = tx.send(());
+        }
+    }
+
+    /// Called when the server task ended, with the reason if it was not asked to.
+    fn terminated(&self, error: Option<String>) {
+        if let Ok(mut lifecycle) = self.lifecycle.lock() {
+            lifecycle.stopped = Some(Instant::now());
+            lifecycle.status = match error {
+                Some(error) => {
+                    lifecycle.last_error = Some(error);
+                    MockServerStatus::Crashed
+                }
+                None => MockServerStatus::Stopped,
+            };
+        }
+    }
+
+    /// Count a handled request and add it to the journal.
+    fn record(&self, entry: JournalEntry) {
+        let counters = &self.counters;
+        counters.requests.fetch_add(1, Ordering::Relaxed);
+        match entry.matched {
+            Some(_) => counters.matched.fetch_add(1, Ordering::Relaxed),
+            None => counters.unmatched.fetch_add(1, Ordering::Relaxed),
+        };
+        if entry.fault.is_some() {
+            counters.faults.fetch_add(1, Ordering::Relaxed);
+        }
+        self.journal.record(entry);
+    }
+
+    pub fn summary(&self) -> MockServerSummary {
+        let (status, stopped, last_error) = match self.lifecycle.lock() {
+            Ok(l) => (l.status, l.stopped, l.last_error.clone()),
+            Err(_) => (MockServerStatus::Crashed, None, None),
+        };
+        let uptime = stopped.unwrap_or_else(Instant::now) - self.started;
+        let counters = &self.counters;
+        MockServerSummary {
+            info: self.info.clone(),
+            status,
+            uptime_ms: uptime.as_millis() as u64,
+            requests: counters.requests.load(Ordering::Relaxed),
+            matched: counters.matched.load(Ordering::Relaxed),
+            unmatched: counters.unmatched.load(Ordering::Relaxed),
+            faults: counters.faults.load(Ordering::Relaxed),
+            last_error,
+        }
+    }
+
+    pub fn scenarios(&self) -> HashMap<String, String> {
+        self.scenarios.states()
+    }
+
+    pub fn set_scenario_state(&self, scenario: &str, new_state: &str) -> Result<(), String> {
+        self.scenarios.set(scenario, new_state)
+    }
+
+    /// Put one scenario, or all of them along with the response sequences, back at the start.
+    pub fn reset_scenarios(&self, scenario: Option<&str>) -> Result<(), String> {
+        let Some(scenario) = scenario else {
+            self.scenarios.reset();
+            for endpoint in &self.endpoints {
+                endpoint.calls.store(0, Ordering::Relaxed);
+            }
+            return Ok(());
+        };
+        self.scenarios.set(scenario, SCENARIO_STARTED)
+    }
+
+    /// Look up a resource by name, or all of them.
+    pub fn resources(&self, name: Option<&str>) -> Result<Vec<&MockResource>, String> {
+        match name {
+            None => Ok(self.resources.iter().collect()),
+            Some(name) => self
+                .resources
+                .iter()
+                .find(|r| r.name == name)
+                .map(|r| vec![r])
+                .ok_or_else(|| format!("No mock resource named {}", name)),
+        }
+    }
+
+    /// Pass 1: endpoints with a query string whose parameters are all in the request.
+    /// Pass 2: generic endpoints (no query string), ignoring the request's query params.
+    fn find(
+        &self,
+        method: &str,
+        ctx: &RequestContext,
+    ) -> Option<(&Endpoint, HashMap<String, String>)> {
+        let candidates = |with_query: bool| {
+            self.endpoints.iter().filter(move |e| {
+                e.method == method
+                    && self.scenarios.allows(&e.definition)
+                    && e.query.is_some() == with_query
+                    && e.query
+                        .as_ref()
+                        .is_none_or(|q| query_contains(q, &ctx.query))
+            })
+        };
+        let find_in = |with_query: bool| {
+            candidates(with_query)
+                .find_map(|e| path_params(&e.path, ctx.path).map(|params| (e, params)))
+        };
+        find_in(true).or_else(|| find_in(false))
+    }
+}
+
+fn build_response(response: &MockResponseDefinition, ctx: &RequestContext) -> Response {
+    let text = |value: &String| {
+        if response.templated {
+            render(value, ctx)
+        } else {
+            value.clone()
+        }
+    };
+    let mut hm = HeaderMap::new();
+    for pair in &response.headers {
+        if pair.len() == 2 {
+            if let (Ok(name), Ok(val)) = (
+                axum::http::HeaderName::from_bytes(pair[0].as_bytes()),
+                axum::http::HeaderValue::from_bytes(text(&pair[1]).as_bytes()),
+            ) {
+                hm.append(name, val);
+            }
+        }
+    }
+
+    let status = StatusCode::from_u16(response.status_code).unwrap_or(StatusCode::OK);
+    (status, hm, text(&response.body)).into_response()
+}
+
+async fn handle(
+    server: Arc<MockServer>,
+    control: Option<ConnectionControl>,
+    method: HttpMethod,
+    uri: axum::http::Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let started = Instant::now();
+    let target_method = method.as_str().to_uppercase();
+    let raw_body = body;
+    let body = String::from_utf8_lossy(&raw_body);
+    let mut ctx = RequestContext::new(&target_method, &uri, &headers, &body);
+    let mut entry = JournalEntry {
+        id: 0,
+        collection_id: String::new(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        method: target_method.clone(),
+        uri: ctx.uri.to_string(),
+        headers: headers
+            .iter()
+            .map(|(k, v)| vec![k.to_string(), String::from_utf8_lossy(v.as_bytes()).into()])
+            .collect(),
+        body: body.to_string(),
+        matched: None,
+        status: None,
+        fault: None,
+        latency_ms: 0,
+    };
+
+    let matched = server.find(&target_method, &ctx);
+    let faults = MockFaults::merged(
+        matched
+            .as_ref()
+            .and_then(|(e, _)| e.definition.faults.as_ref()),
+        server.faults.as_ref(),
+    );
+    entry.matched = matched
+        .as_ref()
+        .map(|(e, _)| format!("{} {}", e.method, e.definition.path));
+
+    faults.delay().await;
+    let response = if let (Some(fault), Some(control)) = (faults.connection_fault(), control) {
+        // The connection is closed instead of sending this response
+        control.set(fault);
+        entry.fault = Some(format!("connection {}", fault.as_str()));
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    } else if let Some(response) = faults.error_response() {
+        entry.fault = Some(format!("error {}", response.status().as_u16()));
+        entry.status = Some(response.status().as_u16());
+        response
+    } else {
+        let (response, source) = respond(&server, matched, &mut ctx, &raw_body).await;
+        if source.is_some() {
+            entry.matched = source;
+        }
+        entry.status = Some(response.status().as_u16());
+        faults.shape(response).await
+    };
+
+    entry.latency_ms = started.elapsed().as_millis() as u64;
+    server.record(entry);
+    response
+}
+
+/// Answer from the matched endpoint, else a resource or the proxy (returning which one),
+/// else 404.
+async fn respond(
+    server: &MockServer,
+    matched: Option<(&Endpoint, HashMap<String, String>)>,
+    ctx: &mut RequestContext<'_>,
+    body: &Bytes,
+) -> (Response, Option<String>) {
+    if let Some((endpoint, params)) = matched {
+        ctx.path_params = params;
+        server.scenarios.transition(&endpoint.definition);
+        return (build_response(endpoint.respond(ctx), ctx), None);
+    }
+
+    for resource in &server.resources {
+        if let Some(response) = resource.handle(ctx).await {
+            return (response, Some(format!("resource {}", resource.name)));
+        }
+    }
+
+    if let Some(proxy) = &server.proxy {
+        let response = proxy.forward(ctx, body).await;
+        return (response, Some(format!("proxy {}", proxy.upstream())));
+    }
+
+    (StatusCode::NOT_FOUND.into_response(), None)
+}
+
+/// Serve one HTTP/1.1 or HTTP/2 connection. When a handler asks for a connection fault,
+/// its request fails and the whole connection is dropped, which with HTTP/2 also ends the
+/// other streams. `control` is the one given to the connection's `FaultStream`.
+async fn serve_connection<I>(io: I, app: Router, control: ConnectionControl)
+where
+    I: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let connection_control = control.clone();
+    let service =
+        hyper::service::service_fn(move |mut request: hyper::Request<hyper::body::Incoming>| {
+            let (app, connection_control) = (app.clone(), connection_control.clone());
+            // Per request, so that other streams of an HTTP/2 connection are not affected
+            let control = ConnectionControl::default();
+            request.extensions_mut().insert(control.clone());
+            async move {
+                let response = app
+                    .oneshot(request.map(Body::new))
+                    .await
+                    .unwrap_or_else(|e| match e {});
+                match control.get() {
+                    Some(fault) => {
+                        connection_control.set(fault);
+                        Err(io::Error::other("injected connection fault"))
+                    }
+                    None => Ok(response),
+                }
+            }
+        });
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let connection = builder.serve_connection(TokioIo::new(io), service);
+    tokio::pin!(connection);
+    tokio::select! {
+        _ = connection.as_mut() => {}
+        fault = control.faulted() => {
+            if fault == ConnectionFault::Empty {
+                // Tell HTTP/2 clients the connection is going away, then close it
+                connection.as_mut().graceful_shutdown();
+                let _ = tokio::time::timeout(GOAWAY_GRACE, connection.as_mut()).await;
+            }
+            // Dropping the connection closes the socket, with an RST for a reset
+        }
+    }
+}
+
+/// Accept connections until shutdown, over TLS when an acceptor is given. Connections are
+/// served here rather than through `axum::serve` so that an injected fault can drop or
+/// reset them. Fails when the listener keeps failing to accept.
+async fn serve(
+    app_handle: AppHandle,
+    server: Arc<MockServer>,
+    listener: TcpListener,
+    tls: Option<TlsAcceptor>,
+    app: Router,
+    mut shutdown: oneshot::Receiver<()>,
+) -> Result<(), String> {
+    let mut connections = JoinSet::new();
+    let mut accept_failures = 0;
+    let result = loop {
+        tokio::select! {
+            _ = &mut shutdown => break Ok(()),
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => {
+                        accept_failures = 0;
+                        stream
+                    }
+                    Err(e) => {
+                        crate::rust_error!(&app_handle, "Mock server error: {}", e);
+                        server.set_error(e.to_string());
+                        accept_failures += 1;
+                        if accept_failures == MAX_ACCEPT_FAILURES {
+                            break Err(format!(
+                                "Stopped accepting connections after {} failures in a row: {}",
+                                accept_failures, e
+                            ));
+                        }
+                        // Such errors (e.g. too many open files) tend to repeat immediately
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        continue;
+                    }
+                };
+                let control = ConnectionControl::default();
+                let stream = FaultStream::new(stream, control.clone());
+                let (app, tls, app_handle, server) =
+                    (app.clone(), tls.clone(), app_handle.clone(), Arc::clone(&server));
+                connections.spawn(async move {
+                    let Some(tls) = tls else {
+                        return serve_connection(stream, app, control).await;
+                    };
+                    match tls.accept(stream).await {
+                        Ok(stream) => serve_connection(stream, app, control).await,
+                        // Typically a client that does not trust the certificate
+                        Err(e) => {
+                            crate::rust_warn!(&app_handle, "Mock server TLS handshake failed: {}", e);
+                            server.set_error(format!("TLS handshake failed: {}", e));
+                        }
+                    }
+                });
+            }
+        }
+    };
+    connections.abort_all();
+    result
+}
+
+/// `~/.curl-ui`, where the local mock CA is kept.
+fn config_dir(app_handle: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let home_dir = app_handle.path().home_dir().map_err(|e| e.to_string())?;
+    Ok(home_dir.join(".curl-ui"))
+}
+
+/// Why the mock could not listen on `address:port`, naming the curl-ui mock holding the
+/// port if there is one.
+fn bind_error(
+    servers: &HashMap<String, Arc<MockServer>>,
+    address: &str,
+    port: u16,
+    e: io::Error,
+) -> String {
+    match e.kind() {
+        io::ErrorKind::AddrInUse => {
+            let owner = servers
+                .values()
+                .find(|s| s.info.address.port() == port && s.status() == MockServerStatus::Running);
+            match owner {
+                Some(owner) => format!(
+                    "Port {} is already used by the curl-ui mock server \"{}\"",
+                    port,
+                    owner
+                        .info
+                        .name
+                        .as_deref()
+                        .unwrap_or(&owner.info.collection_id)
+                ),
+                None => format!(
+                    "Port {} on {} is already in use by another program",
+                    port, address
+                ),
+            }
+        }
+        io::ErrorKind::AddrNotAvailable => format!(
+            "Cannot listen on {}: the address does not belong to this machine",
+            address
+        ),
+        io::ErrorKind::PermissionDenied => format!(
+            "Permission denied listening on {}:{} (ports below 1024 usually need elevated rights)",
+            address, port
+        ),
+        _ => format!("Failed to listen on {}:{}: {}", address, port, e),
+    }
+}
+
+/// Bind the mock's listener. `previous` is the address of the server being restarted, whose
+/// listener may take a moment to close.
+async fn bind(
+    servers: &HashMap<String, Arc<MockServer>>,
+    address: &str,
+    port: u16,
+    previous: Option<SocketAddr>,
+) -> Result<TcpListener, String> {
+    let mut retries = 20;
+    loop {
+        match TcpListener::bind((address, port)).await {
+            Ok(listener) => return Ok(listener),
+            Err(e)
+                if e.kind() == io::ErrorKind::AddrInUse
+                    && port != 0
+                    && previous.is_some_and(|p| p.port() == port)
+                    && retries > 0 =>
+            {
+                retries -= 1;
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            Err(e) => return Err(bind_error(servers, address, port, e)),
+        }
+    }
+}
+
+/// Start (or restart) the mock server of a collection and return the port it listens on.
+#[command]
+pub async fn start_mock_server(
+    app_handle: AppHandle,
+    state: tauri::State<'_, MockServerState>,
+    args: StartMockArgs,
+) -> Result<u16, String> {
+    // Held until the new server is registered, so that starts do not race
+    let mut servers = state.servers.lock().await;
+    let previous = servers.remove(&args.collection_id);
+    if let Some(previous) = &previous {
+        previous.stop();
+    }
+
+    let journal = MockJournal::new(
+        args.collection_id.clone(),
+        args.journal_size.unwrap_or(DEFAULT_JOURNAL_SIZE),
+        app_handle.clone(),
+    );
+    let proxy = args.proxy.map(MockProxy::new).transpose()?;
+    let tls = match &args.tls {
+        Some(config) => Some(crate::mock_tls::acceptor(
+            config,
+            &config_dir(&app_handle)?,
+        )?),
+        None => None,
+    };
+
+    let address = args
+        .bind_address
+        .as_deref()
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .unwrap_or(DEFAULT_BIND_ADDRESS);
+    let listener = bind(
+        &servers,
+        address,
+        args.port,
+        previous.map(|p| p.info.address),
+    )
+    .await?;
+    let info = MockServerInfo {
+        collection_id: args.collection_id.clone(),
+        name: args.name,
+        address: listener.local_addr().map_err(|e| e.to_string())?,
+        https: tls.is_some(),
+        started_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let port = info.address.port();
+
+    let mut server = MockServer::new(
+        info,
+        args.requests,
+        args.resources,
+        args.faults,
+        journal,
+        proxy,
+    )
+    .await?;
+    let (tx, rx) = oneshot::channel::<()>();
+    server.shutdown = std::sync::Mutex::new(Some(tx));
+    let server = Arc::new(server);
+    let app_server = Arc::clone(&server);
+
+    let app = Router::new().fallback(any(
+        move |control: Option<Extension<ConnectionControl>>,
+              method: HttpMethod,
+              uri: axum::http::Uri,
+              headers: HeaderMap,
+              body: Bytes| {
+            let control = control.map(|Extension(control)| control);
+            handle(Arc::clone(&app_server), control, method, uri, headers, body)
+        },
+    ));
+
+    servers.insert(args.collection_id.clone(), Arc::clone(&server));
+
+    // Report the end of the server task, and why if nobody asked for it
+    let task = tokio::spawn(serve(
+        app_handle.clone(),
+        Arc::clone(&server),
+        listener,
+        tls,
+        app,
+        rx,
+    ));
+    tokio::spawn(async move {
+        let error = match task.await {
+            Ok(result) => result.err(),
+            Err(e) => Some(format!("Mock server task failed: {}", e)),
+        };
+        if let Some(error) = &error {
+            crate::rust_error!(&app_handle, "{}", error);
+        }
+        server.terminated(error);
+    });
+
+    Ok(port)
+}
+
+/// Stop a mock server. It stays listed, with its journal, until restarted or removed.
+#[command]
+pub async fn stop_mock_server(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+) -> Result<(), String> {
+    // A no-op for a server that already stopped or crashed
+    known_server(&state, &collection_id).await?.stop();
+    Ok(())
+}
+
+/// Stop a mock server if it runs and forget it, e.g. when its mock is deleted.
+#[command]
+pub async fn remove_mock_server(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+) -> Result<(), String> {
+    if let Some(server) = state.servers.lock().await.remove(&collection_id) {
+        server.stop();
+    }
+    Ok(())
+}
+
+/// Stop every mock server, and with `remove` forget them too, e.g. when switching projects.
+/// Returns how many were running.
+#[command]
+pub async fn stop_all_mock_servers(
+    state: tauri::State<'_, MockServerState>,
+    remove: Option<bool>,
+) -> Result<usize, String> {
+    let mut servers = state.servers.lock().await;
+    let count = servers
+        .values()
+        .filter(|s| s.status() == MockServerStatus::Running)
+        .count();
+    for server in servers.values() {
+        server.stop();
+    }
+    if remove.unwrap_or(false) {
+        servers.clear();
+    }
+    Ok(count)
+}
+
+/// Every known mock server, running, stopped or crashed, with its address, uptime and request
+/// counts.
+#[command]
+pub async fn list_mock_servers(
+    state: tauri::State<'_, MockServerState>,
+) -> Result<Vec<MockServerSummary>, String> {
+    let mut list: Vec<MockServerSummary> = state
+        .servers
+        .lock()
+        .await
+        .values()
+        .map(|s| s.summary())
+        .collect();
+    list.sort_by_key(|s| s.info.started_at);
+    Ok(list)
+}
+
+/// The collection's server, whether it runs, stopped or crashed.
+async fn known_server(
+    state: &MockServerState,
+    collection_id: &str,
+) -> Result<Arc<MockServer>, String> {
+    state
+        .servers
+        .lock()
+        .await
+        .get(collection_id)
+        .cloned()
+        .ok_or_else(|| "No mock server for this collection".to_string())
+}
+
+/// The collection's server, only while it runs.
+async fn running_server(
+    state: &MockServerState,
+    collection_id: &str,
+) -> Result<Arc<MockServer>, String> {
+    known_server(state, collection_id)
+        .await
+        .ok()
+        .filter(|server| server.status() == MockServerStatus::Running)
+        .ok_or_else(|| "No mock server running for this collection".to_string())
+}
+
+/// Current items of a running server's resources (or of one of them), by resource name.
+#[command]
+pub async fn get_mock_resources(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+    resource: Option<String>,
+) -> Result<HashMap<String, Vec<serde_json::Value>>, String> {
+    let server = running_server(&state, &collection_id).await?;
+    let mut items = HashMap::new();
+    for r in server.resources(resource.as_deref())? {
+        items.insert(r.name.clone(), r.items().await);
+    }
+    Ok(items)
+}
+
+/// Put a running server's resources (or one of them) back to their seed items.
+#[command]
+pub async fn reset_mock_resources(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+    resource: Option<String>,
+) -> Result<(), String> {
+    let server = running_server(&state, &collection_id).await?;
+    for r in server.resources(resource.as_deref())? {
+        r.reset().await;
+    }
+    Ok(())
+}
+
+/// Current state of every scenario of a running server.
+#[command]
+pub async fn get_mock_scenarios(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+) -> Result<HashMap<String, String>, String> {
+    Ok(running_server(&state, &collection_id).await?.scenarios())
+}
+
+#[command]
+pub async fn set_mock_scenario_state(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+    scenario: String,
+    scenario_state: String,
+) -> Result<(), String> {
+    running_server(&state, &collection_id)
+        .await?
+        .set_scenario_state(&scenario, &scenario_state)
+}
+
+/// Reset one scenario to "Started", or all scenarios and response sequences.
+#[command]
+pub async fn reset_mock_scenarios(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+    scenario: Option<String>,
+) -> Result<(), String> {
+    running_server(&state, &collection_id)
+        .await?
+        .reset_scenarios(scenario.as_deref())
+}
+
+/// Requests received by a server, oldest first. Kept after it stops.
+#[command]
+pub async fn get_mock_journal(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+    unmatched_only: Option<bool>,
+    limit: Option<usize>,
+) -> Result<Vec<JournalEntry>, String> {
+    Ok(known_server(&state, &collection_id)
+        .await?
+        .journal
+        .entries(unmatched_only.unwrap_or(false), limit))
+}
+
+#[command]
+pub async fn clear_mock_journal(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+) -> Result<(), String> {
+    known_server(&state, &collection_id).await?.journal.clear();
+    Ok(())
+}
+
+/// Turn a journal entry into an endpoint definition answering its method and path with an
+/// empty 200, for the frontend to add to the mock.
+#[command]
+pub async fn promote_mock_request(
+    state: tauri::State<'_, MockServerState>,
+    collection_id: String,
+    entry_id: u64,
+) -> Result<MockRequestDefinition, String> {
+    let entry = known_server(&state, &collection_id)
+        .await?
+        .journal
+        .get(entry_id)
+        .ok_or_else(|| format!("No journal entry {}", entry_id))?;
+    let path = entry.uri.split('?').next().unwrap_or("/").to_string();
+    Ok(MockRequestDefinition::new(
+        &entry.method,
+        &path,
+        MockResponseDefinition {
+            status_code: 200,
+            headers: Vec::new(),
+            body: String::new(),
+            templated: false,
+        },
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveRecordingsArgs {
+    pub collection_id: String,
+    /// Target `.mock.json` file
+    pub path: String,
+    /// Name of the saved mock, "Recorded Mock" by default
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Response headers to leave out, case-insensitive; defaults to volatile ones such as
+    /// Date, Server and Set-Cookie
+    #[serde(default)]
+    pub exclude_headers: Option<Vec<String>>,
+    /// One endpoint per method and path with the latest response, true by default
+    #[serde(default)]
+    pub dedupe: Option<bool>,
+    /// Forget the recordings once saved
+    #[serde(default)]
+    pub clear: bool,
+}
+
+/// Save what a proxying server recorded as an external mock file, returning its endpoints.
+#[command]
+pub async fn save_mock_recordings(
+    app_handle: AppHandle,
+    state: tauri::State<'_, MockServerState>,
+    args: SaveRecordingsArgs,
+) -> Result<Vec<MockRequestDefinition>, String> {
+    // Recordings outlive the server, so they can be saved after stopping it
+    let server = known_server(&state, &args.collection_id).await?;
+    let proxy = server
+        .proxy
+        .as_ref()
+        .ok_or_else(|| "This mock server is not in proxy mode".to_string())?;
+
+    let exclude = args.exclude_headers.unwrap_or_else(|| {
+        DEFAULT_EXCLUDED_HEADERS
+            .iter()
+            .map(|h| h.to_string())
+            .collect()
+    });
+    let definitions = crate::mock_proxy::to_definitions(
+        &proxy.recordings(),
+        &exclude,
+        args.dedupe.unwrap_or(true),
+    );
+    let mock = crate::mock_proxy::to_external_mock(
+        args.name.as_deref().unwrap_or("Recorded Mock"),
+        args.port.unwrap_or(3000),
+        &definitions,
+    );
+    let data = serde_json::to_string_pretty(&mock).map_err(|e| e.to_string())?;
+    tokio::fs::write(&args.path, data).await.map_err(|e| {
+        let msg = e.to_string();
+        crate::rust_error!(
+            &app_handle,
+            "Failed to save recordings to {}: {}",
+            args.path,
+            msg
+        );
+        msg
+    })?;
+
+    if args.clear {
+        proxy.clear();
+    }
+    Ok(definitions)
+}
+
+/// PEM certificate of the local CA issuing HTTPS mock certificates, created if needed and
+/// also written to `path` when given, to be added to the trust store of clients.
+#[command]
+pub async fn export_mock_ca(app_handle: AppHandle, path: Option<String>) -> Result<String, String> {
+    let (_, _, pem) = crate::mock_tls::load_or_create_ca(&config_dir(&app_handle)?)?;
+    if let Some(path) = path {
+        tokio::fs::write(&path, &pem).await.map_err(|e| {
+            let msg = e.to_string();
+            crate::rust_error!(&app_handle, "Failed to export mock CA to {}: {}", path, msg);
+            msg
+        })?;
+    }
+    Ok(pem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answers `/reset` and `/empty` with that connection fault, anything else with "ok".
+    fn app() -> Router {
+        Router::new().fallback(any(
+            |Extension(control): Extension<ConnectionControl>, uri: axum::http::Uri| async move {
+                match uri.path() {
+                    "/reset" => control.set(ConnectionFault::Reset),
+                    "/empty" => control.set(ConnectionFault::Empty),
+                    _ => {}
+                }
+                "ok"
+            },
+        ))
+    }
+
+    /// Serve `app()` the way `serve` does, without TLS.
+    async fn start() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let control = ConnectionControl::default();
+                let stream = FaultStream::new(stream, control.clone());
+                tokio::spawn(serve_connection(stream, app(), control));
+            }
+        });
+        address
+    }
+
+    async fn http1_get(address: SocketAddr, path: &str) -> io::Result<String> {
+        let mut stream = tokio::net::TcpStream::connect(address).await?;
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await?;
+        let mut buf = vec![0; 1024];
+        let n = stream.read(&mut buf).await?;
+        Ok(String::from_utf8_lossy(&buf[..n]).into())
+    }
+
+    fn templated_response(templated: bool) -> Response {
+        let uri: axum::http::Uri = "/pets/7?name=Rex".parse().unwrap();
+        let headers = HeaderMap::new();
+        let ctx = RequestContext::new("GET", &uri, &headers, "");
+        let response = MockResponseDefinition {
+            status_code: 200,
+            headers: vec![vec![
+                "X-Name".to_string(),
+                "{{request.query.name}}".to_string(),
+            ]],
+            body: "{{request.path}}".to_string(),
+            templated,
+        };
+        build_response(&response, &ctx)
+    }
+
+    #[tokio::test]
+    async fn templated_responses_render_headers_and_body() {
+        let response = templated_response(true);
+        assert_eq!(response.headers()["x-name"], "Rex");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"/pets/7");
+
+        let response = templated_response(false);
+        assert_eq!(response.headers()["x-name"], "{{request.query.name}}");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"{{request.path}}");
+    }
+
+    fn reply(status_code: u16) -> MockResponseDefinition {
+        MockResponseDefinition {
+            status_code,
+            headers: Vec::new(),
+            body: String::new(),
+            templated: false,
+        }
+    }
+
+    /// Status answered by `endpoint` to a GET of `uri` with the given `X-Tier` header.
+    fn status_for(endpoint: &Endpoint, uri: &str, tier: Option<&str>) -> u16 {
+        let uri: axum::http::Uri = uri.parse().unwrap();
+        let mut headers = HeaderMap::new();
+        if let Some(tier) = tier {
+            headers.insert("x-tier", tier.parse().unwrap());
+        }
+        let ctx = RequestContext::new("GET", &uri, &headers, "");
+        endpoint.respond(&ctx).status_code
+    }
+
+    #[test]
+    fn rules_are_tried_by_priority_before_the_default() {
+        let rule = |name: &str, priority: i32, when: serde_json::Value, status: u16| {
+            serde_json::from_value::<MockResponseRule>(serde_json::json!({
+                "name": name,
+                "priority": priority,
+                "when": when,
+                "response": { "status_code": status, "headers": [], "body": "" }
+            }))
+            .unwrap()
+        };
+        let mut definition = MockRequestDefinition::new("GET", "/pets", reply(200));
+        definition.rules = vec![
+            rule(
+                "any tier",
+                0,
+                serde_json::json!({ "headers": [{ "name": "X-Tier" }] }),
+                201,
+            ),
+            rule(
+                "gold",
+                10,
+                serde_json::json!({ "headers": [{ "name": "X-Tier", "equals": "gold" }] }),
+                202,
+            ),
+            rule(
+                "also any tier",
+                0,
+                serde_json::json!({ "headers": [{ "name": "X-Tier" }] }),
+                203,
+            ),
+        ];
+        let endpoint = Endpoint::new(definition).unwrap();
+
+        assert_eq!(status_for(&endpoint, "/pets", Some("gold")), 202);
+        // Equal priorities keep their declaration order
+        assert_eq!(status_for(&endpoint, "/pets", Some("silver")), 201);
+        assert_eq!(status_for(&endpoint, "/pets", None), 200);
+    }
+
+    #[test]
+    fn sequences_stick_or_cycle_after_the_last_response() {
+        let statuses = |mode: Option<SequenceMode>| {
+            let mut definition = MockRequestDefinition::new("GET", "/jobs", reply(200));
+            definition.sequence = vec![reply(202), reply(201)];
+            definition.sequence_mode = mode;
+            let endpoint = Endpoint::new(definition).unwrap();
+            (0..4)
+                .map(|_| status_for(&endpoint, "/jobs", None))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(statuses(None), [202, 201, 201, 201]);
+        assert_eq!(statuses(Some(SequenceMode::Stick)), [202, 201, 201, 201]);
+        assert_eq!(statuses(Some(SequenceMode::Cycle)), [202, 201, 202, 201]);
+
+        let mode = serde_json::from_value::<SequenceMode>(serde_json::json!("loop"));
+        assert!(mode.is_err());
+    }
+
+    #[test]
+    fn scenarios_gate_endpoints_and_move_between_states() {
+        let step = |scenario: &str, required: Option<&str>, new_state: Option<&str>| {
+            let mut definition = MockRequestDefinition::new("POST", "/cart", reply(200));
+            definition.scenario = Some(scenario.to_string());
+            definition.required_state = required.map(str::to_string);
+            definition.new_state = new_state.map(str::to_string);
+            definition
+        };
+        let add = step("cart", Some(SCENARIO_STARTED), Some("Filled"));
+        let checkout = step("cart", Some("Filled"), None);
+        let any_state = step("cart", None, Some("Emptied"));
+        let plain = MockRequestDefinition::new("GET", "/cart", reply(200));
+        let scenarios = Scenarios::new([&add, &checkout, &any_state, &plain].into_iter());
+        let started = HashMap::from([("cart".to_string(), SCENARIO_STARTED.to_string())]);
+        assert_eq!(scenarios.states(), started);
+
+        assert!(scenarios.allows(&add) && !scenarios.allows(&checkout));
+        assert!(scenarios.allows(&any_state) && scenarios.allows(&plain));
+        scenarios.transition(&add);
+        assert!(!scenarios.allows(&add) && scenarios.allows(&checkout));
+        // Matching without a new state leaves the scenario where it is
+        scenarios.transition(&checkout);
+        assert_eq!(scenarios.states()["cart"], "Filled");
+
+        scenarios.set("cart", "Emptied").unwrap();
+        assert!(!scenarios.allows(&add) && !scenarios.allows(&checkout));
+        let error = scenarios.set("wishlist", "Filled").unwrap_err();
+        assert_eq!(error, "No mock scenario named wishlist");
+        scenarios.reset();
+        assert_eq!(scenarios.states(), started);
+    }
+
+    #[tokio::test]
+    async fn reset_fault_sends_rst() {
+        let address = start().await;
+        let error = http1_get(address, "/reset").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionReset);
+    }
+
+    #[tokio::test]
+    async fn empty_fault_closes_without_reply() {
+        let address = start().await;
+        assert_eq!(http1_get(address, "/empty").await.unwrap(), "");
+        assert!(http1_get(address, "/")
+            .await
+            .unwrap()
+            .starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn http2_faults_close_the_connection_only_once() {
+        let address = start().await;
+        let client = reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .build()
+            .unwrap();
+        let get = |path: &str| client.get(format!("http://{}{}", address, path)).send();
+        for fault in ["/reset", "/empty"] {
+            assert_eq!(get("/").await.unwrap().text().await.unwrap(), "ok");
+            assert!(get(fault).await.is_err(), "{} got a response", fault);
+            // The faulted connection is gone and the next request gets a new one
+            assert_eq!(get("/").await.unwrap().text().await.unwrap(), "ok");
+        }
+    }
+}
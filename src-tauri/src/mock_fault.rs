@@ -0,0 +1,361 @@
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio::sync::Notify;
+
+/// Throttled bodies are sent in this many chunks per second.
+const CHUNKS_PER_SEC: u64 = 10;
+
+/// Latency and failures to inject, per server and per endpoint. Endpoint values win field by
+/// field over the server's.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MockFaults {
+    /// Fixed delay before answering, or the lower bound when `delay_max_ms` is set
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+    /// Pick the delay at random between `delay_ms` (or 0) and this
+    #[serde(default)]
+    pub delay_max_ms: Option<u64>,
+    /// Percentage (0-100) of requests answered with `error_status` instead
+    #[serde(default)]
+    pub error_rate: Option<f64>,
+    /// Defaults to 500
+    #[serde(default)]
+    pub error_status: Option<u16>,
+    /// "reset" (TCP RST) or "empty" (connection closed without a reply)
+    #[serde(default)]
+    pub connection_fault: Option<String>,
+    /// Percentage of requests hit by `connection_fault`, default 100
+    #[serde(default)]
+    pub connection_fault_rate: Option<f64>,
+    /// Send only this many body bytes while announcing the full Content-Length
+    #[serde(default)]
+    pub truncate_body_bytes: Option<usize>,
+    /// Deliver the body chunked at this rate
+    #[serde(default)]
+    pub bandwidth_bytes_per_sec: Option<u64>,
+}
+
+fn roll(percent: f64) -> bool {
+    rand::thread_rng().gen_bool((percent / 100.0).clamp(0.0, 1.0))
+}
+
+impl MockFaults {
+    pub fn merged(endpoint: Option<&MockFaults>, server: Option<&MockFaults>) -> MockFaults {
+        let (endpoint, server) = (
+            endpoint.cloned().unwrap_or_default(),
+            server.cloned().unwrap_or_default(),
+        );
+        MockFaults {
+            delay_ms: endpoint.delay_ms.or(server.delay_ms),
+            delay_max_ms: endpoint.delay_max_ms.or(server.delay_max_ms),
+            error_rate: endpoint.error_rate.or(server.error_rate),
+            error_status: endpoint.error_status.or(server.error_status),
+            connection_fault: endpoint.connection_fault.or(server.connection_fault),
+            connection_fault_rate: endpoint
+                .connection_fault_rate
+                .or(server.connection_fault_rate),
+            truncate_body_bytes: endpoint.truncate_body_bytes.or(server.truncate_body_bytes),
+            bandwidth_bytes_per_sec: endpoint
+                .bandwidth_bytes_per_sec
+                .or(server.bandwidth_bytes_per_sec),
+        }
+    }
+
+    pub async fn delay(&self) {
+        let min = self.delay_ms.unwrap_or(0);
+        let ms = match self.delay_max_ms {
+            Some(max) if max > min => rand::thread_rng().gen_range(min..=max),
+            _ => min,
+        };
+        if ms > 0 {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+        }
+    }
+
+    /// The connection fault to apply to this request, if it is hit.
+    pub fn connection_fault(&self) -> Option<ConnectionFault> {
+        let fault = match self.connection_fault.as_deref()? {
+            "reset" => ConnectionFault::Reset,
+            "empty" => ConnectionFault::Empty,
+            _ => return None,
+        };
+        roll(self.connection_fault_rate.unwrap_or(100.0)).then_some(fault)
+    }
+
+    /// An error response replacing the real one, if this request is hit.
+    pub fn error_response(&self) -> Option<Response> {
+        if !roll(self.error_rate?) {
+            return None;
+        }
+        let status = self
+            .error_status
+            .and_then(|s| StatusCode::from_u16(s).ok())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        Some((status, "Injected fault").into_response())
+    }
+
+    /// Truncate and/or throttle the body of a response.
+    pub async fn shape(&self, response: Response) -> Response {
+        if self.truncate_body_bytes.is_none() && self.bandwidth_bytes_per_sec.is_none() {
+            return response;
+        }
+        let (mut parts, body) = response.into_parts();
+        let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+
+        let sent = match self.truncate_body_bytes {
+            Some(limit) if limit < bytes.len() => {
+                // The announced length no longer matches, so clients see a cut-off transfer
+                parts
+                    .headers
+                    .insert(header::CONTENT_LENGTH, HeaderValue::from(bytes.len()));
+                bytes.slice(..limit)
+            }
+            _ => {
+                parts.headers.remove(header::CONTENT_LENGTH);
+                bytes
+            }
+        };
+        let chunk_size = self
+            .bandwidth_bytes_per_sec
+            .map(|bps| (bps / CHUNKS_PER_SEC).max(1) as usize)
+            .unwrap_or(sent.len().max(1));
+        Response::from_parts(parts, Body::from_stream(throttled(sent, chunk_size)))
+    }
+}
+
+/// Yield `bytes` in chunks of `chunk_size`, one per 1/CHUNKS_PER_SEC second.
+fn throttled(
+    bytes: Bytes,
+    chunk_size: usize,
+) -> impl futures_util::Stream<Item = Result<Bytes, io::Error>> {
+    futures_util::stream::unfold((bytes, true), move |(mut rest, first)| async move {
+        if rest.is_empty() {
+            return None;
+        }
+        if !first {
+            tokio::time::sleep(Duration::from_millis(1000 / CHUNKS_PER_SEC)).await;
+        }
+        let chunk = rest.split_to(chunk_size.min(rest.len()));
+        Some((Ok(chunk), (rest, false)))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionFault {
+    Reset,
+    Empty,
+}
+
+impl ConnectionFault {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectionFault::Reset => "reset",
+            ConnectionFault::Empty => "empty",
+        }
+    }
+}
+
+/// The connection fault asked for by a handler. One is given to each request, and one is
+/// shared by the connection and its stream, which the first faulted request sets.
+#[derive(Clone, Default)]
+pub struct ConnectionControl {
+    fault: Arc<AtomicU8>,
+    notify: Arc<Notify>,
+}
+
+impl ConnectionControl {
+    pub fn set(&self, fault: ConnectionFault) {
+        let value = match fault {
+            ConnectionFault::Reset => 1,
+            ConnectionFault::Empty => 2,
+        };
+        self.fault.store(value, Ordering::Relaxed);
+        self.notify.notify_one();
+    }
+
+    pub fn get(&self) -> Option<ConnectionFault> {
+        match self.fault.load(Ordering::Relaxed) {
+            1 => Some(ConnectionFault::Reset),
+            2 => Some(ConnectionFault::Empty),
+            _ => None,
+        }
+    }
+
+    /// Wait until a fault is set.
+    pub async fn faulted(&self) -> ConnectionFault {
+        loop {
+            if let Some(fault) = self.get() {
+                return fault;
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+/// A mock server connection that can be closed with a TCP reset.
+pub struct FaultStream {
+    inner: TcpStream,
+    control: ConnectionControl,
+}
+
+impl FaultStream {
+    pub fn new(inner: TcpStream, control: ConnectionControl) -> Self {
+        FaultStream { inner, control }
+    }
+}
+
+impl Drop for FaultStream {
+    fn drop(&mut self) {
+        if self.control.get() == Some(ConnectionFault::Reset) {
+            // A zero linger turns the close into an RST
+            let _ = self.inner.set_linger(Some(Duration::ZERO));
+        }
+    }
+}
+
+impl AsyncRead for FaultStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for FaultStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn delay_waits_at_least_delay_ms() {
+        let faults = MockFaults {
+            delay_ms: Some(50),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        faults.delay().await;
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn error_response_uses_rate_and_status() {
+        let never = MockFaults {
+            error_rate: Some(0.0),
+            ..Default::default()
+        };
+        assert!(never.error_response().is_none());
+        let always = MockFaults {
+            error_rate: Some(100.0),
+            error_status: Some(503),
+            ..Default::default()
+        };
+        assert_eq!(always.error_response().unwrap().status(), 503);
+    }
+
+    #[test]
+    fn connection_fault_kinds() {
+        let fault = |kind: &str, rate: f64| {
+            MockFaults {
+                connection_fault: Some(kind.to_string()),
+                connection_fault_rate: Some(rate),
+                ..Default::default()
+            }
+            .connection_fault()
+        };
+        assert_eq!(fault("reset", 100.0), Some(ConnectionFault::Reset));
+        assert_eq!(fault("empty", 100.0), Some(ConnectionFault::Empty));
+        assert_eq!(fault("empty", 0.0), None);
+        assert_eq!(fault("unknown", 100.0), None);
+    }
+
+    #[tokio::test]
+    async fn truncate_keeps_the_full_content_length() {
+        let faults = MockFaults {
+            truncate_body_bytes: Some(3),
+            ..Default::default()
+        };
+        let response = faults.shape("hello world".into_response()).await;
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], "11");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"hel");
+    }
+
+    #[tokio::test]
+    async fn bandwidth_throttles_the_body() {
+        // 10 bytes per second: 1 byte per chunk, 100ms apart
+        let faults = MockFaults {
+            bandwidth_bytes_per_sec: Some(10),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let response = faults.shape("abcd".into_response()).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"abcd");
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn endpoint_faults_win_field_by_field() {
+        let endpoint = MockFaults {
+            error_rate: Some(100.0),
+            ..Default::default()
+        };
+        let server = MockFaults {
+            error_rate: Some(0.0),
+            error_status: Some(502),
+            ..Default::default()
+        };
+        let merged = MockFaults::merged(Some(&endpoint), Some(&server));
+        assert_eq!(merged.error_rate, Some(100.0));
+        assert_eq!(merged.error_status, Some(502));
+    }
+}
@@ -0,0 +1,91 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Entries kept per server when `journal_size` is not given.
+pub const DEFAULT_JOURNAL_SIZE: usize = 500;
+
+/// A request received by a mock server and how it was answered.
+#[derive(Debug, Serialize, Clone)]
+pub struct JournalEntry {
+    pub id: u64,
+    pub collection_id: String,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: i64,
+    pub method: String,
+    /// Path and query string as received
+    pub uri: String,
+    pub headers: Vec<Vec<String>>,
+    pub body: String,
+    /// Method and path of the matched endpoint, `resource <name>` for a CRUD resource,
+    /// or None when the request got a 404
+    pub matched: Option<String>,
+    /// None when the connection was dropped by an injected fault
+    pub status: Option<u16>,
+    /// Injected fault, if any
+    pub fault: Option<String>,
+    pub latency_ms: u64,
+}
+
+/// Bounded log of the requests of one mock server. Each new entry is also emitted to the
+/// frontend as a `mock-request` event.
+pub struct MockJournal {
+    collection_id: String,
+    capacity: usize,
+    app_handle: AppHandle,
+    /// Entries and the id of the next one. A std lock, it is never held across an await
+    entries: Mutex<(VecDeque<JournalEntry>, u64)>,
+}
+
+impl MockJournal {
+    pub fn new(collection_id: String, capacity: usize, app_handle: AppHandle) -> Self {
+        MockJournal {
+            collection_id,
+            capacity: capacity.max(1),
+            app_handle,
+            entries: Mutex::new((VecDeque::new(), 1)),
+        }
+    }
+
+    /// Store an entry, dropping the oldest one when full. `id` and `collection_id` are set here.
+    pub fn record(&self, mut entry: JournalEntry) {
+        entry.collection_id = self.collection_id.clone();
+        if let Ok(mut guard) = self.entries.lock() {
+            let (entries, next_id) = &mut *guard;
+            entry.id = *next_id;
+            *next_id += 1;
+            if entries.len() == self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(entry.clone());
+        }
+        let _ = self.app_handle.emit("mock-request", entry);
+    }
+
+    /// Entries oldest first, optionally only the unmatched ones or the last `limit`.
+    pub fn entries(&self, unmatched_only: bool, limit: Option<usize>) -> Vec<JournalEntry> {
+        let Ok(guard) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let entries: Vec<JournalEntry> = guard
+            .0
+            .iter()
+            .filter(|e| !unmatched_only || e.matched.is_none())
+            .cloned()
+            .collect();
+        let skip = limit.map_or(0, |limit| entries.len().saturating_sub(limit));
+        entries.into_iter().skip(skip).collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<JournalEntry> {
+        let guard = self.entries.lock().ok()?;
+        guard.0.iter().find(|e| e.id == id).cloned()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut guard) = self.entries.lock() {
+            guard.0.clear();
+        }
+    }
+}
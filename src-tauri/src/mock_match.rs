@@ -0,0 +1,239 @@
+use crate::mock::MockResponseDefinition;
+use crate::template::RequestContext;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Conditions on the incoming request; all of them must hold.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MockMatch {
+    #[serde(default)]
+    pub headers: Vec<HeaderCondition>,
+    #[serde(default)]
+    pub query: Vec<QueryCondition>,
+    #[serde(default)]
+    pub json: Vec<JsonCondition>,
+    #[serde(default)]
+    pub body_regex: Option<String>,
+}
+
+/// Without `equals` or `regex` the header only has to be present.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeaderCondition {
+    pub name: String,
+    #[serde(default)]
+    pub equals: Option<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+}
+
+/// Without `equals` the parameter only has to be present.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueryCondition {
+    pub name: String,
+    #[serde(default)]
+    pub equals: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JsonCondition {
+    /// e.g. `$.user.role`
+    pub path: String,
+    pub equals: serde_json::Value,
+}
+
+/// An alternative response of an endpoint, used when its conditions match.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MockResponseRule {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Rules are tried from the highest priority down, in declaration order for equal ones
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub when: MockMatch,
+    pub response: MockResponseDefinition,
+}
+
+/// A `MockMatch` with its regexes compiled once, when the server starts.
+pub struct CompiledMatch {
+    conditions: MockMatch,
+    header_regexes: Vec<Option<Regex>>,
+    body_regex: Option<Regex>,
+}
+
+fn compile(pattern: &str, what: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("Invalid {} regex \"{}\": {}", what, pattern, e))
+}
+
+impl CompiledMatch {
+    pub fn new(conditions: MockMatch) -> Result<Self, String> {
+        let header_regexes = conditions
+            .headers
+            .iter()
+            .map(|h| {
+                h.regex
+                    .as_deref()
+                    .map(|r| compile(r, &format!("header {}", h.name)))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let body_regex = conditions
+            .body_regex
+            .as_deref()
+            .map(|r| compile(r, "body"))
+            .transpose()?;
+        Ok(CompiledMatch {
+            conditions,
+            header_regexes,
+            body_regex,
+        })
+    }
+
+    pub fn matches(&self, ctx: &RequestContext) -> bool {
+        let headers_ok = self
+            .conditions
+            .headers
+            .iter()
+            .zip(&self.header_regexes)
+            .all(|(condition, regex)| {
+                let Some(value) = ctx.header(&condition.name) else {
+                    return false;
+                };
+                condition.equals.as_ref().is_none_or(|e| e == value)
+                    && regex.as_ref().is_none_or(|r| r.is_match(value))
+            });
+        let query_ok = self.conditions.query.iter().all(|condition| {
+            ctx.query.iter().any(|(key, value)| {
+                key == &condition.name && condition.equals.as_ref().is_none_or(|e| e == value)
+            })
+        });
+        let json_ok = self.conditions.json.iter().all(|condition| {
+            ctx.json()
+                .and_then(|json| crate::jsonpath::select(json, &condition.path))
+                .is_some_and(|value| json_equals(value, &condition.equals))
+        });
+        let body_ok = self
+            .body_regex
+            .as_ref()
+            .is_none_or(|r| r.is_match(ctx.body));
+        headers_ok && query_ok && json_ok && body_ok
+    }
+}
+
+/// JSON equality, also accepting the expected value written as a string (`"42"` for 42).
+fn json_equals(actual: &serde_json::Value, expected: &serde_json::Value) -> bool {
+    actual == expected
+        || matches!(expected, serde_json::Value::String(s) if *s == crate::jsonpath::to_text(actual))
+}
+
+/// True when every `key=value` of `expected` is in the request query, in any order.
+pub fn query_contains(expected: &[(String, String)], actual: &[(String, String)]) -> bool {
+    expected.iter().all(|pair| actual.contains(pair))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::parse_query;
+    use axum::http::{HeaderMap, Uri};
+
+    /// Whether `conditions` (as JSON) match a request to `uri` with an `X-Tier: gold` header.
+    fn matches(conditions: serde_json::Value, uri: &str, body: &str) -> bool {
+        let conditions: MockMatch = serde_json::from_value(conditions).unwrap();
+        let uri: Uri = uri.parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tier", "gold".parse().unwrap());
+        let ctx = RequestContext::new("POST", &uri, &headers, body);
+        CompiledMatch::new(conditions).unwrap().matches(&ctx)
+    }
+
+    #[test]
+    fn header_conditions() {
+        let check = |condition| matches(serde_json::json!({ "headers": [condition] }), "/", "");
+        assert!(check(serde_json::json!({ "name": "X-Tier" })));
+        assert!(check(
+            serde_json::json!({ "name": "x-tier", "equals": "gold" })
+        ));
+        assert!(!check(
+            serde_json::json!({ "name": "X-Tier", "equals": "Gold" })
+        ));
+        assert!(check(
+            serde_json::json!({ "name": "X-Tier", "regex": "^g(old|reen)$" })
+        ));
+        assert!(!check(
+            serde_json::json!({ "name": "X-Tier", "regex": "^silver" })
+        ));
+        assert!(!check(serde_json::json!({ "name": "X-Missing" })));
+    }
+
+    #[test]
+    fn query_conditions() {
+        let conditions = serde_json::json!({ "query": [
+            { "name": "status", "equals": "sold" },
+            { "name": "page" }
+        ] });
+        assert!(matches(conditions.clone(), "/pets?page=2&status=sold", ""));
+        assert!(matches(
+            conditions.clone(),
+            "/pets?status=new&status=sold&page",
+            ""
+        ));
+        assert!(!matches(conditions.clone(), "/pets?status=sold", ""));
+        assert!(!matches(conditions, "/pets?status=new&page=1", ""));
+    }
+
+    #[test]
+    fn json_and_body_conditions() {
+        let body = r#"{"user": {"role": "admin", "age": 42}}"#;
+        let json = |path: &str, equals: serde_json::Value| {
+            let condition = serde_json::json!({ "path": path, "equals": equals });
+            serde_json::json!({ "json": [condition] })
+        };
+        assert!(matches(json("$.user.role", "admin".into()), "/", body));
+        assert!(matches(json("$.user.age", 42.into()), "/", body));
+        // Expected values written as strings still match numbers
+        assert!(matches(json("$.user.age", "42".into()), "/", body));
+        assert!(!matches(json("$.user.role", "guest".into()), "/", body));
+        assert!(!matches(
+            json("$.user.role", "admin".into()),
+            "/",
+            "not json"
+        ));
+
+        let regex = serde_json::json!({ "body_regex": "\"role\":\\s*\"admin\"" });
+        assert!(matches(regex.clone(), "/", body));
+        assert!(!matches(regex, "/", "{}"));
+    }
+
+    #[test]
+    fn invalid_regexes_are_reported() {
+        let conditions: MockMatch = serde_json::from_value(serde_json::json!({
+            "headers": [{ "name": "X-Tier", "regex": "(" }]
+        }))
+        .unwrap();
+        let error = CompiledMatch::new(conditions).err().unwrap();
+        assert!(
+            error.starts_with("Invalid header X-Tier regex"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn query_params_match_in_any_order() {
+        let expected = parse_query("status=sold&sort=name");
+        assert!(query_contains(
+            &expected,
+            &parse_query("sort=name&status=sold")
+        ));
+        assert!(query_contains(
+            &expected,
+            &parse_query("page=2&sort=name&status=sold")
+        ));
+        assert!(!query_contains(&expected, &parse_query("status=sold")));
+        assert!(!query_contains(
+            &expected,
+            &parse_query("status=new&sort=name")
+        ));
+    }
+}
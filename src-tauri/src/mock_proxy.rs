@@ -0,0 +1,358 @@
+use crate::mock::{MockRequestDefinition, MockResponseDefinition};
+use crate::template::RequestContext;
+use axum::body::Bytes;
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Headers that only concern one connection, never forwarded nor recorded.
+const HOP_BY_HOP: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "te",
+    "trailer",
+    "upgrade",
+    "host",
+    "content-length",
+];
+
+/// Response headers left out of saved mocks unless the caller gives its own list.
+pub const DEFAULT_EXCLUDED_HEADERS: &[&str] = &[
+    "date",
+    "server",
+    "set-cookie",
+    "age",
+    "via",
+    "x-request-id",
+    "x-amzn-requestid",
+    "cf-ray",
+];
+
+/// Recordings kept per server; the oldest ones are dropped past this.
+const MAX_RECORDINGS: usize = 1000;
+
+/// Responses with a larger body are relayed but not recorded.
+const MAX_RECORDED_BODY: usize = 1024 * 1024;
+
+/// Forward requests no endpoint or resource matched to a real service.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MockProxyConfig {
+    /// Base URL the request path is appended to, e.g. `https://api.example.com/v1`
+    pub upstream: String,
+    /// Keep the exchanges for `save_mock_recordings`, true by default
+    #[serde(default = "default_true")]
+    pub record: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A request forwarded upstream and the response it got.
+#[derive(Debug, Serialize, Clone)]
+pub struct Recording {
+    pub method: String,
+    /// Path and query string
+    pub uri: String,
+    pub status: u16,
+    pub headers: Vec<Vec<String>>,
+    pub body: String,
+}
+
+pub struct MockProxy {
+    config: MockProxyConfig,
+    client: reqwest::Client,
+    recordings: Mutex<VecDeque<Recording>>,
+}
+
+fn forwarded(name: &str) -> bool {
+    !HOP_BY_HOP.contains(&name.to_ascii_lowercase().as_str())
+}
+
+impl MockProxy {
+    pub fn new(config: MockProxyConfig) -> Result<Self, String> {
+        reqwest::Url::parse(&config.upstream)
+            .map_err(|e| format!("Invalid proxy upstream {}: {}", config.upstream, e))?;
+        // Redirects go back to the client as they are
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(MockProxy {
+            config,
+            client,
+            recordings: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    pub fn upstream(&self) -> &str {
+        &self.config.upstream
+    }
+
+    pub fn recordings(&self) -> Vec<Recording> {
+        self.recordings
+            .lock()
+            .map(|r| r.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut recordings) = self.recordings.lock() {
+            recordings.clear();
+        }
+    }
+
+    /// Keep an exchange, dropping the oldest one when full. Large bodies are not kept.
+    fn record(&self, recording: Recording) {
+        if recording.body.len() > MAX_RECORDED_BODY {
+            return;
+        }
+        if let Ok(mut recordings) = self.recordings.lock() {
+            if recordings.len() == MAX_RECORDINGS {
+                recordings.pop_front();
+            }
+            recordings.push_back(recording);
+        }
+    }
+
+    /// Send the request upstream and relay the answer, or a 502 when it cannot be reached.
+    pub async fn forward(&self, ctx: &RequestContext<'_>, body: &Bytes) -> Response {
+        let url = format!("{}{}", self.config.upstream.trim_end_matches('/'), ctx.uri);
+        let Ok(method) = reqwest::Method::from_bytes(ctx.method.as_bytes()) else {
+            return StatusCode::METHOD_NOT_ALLOWED.into_response();
+        };
+
+        let mut request = self.client.request(method, &url).body(body.clone());
+        for (name, value) in ctx.headers {
+            // Ask for an identity encoding so that recordings hold readable bodies
+            if forwarded(name.as_str()) && name != "accept-encoding" {
+                request = request.header(name.as_str(), value.as_bytes());
+            }
+        }
+
+        let upstream = match request.send().await {
+            Ok(upstream) => upstream,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    format!("Mock proxy failed to reach {}: {}", url, e),
+                )
+                    .into_response()
+            }
+        };
+        let status = upstream.status().as_u16();
+        let headers: Vec<Vec<String>> = upstream
+            .headers()
+            .iter()
+            .filter(|(name, _)| forwarded(name.as_str()))
+            .map(|(name, value)| {
+                vec![
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into(),
+                ]
+            })
+            .collect();
+        let bytes = match upstream.bytes().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    format!("Mock proxy failed to read the response of {}: {}", url, e),
+                )
+                    .into_response()
+            }
+        };
+
+        let mut response = (
+            StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY),
+            bytes.clone(),
+        )
+            .into_response();
+        for pair in &headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(pair[0].as_bytes()),
+                HeaderValue::from_str(&pair[1]),
+            ) {
+                response.headers_mut().append(name, value);
+            }
+        }
+
+        if self.config.record {
+            self.record(Recording {
+                method: ctx.method.to_string(),
+                uri: ctx.uri.to_string(),
+                status,
+                headers,
+                body: String::from_utf8_lossy(&bytes).into(),
+            });
+        }
+        response
+    }
+}
+
+/// Turn recordings into endpoints, dropping the `exclude`d response headers. With `dedupe`,
+/// each method and path (query string included) gives one endpoint, with the latest response.
+pub fn to_definitions(
+    recordings: &[Recording],
+    exclude: &[String],
+    dedupe: bool,
+) -> Vec<MockRequestDefinition> {
+    let mut definitions: Vec<MockRequestDefinition> = Vec::new();
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    for recording in recordings {
+        let definition = MockRequestDefinition::new(
+            &recording.method,
+            &recording.uri,
+            MockResponseDefinition {
+                status_code: recording.status,
+                headers: recording
+                    .headers
+                    .iter()
+                    .filter(|pair| !exclude.iter().any(|e| e.eq_ignore_ascii_case(&pair[0])))
+                    .cloned()
+                    .collect(),
+                body: recording.body.clone(),
+                // Recorded bodies are replayed as they were, `{{` included
+                templated: false,
+            },
+        );
+        let key = (recording.method.clone(), recording.uri.clone());
+        match seen.get(&key) {
+            Some(&index) if dedupe => definitions[index] = definition,
+            _ => {
+                seen.insert(key, definitions.len());
+                definitions.push(definition);
+            }
+        }
+    }
+    definitions
+}
+
+/// An external mock as the frontend saves it in `.mock.json` files.
+pub fn to_external_mock(name: &str, port: u16, definitions: &[MockRequestDefinition]) -> Value {
+    let endpoints: Vec<Value> = definitions
+        .iter()
+        .map(|definition| {
+            // The editor keeps one row per header name with all of its values
+            let mut rows: Vec<(String, Vec<String>)> = Vec::new();
+            for pair in &definition.response.headers {
+                match rows.iter_mut().find(|(key, _)| key.eq_ignore_ascii_case(&pair[0])) {
+                    Some((_, values)) => values.push(pair[1].clone()),
+                    None => rows.push((pair[0].clone(), vec![pair[1].clone()])),
+                }
+            }
+            json!({
+                "method": definition.method,
+                "path": definition.path,
+                "response": {
+                    "statusCode": definition.response.status_code,
+                    "headers": rows
+                        .into_iter()
+                        .map(|(key, values)| json!({ "key": key, "values": values, "enabled": true }))
+                        .collect::<Vec<_>>(),
+                    "body": definition.response.body,
+                    "templated": definition.response.templated,
+                    "enabled": true
+                }
+            })
+        })
+        .collect();
+    json!({
+        "id": crate::template::uuid_v4(),
+        "name": name,
+        "port": port,
+        "endpoints": endpoints
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(method: &str, uri: &str, status: u16, body: &str) -> Recording {
+        Recording {
+            method: method.to_string(),
+            uri: uri.to_string(),
+            status,
+            headers: vec![
+                vec!["Content-Type".to_string(), "application/json".to_string()],
+                vec![
+                    "Date".to_string(),
+                    "Sat, 17 Oct 2026 10:00:00 GMT".to_string(),
+                ],
+                vec!["X-Request-Id".to_string(), "abc".to_string()],
+            ],
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn dedupe_keeps_the_latest_response_per_method_and_uri() {
+        let recordings = [
+            recording("GET", "/pets", 200, "old"),
+            recording("GET", "/pets?page=2", 200, "page 2"),
+            recording("POST", "/pets", 201, "created"),
+            recording("GET", "/pets", 200, "new"),
+        ];
+        let endpoints = |dedupe| {
+            to_definitions(&recordings, &[], dedupe)
+                .into_iter()
+                .map(|d| (d.method, d.path, d.response.body))
+                .collect::<Vec<_>>()
+        };
+        let owned = |(method, path, body): (&str, &str, &str)| {
+            (method.to_string(), path.to_string(), body.to_string())
+        };
+        assert_eq!(
+            endpoints(true),
+            [
+                ("GET", "/pets", "new"),
+                ("GET", "/pets?page=2", "page 2"),
+                ("POST", "/pets", "created")
+            ]
+            .map(owned)
+        );
+        assert_eq!(endpoints(false).len(), 4);
+    }
+
+    #[test]
+    fn excluded_headers_are_dropped_whatever_their_case() {
+        let exclude = ["date".to_string(), "x-request-ID".to_string()];
+        let definitions = to_definitions(&[recording("GET", "/", 200, "")], &exclude, true);
+        let names: Vec<&str> = definitions[0]
+            .response
+            .headers
+            .iter()
+            .map(|pair| pair[0].as_str())
+            .collect();
+        assert_eq!(names, ["Content-Type"]);
+        assert!(!definitions[0].response.templated);
+    }
+
+    #[test]
+    fn recordings_are_capped() {
+        let proxy = MockProxy::new(MockProxyConfig {
+            upstream: "http://localhost:1".to_string(),
+            record: true,
+        })
+        .unwrap();
+        for i in 0..=MAX_RECORDINGS {
+            proxy.record(recording("GET", &format!("/{}", i), 200, ""));
+        }
+        let large = "x".repeat(MAX_RECORDED_BODY + 1);
+        proxy.record(recording("GET", "/large", 200, &large));
+
+        let recordings = proxy.recordings();
+        assert_eq!(recordings.len(), MAX_RECORDINGS);
+        assert_eq!(recordings[0].uri, "/1");
+        assert_eq!(
+            recordings[MAX_RECORDINGS - 1].uri,
+            format!("/{}", MAX_RECORDINGS)
+        );
+    }
+}
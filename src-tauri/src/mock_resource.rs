@@ -0,0 +1,367 @@
+use crate::jsonpath::to_text;
+use crate::template::RequestContext;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+/// A REST collection served from memory: `GET/POST {base_path}` and
+/// `GET/PUT/PATCH/DELETE {base_path}/{id}`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MockResourceDefinition {
+    /// Defaults to the base path
+    #[serde(default)]
+    pub name: Option<String>,
+    pub base_path: String,
+    #[serde(default = "default_id_field")]
+    pub id_field: String,
+    /// Initial items
+    #[serde(default)]
+    pub seed: Vec<Value>,
+    /// JSON file holding an array of initial items, read when the server starts
+    #[serde(default)]
+    pub seed_file: Option<String>,
+}
+
+fn default_id_field() -> String {
+    "id".to_string()
+}
+
+pub struct MockResource {
+    pub name: String,
+    base_path: String,
+    id_field: String,
+    seed: Vec<Value>,
+    items: Mutex<Vec<Value>>,
+}
+
+impl MockResource {
+    pub async fn new(definition: MockResourceDefinition) -> Result<Self, String> {
+        let mut seed = definition.seed;
+        if let Some(path) = &definition.seed_file {
+            let data = tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| format!("Failed to read seed file {}: {}", path, e))?;
+            match serde_json::from_str(&data) {
+                Ok(Value::Array(items)) => seed.extend(items),
+                Ok(_) => return Err(format!("Seed file {} must contain a JSON array", path)),
+                Err(e) => return Err(format!("Invalid JSON in seed file {}: {}", path, e)),
+            }
+        }
+
+        if definition.base_path.trim().is_empty() {
+            return Err("Resource base path cannot be empty".to_string());
+        }
+        // Kept without a trailing slash, so "/" serves the collection at the root
+        let trimmed = definition.base_path.trim().trim_matches('/');
+        let base_path = if trimmed.is_empty() {
+            String::new()
+        } else {
+            format!("/{}", trimmed)
+        };
+        Ok(MockResource {
+            name: definition.name.unwrap_or_else(|| format!("/{}", trimmed)),
+            base_path,
+            id_field: definition.id_field,
+            items: Mutex::new(seed.clone()),
+            seed,
+        })
+    }
+
+    pub async fn items(&self) -> Vec<Value> {
+        self.items.lock().await.clone()
+    }
+
+    pub async fn reset(&self) {
+        *self.items.lock().await = self.seed.clone();
+    }
+
+    /// `Some(None)` for the collection path, `Some(Some(id))` for an item path.
+    fn route<'p>(&self, path: &'p str) -> Option<Option<&'p str>> {
+        let rest = path.strip_prefix(self.base_path.as_str())?;
+        match rest.trim_end_matches('/') {
+            "" => Some(None),
+            id => id
+                .strip_prefix('/')
+                .filter(|id| !id.contains('/'))
+                .map(Some),
+        }
+    }
+
+    fn id_of(&self, item: &Value) -> Option<String> {
+        item.get(&self.id_field).map(to_text)
+    }
+
+    /// Numeric ids continue from the highest one; otherwise a UUID is generated.
+    fn next_id(&self, items: &[Value]) -> Value {
+        let ids: Vec<&Value> = items.iter().filter_map(|i| i.get(&self.id_field)).collect();
+        if ids.iter().all(|id| id.is_u64()) {
+            let max = ids.iter().filter_map(|id| id.as_u64()).max().unwrap_or(0);
+            json!(max + 1)
+        } else {
+            json!(crate::template::uuid_v4())
+        }
+    }
+
+    /// Answer the request if it targets this resource.
+    pub async fn handle(&self, ctx: &RequestContext<'_>) -> Option<Response> {
+        let id = self.route(ctx.path)?;
+        let mut items = self.items.lock().await;
+        let position = id.and_then(|id| {
+            items
+                .iter()
+                .position(|i| self.id_of(i).as_deref() == Some(id))
+        });
+        let body = || match serde_json::from_str::<Value>(ctx.body) {
+            Ok(value @ Value::Object(_)) => Some(value),
+            _ => None,
+        };
+
+        let response = match (ctx.method, id, position) {
+            ("GET", None, _) => {
+                // Query params filter on top-level fields, e.g. ?status=sold. Params no item
+                // has as a field, like ?page=2 or a cache buster, are ignored
+                let filters: Vec<&(String, String)> = ctx
+                    .query
+                    .iter()
+                    .filter(|(key, _)| items.iter().any(|item| item.get(key).is_some()))
+                    .collect();
+                let list: Vec<&Value> = items
+                    .iter()
+                    .filter(|item| {
+                        filters.iter().all(|(key, value)| {
+                            item.get(key).is_some_and(|field| to_text(field) == *value)
+                        })
+                    })
+                    .collect();
+                json_response(StatusCode::OK, &json!(list))
+            }
+            ("POST", None, _) => match body() {
+                Some(mut item) => {
+                    let id = match item.get(&self.id_field) {
+                        Some(id) if !id.is_null() => id.clone(),
+                        _ => self.next_id(&items),
+                    };
+                    if items.iter().any(|i| i.get(&self.id_field) == Some(&id)) {
+                        return Some(error(
+                            StatusCode::CONFLICT,
+                            "An item with this id already exists",
+                        ));
+                    }
+                    item[&self.id_field] = id.clone();
+                    items.push(item.clone());
+                    let mut response = json_response(StatusCode::CREATED, &item);
+                    let location = format!("{}/{}", self.base_path, to_text(&id));
+                    if let Ok(value) = HeaderValue::from_str(&location) {
+                        response.headers_mut().insert(header::LOCATION, value);
+                    }
+                    response
+                }
+                None => bad_body(),
+            },
+            (_, Some(_), None) => error(StatusCode::NOT_FOUND, "Not found"),
+            ("GET", Some(_), Some(index)) => json_response(StatusCode::OK, &items[index]),
+            ("PUT", Some(_), Some(index)) => match body() {
+                Some(mut item) => {
+                    item[&self.id_field] = items[index][&self.id_field].clone();
+                    items[index] = item;
+                    json_response(StatusCode::OK, &items[index])
+                }
+                None => bad_body(),
+            },
+            ("PATCH", Some(_), Some(index)) => match body() {
+                Some(patch) => {
+                    let id = items[index][&self.id_field].clone();
+                    merge_patch(&mut items[index], &patch);
+                    items[index][&self.id_field] = id;
+                    json_response(StatusCode::OK, &items[index])
+                }
+                None => bad_body(),
+            },
+            ("DELETE", Some(_), Some(index)) => {
+                items.remove(index);
+                StatusCode::NO_CONTENT.into_response()
+            }
+            _ => error(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed"),
+        };
+        Some(response)
+    }
+}
+
+/// RFC 7386 JSON merge patch: objects merge recursively, `null` removes a field.
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = json!({});
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        value.to_string(),
+    )
+        .into_response()
+}
+
+fn bad_body() -> Response {
+    error(StatusCode::BAD_REQUEST, "Body must be a JSON object")
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    json_response(status, &json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, Uri};
+
+    async fn resource(base_path: &str, seed: Value) -> MockResource {
+        let definition = serde_json::from_value(json!({ "base_path": base_path, "seed": seed }));
+        MockResource::new(definition.unwrap()).await.unwrap()
+    }
+
+    /// Status, `Location` header and JSON body of the resource's answer, `None` when
+    /// the request isn't for it.
+    async fn call(
+        resource: &MockResource,
+        method: &str,
+        uri: &str,
+        body: &str,
+    ) -> Option<(u16, Option<String>, Value)> {
+        let uri: Uri = uri.parse().unwrap();
+        let headers = HeaderMap::new();
+        let ctx = RequestContext::new(method, &uri, &headers, body);
+        let response = resource.handle(&ctx).await?;
+        let status = response.status().as_u16();
+        let location = response
+            .headers()
+            .get(header::LOCATION)
+            .map(|v| v.to_str().unwrap().to_string());
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        Some((status, location, body))
+    }
+
+    #[tokio::test]
+    async fn base_paths_route_collections_and_items() {
+        let pets = resource("/pets/", json!([{ "id": 1 }])).await;
+        assert_eq!(pets.name, "/pets");
+        assert_eq!(call(&pets, "GET", "/pets", "").await.unwrap().0, 200);
+        assert_eq!(call(&pets, "GET", "/pets/1/", "").await.unwrap().0, 200);
+        assert!(call(&pets, "GET", "/petshop", "").await.is_none());
+        assert!(call(&pets, "GET", "/pets/1/toys", "").await.is_none());
+
+        let root = resource("/", json!([{ "id": 1 }])).await;
+        assert_eq!(root.name, "/");
+        assert_eq!(
+            call(&root, "GET", "/", "").await.unwrap().2,
+            json!([{ "id": 1 }])
+        );
+        assert_eq!(
+            call(&root, "GET", "/1", "").await.unwrap().2,
+            json!({ "id": 1 })
+        );
+        let (status, location, _) = call(&root, "POST", "/", "{}").await.unwrap();
+        assert_eq!((status, location.as_deref()), (201, Some("/2")));
+
+        let definition = serde_json::from_value(json!({ "base_path": " " })).unwrap();
+        let error = MockResource::new(definition).await.err().unwrap();
+        assert_eq!(error, "Resource base path cannot be empty");
+    }
+
+    #[tokio::test]
+    async fn posts_generate_ids_and_reject_duplicates() {
+        let pets = resource("/pets", json!([{ "id": 1 }, { "id": 7 }])).await;
+        let (status, location, body) = call(&pets, "POST", "/pets", r#"{"name":"Rex"}"#)
+            .await
+            .unwrap();
+        assert_eq!(status, 201);
+        assert_eq!(location.as_deref(), Some("/pets/8"));
+        assert_eq!(body, json!({ "id": 8, "name": "Rex" }));
+
+        let (status, _, body) = call(&pets, "POST", "/pets", r#"{"id":7}"#).await.unwrap();
+        assert_eq!(status, 409);
+        assert_eq!(body["error"], "An item with this id already exists");
+        assert_eq!(call(&pets, "POST", "/pets", "[]").await.unwrap().0, 400);
+
+        // Non-numeric ids fall back to UUIDs
+        let tags = resource("/tags", json!([{ "id": "red" }])).await;
+        let (_, _, body) = call(&tags, "POST", "/tags", "{}").await.unwrap();
+        assert_eq!(body["id"].as_str().unwrap().len(), 36);
+    }
+
+    #[tokio::test]
+    async fn put_and_patch_keep_the_id() {
+        let pets = resource("/pets", json!([{ "id": 1, "name": "Rex", "tag": "dog" }])).await;
+        let (status, _, body) = call(&pets, "PUT", "/pets/1", r#"{"id":5,"name":"Max"}"#)
+            .await
+            .unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body, json!({ "id": 1, "name": "Max" }));
+
+        let patch = r#"{"id":5,"name":null,"owner":{"name":"Ann"}}"#;
+        let (_, _, body) = call(&pets, "PATCH", "/pets/1", patch).await.unwrap();
+        assert_eq!(body, json!({ "id": 1, "owner": { "name": "Ann" } }));
+        assert_eq!(pets.items().await, vec![body]);
+        assert_eq!(call(&pets, "PUT", "/pets/2", "{}").await.unwrap().0, 404);
+    }
+
+    #[tokio::test]
+    async fn lists_filter_on_item_fields() {
+        let seed = json!([
+            { "id": 1, "status": "sold", "age": 3 },
+            { "id": 2, "status": "new", "age": 3 },
+            { "id": 3, "status": "sold", "age": 5 }
+        ]);
+        let pets = resource("/pets", seed).await;
+        let ids = |body: Value| -> Vec<Value> {
+            body.as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["id"].clone())
+                .collect()
+        };
+        let list = |uri| call(&pets, "GET", uri, "");
+        assert_eq!(ids(list("/pets?status=sold").await.unwrap().2), [1, 3]);
+        assert_eq!(ids(list("/pets?age=3&status=sold").await.unwrap().2), [1]);
+        // Params no item has, like paging, don't filter
+        assert_eq!(ids(list("/pets?page=2&status=new").await.unwrap().2), [2]);
+    }
+
+    #[tokio::test]
+    async fn deletes_until_reset_to_the_seed() {
+        let pets = resource("/pets", json!([{ "id": 1 }, { "id": 2 }])).await;
+        assert_eq!(call(&pets, "DELETE", "/pets/1", "").await.unwrap().0, 204);
+        assert_eq!(call(&pets, "DELETE", "/pets/1", "").await.unwrap().0, 404);
+        assert_eq!(call(&pets, "DELETE", "/pets", "").await.unwrap().0, 405);
+        call(&pets, "POST", "/pets", "{}").await.unwrap();
+        assert_eq!(
+            pets.items().await,
+            vec![json!({ "id": 2 }), json!({ "id": 3 })]
+        );
+
+        pets.reset().await;
+        assert_eq!(
+            pets.items().await,
+            vec![json!({ "id": 1 }), json!({ "id": 2 })]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use chrono::Datelike;
+use rcgen::{
+    date_time_ymd, BasicConstraints, Certificate, CertificateParams, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose,
+};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{crypto::ring, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+const CA_CERT_FILE: &str = "mock-ca.pem";
+const CA_KEY_FILE: &str = "mock-ca-key.pem";
+const CA_NAME: &str = "cURL-UI Local Mock CA";
+
+/// Serve a mock over HTTPS, with the user's certificate or one issued by the local CA.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MockTlsConfig {
+    /// PEM certificate chain, used with `key_file` instead of a generated certificate
+    #[serde(default)]
+    pub cert_file: Option<String>,
+    /// PEM private key of `cert_file`
+    #[serde(default)]
+    pub key_file: Option<String>,
+    /// Names and IPs the generated certificate covers besides localhost, 127.0.0.1 and ::1
+    #[serde(default)]
+    pub hosts: Vec<String>,
+}
+
+fn rcgen_error(e: rcgen::Error) -> String {
+    format!("Failed to generate mock certificate: {}", e)
+}
+
+/// The CA's parameters. They are rebuilt on every load, as signing with a CA only needs its
+/// name and key to match the persisted certificate.
+fn ca_params() -> Result<CertificateParams, String> {
+    let mut params = CertificateParams::new(Vec::<String>::new()).map_err(rcgen_error)?;
+    params.distinguished_name.push(DnType::CommonName, CA_NAME);
+    params
+        .distinguished_name
+        .push(DnType::OrganizationName, "cURL-UI");
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![
+        KeyUsagePurpose::KeyCertSign,
+        KeyUsagePurpose::CrlSign,
+        KeyUsagePurpose::DigitalSignature,
+    ];
+    Ok(params)
+}
+
+/// Validity from yesterday for `years` years.
+fn validity(params: &mut CertificateParams, years: i32) {
+    let yesterday = chrono::Utc::now() - chrono::Duration::days(1);
+    let (year, month, day) = (
+        yesterday.year(),
+        yesterday.month() as u8,
+        yesterday.day() as u8,
+    );
+    params.not_before = date_time_ymd(year, month, day);
+    // Day 28 at the latest, so that the end date exists in every month
+    params.not_after = date_time_ymd(year + years, month, day.min(28));
+}
+
+/// Write the CA key, readable by the current user only.
+fn write_key(path: &Path, pem: &str) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(pem.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// The local CA in `dir`, created on first use. Returns it with its key and PEM certificate.
+/// The returned certificate is only fit for signing: send the PEM one to clients.
+pub fn load_or_create_ca(dir: &Path) -> Result<(Certificate, KeyPair, String), String> {
+    let cert_path = dir.join(CA_CERT_FILE);
+    let key_path = dir.join(CA_KEY_FILE);
+
+    if cert_path.exists() && key_path.exists() {
+        let key_pem = std::fs::read_to_string(&key_path)
+            .map_err(|e| format!("Failed to read {}: {}", key_path.display(), e))?;
+        let cert_pem = std::fs::read_to_string(&cert_path)
+            .map_err(|e| format!("Failed to read {}: {}", cert_path.display(), e))?;
+        let key = KeyPair::from_pem(&key_pem)
+            .map_err(|e| format!("Invalid mock CA key {}: {}", key_path.display(), e))?;
+        let cert = ca_params()?.self_signed(&key).map_err(rcgen_error)?;
+        return Ok((cert, key, cert_pem));
+    }
+
+    let key = KeyPair::generate().map_err(rcgen_error)?;
+    let mut params = ca_params()?;
+    validity(&mut params, 10);
+    let cert = params.self_signed(&key).map_err(rcgen_error)?;
+    let cert_pem = cert.pem();
+
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    write_key(&key_path, &key.serialize_pem())?;
+    std::fs::write(&cert_path, &cert_pem)
+        .map_err(|e| format!("Failed to write {}: {}", cert_path.display(), e))?;
+    Ok((cert, key, cert_pem))
+}
+
+/// A leaf certificate for the mock hosts, signed by the local CA, followed by the CA.
+fn generate_leaf(
+    ca_dir: &Path,
+    hosts: &[String],
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), String> {
+    let (ca, ca_key, ca_pem) = load_or_create_ca(ca_dir)?;
+    // The persisted CA, as clients trust it. `ca` is rebuilt on load, so its DER differs
+    let ca_der = rustls_pemfile::certs(&mut ca_pem.as_bytes())
+        .next()
+        .ok_or_else(|| "No certificate found in the mock CA file".to_string())?
+        .map_err(|e| format!("Invalid mock CA certificate: {}", e))?;
+
+    let mut names = vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ];
+    names.extend(hosts.iter().filter(|h| !h.is_empty()).cloned());
+    let mut params = CertificateParams::new(names).map_err(rcgen_error)?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, "localhost");
+    params.is_ca = IsCa::NoCa;
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+    // Apple platforms reject server certificates valid for more than 825 days
+    validity(&mut params, 1);
+
+    let key = KeyPair::generate().map_err(rcgen_error)?;
+    let leaf = params.signed_by(&key, &ca, &ca_key).map_err(rcgen_error)?;
+    let chain = vec![leaf.der().clone(), ca_der];
+    Ok((chain, PrivateKeyDer::Pkcs8(key.serialize_der().into())))
+}
+
+fn read_pem_files(
+    cert_file: &str,
+    key_file: &str,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), String> {
+    let cert_data = std::fs::read(cert_file)
+        .map_err(|e| format!("Failed to read certificate {}: {}", cert_file, e))?;
+    let chain = rustls_pemfile::certs(&mut cert_data.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid certificate {}: {}", cert_file, e))?;
+    if chain.is_empty() {
+        return Err(format!("No certificate found in {}", cert_file));
+    }
+    let key_data =
+        std::fs::read(key_file).map_err(|e| format!("Failed to read key {}: {}", key_file, e))?;
+    let key = rustls_pemfile::private_key(&mut key_data.as_slice())
+        .map_err(|e| format!("Invalid key {}: {}", key_file, e))?
+        .ok_or_else(|| format!("No private key found in {}", key_file))?;
+    Ok((chain, key))
+}
+
+/// TLS acceptor offering HTTP/2 and HTTP/1.1 through ALPN. `ca_dir` holds the local CA.
+pub fn acceptor(config: &MockTlsConfig, ca_dir: &Path) -> Result<TlsAcceptor, String> {
+    let (chain, key) = match (&config.cert_file, &config.key_file) {
+        (Some(cert_file), Some(key_file)) => read_pem_files(cert_file, key_file)?,
+        (None, None) => generate_leaf(ca_dir, &config.hosts)?,
+        _ => return Err("TLS needs both a certificate and a key file".to_string()),
+    };
+
+    let mut server_config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_no_client_auth()
+        .with_single_cert(chain, key)
+        .map_err(|e| format!("Invalid mock certificate: {}", e))?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
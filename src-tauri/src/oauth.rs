@@ -0,0 +1,652 @@
+use axum::{extract::Query, response::Html, routing::get, Router};
+use base64::Engine;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, Mutex};
+
+/// Tokens are refreshed this many seconds before they actually expire.
+const REFRESH_SKEW_SECS: u64 = 30;
+/// How long interactive grants (browser login, device code) wait for the user.
+const INTERACTIVE_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OAuth2Config {
+    /// "client_credentials", "password", "authorization_code", "device_code" or "refresh_token"
+    pub grant_type: String,
+    pub token_url: String,
+    #[serde(default)]
+    pub auth_url: Option<String>,
+    #[serde(default)]
+    pub device_auth_url: Option<String>,
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub scope: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// Loopback port for the authorization code redirect, 0 or None picks a free one.
+    #[serde(default)]
+    pub redirect_port: Option<u16>,
+    /// "body" (default) sends client credentials as form fields, "basic" as an Authorization header.
+    #[serde(default)]
+    pub client_auth: Option<String>,
+    #[serde(default)]
+    pub extra_params: Vec<Vec<String>>,
+}
+
+impl OAuth2Config {
+    /// Key identifying tokens obtained with this configuration inside a project. The client
+    /// secret only appears as a hash.
+    pub fn cache_key(&self, project_name: &str) -> String {
+        let secret = self
+            .client_secret
+            .as_deref()
+            .map(|secret| hex::encode(Sha256::digest(secret.as_bytes())))
+            .unwrap_or_default();
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}",
+            project_name,
+            self.grant_type,
+            self.token_url,
+            self.client_id,
+            secret,
+            self.scope.as_deref().unwrap_or(""),
+            self.username.as_deref().unwrap_or("")
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    pub token_type: String,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+    /// Unix timestamp (seconds), None when the server did not send `expires_in`.
+    pub expires_at: Option<u64>,
+}
+
+impl OAuth2Token {
+    fn is_fresh(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now_secs() + REFRESH_SKEW_SECS < expires_at,
+            None => true,
+        }
+    }
+
+    /// Value for the Authorization header.
+    pub fn header_value(&self) -> String {
+        // Servers often answer "bearer"; normalise the common case.
+        let token_type = if self.token_type.eq_ignore_ascii_case("bearer") {
+            "Bearer"
+        } else {
+            &self.token_type
+        };
+        format!("{} {}", token_type, self.access_token)
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct DeviceCodePayload {
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+}
+
+enum TokenError {
+    Pending,
+    SlowDown,
+    Failed(String),
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// OAuth2 tokens by `OAuth2Config::cache_key`.
+#[derive(Default)]
+pub struct TokenCache {
+    tokens: Mutex<HashMap<String, OAuth2Token>>,
+    /// One lock per key, held while a token is obtained so that concurrent requests wait for
+    /// that token instead of running the grant again; removed once no request waits on it
+    fetches: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl TokenCache {
+    pub async fn get(&self, key: &str) -> Option<OAuth2Token> {
+        self.tokens.lock().await.get(key).cloned()
+    }
+
+    pub async fn insert(&self, key: String, token: OAuth2Token) {
+        self.tokens.lock().await.insert(key, token);
+    }
+
+    /// Forget the tokens of `project_name`, or all of them.
+    pub async fn clear(&self, project_name: Option<&str>) {
+        let mut tokens = self.tokens.lock().await;
+        match project_name {
+            Some(p_name) => tokens.retain(|key, _| !key.starts_with(&format!("{}|", p_name))),
+            None => tokens.clear(),
+        }
+    }
+
+    /// The fresh token under `key`, or the one `obtain` returns given the stale token, if any.
+    /// Callers asking for the same key meanwhile wait for that token.
+    async fn get_or_obtain<F, Fut>(&self, key: String, obtain: F) -> Result<OAuth2Token, String>
+    where
+        F: FnOnce(Option<OAuth2Token>) -> Fut,
+        Fut: std::future::Future<Output = Result<OAuth2Token, String>>,
+    {
+        if let Some(token) = self.get(&key).await.filter(OAuth2Token::is_fresh) {
+            return Ok(token);
+        }
+
+        let fetch_lock = {
+            let mut fetches = self.fetches.lock().await;
+            Arc::clone(fetches.entry(key.clone()).or_default())
+        };
+        let result = async {
+            let _obtaining = fetch_lock.lock().await;
+            // Another caller may have obtained the token while this one waited
+            let cached = self.get(&key).await;
+            if let Some(token) = cached.as_ref().filter(|token| token.is_fresh()) {
+                return Ok(token.clone());
+            }
+
+            let token = obtain(cached).await?;
+            self.insert(key.clone(), token.clone()).await;
+            Ok(token)
+        }
+        .await;
+
+        // The last caller of the key drops its lock, held by the map and this one only
+        let mut fetches = self.fetches.lock().await;
+        if Arc::strong_count(&fetch_lock) == 2 {
+            fetches.remove(&key);
+        }
+        result
+    }
+}
+
+/// Returns a valid token for `config`, using the project cache and refreshing it when it is
+/// about to expire. Only hits the token endpoint (or the user) when really needed, once for
+/// all the requests waiting on the same configuration.
+pub async fn access_token<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    client: &reqwest::Client,
+    cache: &TokenCache,
+    project_name: &str,
+    config: &OAuth2Config,
+) -> Result<OAuth2Token, String> {
+    let key = config.cache_key(project_name);
+    cache
+        .get_or_obtain(key, |cached| async move {
+            if let Some(refresh_token) = cached.and_then(|t| t.refresh_token) {
+                match refresh(client, config, &refresh_token).await {
+                    Ok(token) => return Ok(token),
+                    Err(e) => crate::rust_warn!(
+                        app_handle,
+                        "OAuth2 token refresh failed, requesting a new token: {}",
+                        e
+                    ),
+                }
+            }
+            fetch_token(app_handle, client, config).await
+        })
+        .await
+}
+
+/// Runs the configured grant from scratch, ignoring any cached token.
+pub async fn fetch_token<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    client: &reqwest::Client,
+    config: &OAuth2Config,
+) -> Result<OAuth2Token, String> {
+    let result = match config.grant_type.as_str() {
+        "client_credentials" => {
+            let mut params = vec![("grant_type", "client_credentials".to_string())];
+            if let Some(scope) = &config.scope {
+                params.push(("scope", scope.clone()));
+            }
+            token_request(client, config, params).await
+        }
+        "password" => {
+            let mut params = vec![
+                ("grant_type", "password".to_string()),
+                ("username", config.username.clone().unwrap_or_default()),
+                ("password", config.password.clone().unwrap_or_default()),
+            ];
+            if let Some(scope) = &config.scope {
+                params.push(("scope", scope.clone()));
+            }
+            token_request(client, config, params).await
+        }
+        "refresh_token" => {
+            let refresh_token = config
+                .refresh_token
+                .as_deref()
+                .ok_or("OAuth2 refresh_token grant requires a refresh token")?;
+            return refresh(client, config, refresh_token).await;
+        }
+        "authorization_code" => return authorization_code(app_handle, client, config).await,
+        "device_code" => return device_code(app_handle, client, config).await,
+        other => return Err(format!("Unsupported OAuth2 grant type: {}", other)),
+    };
+
+    result.map_err(|e| match e {
+        TokenError::Failed(msg) => msg,
+        TokenError::Pending | TokenError::SlowDown => "OAuth2 authorization pending".to_string(),
+    })
+}
+
+async fn refresh(
+    client: &reqwest::Client,
+    config: &OAuth2Config,
+    refresh_token: &str,
+) -> Result<OAuth2Token, String> {
+    let mut params = vec![
+        ("grant_type", "refresh_token".to_string()),
+        ("refresh_token", refresh_token.to_string()),
+    ];
+    if let Some(scope) = &config.scope {
+        params.push(("scope", scope.clone()));
+    }
+    match token_request(client, config, params).await {
+        // Servers may omit the refresh token when it is not rotated, so keep the old one.
+        Ok(mut token) => {
+            if token.refresh_token.is_none() {
+                token.refresh_token = Some(refresh_token.to_string());
+            }
+            Ok(token)
+        }
+        Err(TokenError::Failed(msg)) => Err(msg),
+        Err(_) => Err("OAuth2 refresh returned an unexpected pending state".to_string()),
+    }
+}
+
+/// POSTs a form to the token endpoint and parses the RFC 6749 token (or error) response.
+async fn token_request(
+    client: &reqwest::Client,
+    config: &OAuth2Config,
+    mut params: Vec<(&str, String)>,
+) -> Result<OAuth2Token, TokenError> {
+    let use_basic = config.client_auth.as_deref() == Some("basic");
+    if !use_basic {
+        params.push(("client_id", config.client_id.clone()));
+        if let Some(secret) = &config.client_secret {
+            params.push(("client_secret", secret.clone()));
+        }
+    }
+    for pair in &config.extra_params {
+        if pair.len() == 2 && !pair[0].is_empty() {
+            params.push((pair[0].as_str(), pair[1].clone()));
+        }
+    }
+
+    let mut request = client
+        .post(&config.token_url)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .form(&params);
+    if use_basic {
+        request = request.basic_auth(&config.client_id, config.client_secret.as_deref());
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| TokenError::Failed(format!("OAuth2 token request failed: {}", e)))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| TokenError::Failed(format!("Failed to read OAuth2 token response: {}", e)))?;
+    let json: serde_json::Value = serde_json::from_str(&body).map_err(|_| {
+        TokenError::Failed(format!(
+            "OAuth2 token endpoint returned non-JSON response ({}): {}",
+            status, body
+        ))
+    })?;
+
+    if let Some(error) = json["error"].as_str() {
+        return Err(match error {
+            "authorization_pending" => TokenError::Pending,
+            "slow_down" => TokenError::SlowDown,
+            _ => TokenError::Failed(format!(
+                "OAuth2 error: {}{}",
+                error,
+                json["error_description"]
+                    .as_str()
+                    .map(|d| format!(" ({})", d))
+                    .unwrap_or_default()
+            )),
+        });
+    }
+
+    let access_token = json["access_token"].as_str().ok_or_else(|| {
+        TokenError::Failed(format!(
+            "OAuth2 token response ({}) has no access_token",
+            status
+        ))
+    })?;
+    // Some providers send expires_in as a string.
+    let expires_in = json["expires_in"]
+        .as_u64()
+        .or_else(|| json["expires_in"].as_str().and_then(|s| s.parse().ok()));
+
+    Ok(OAuth2Token {
+        access_token: access_token.to_string(),
+        token_type: json["token_type"].as_str().unwrap_or("Bearer").to_string(),
+        refresh_token: json["refresh_token"].as_str().map(String::from),
+        scope: json["scope"].as_str().map(String::from),
+        expires_at: expires_in.map(|secs| now_secs() + secs),
+    })
+}
+
+/// Authorization code grant with PKCE (S256). The browser is redirected back to a
+/// short-lived loopback axum server that hands the code over through a oneshot channel.
+async fn authorization_code<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    client: &reqwest::Client,
+    config: &OAuth2Config,
+) -> Result<OAuth2Token, String> {
+    let auth_url = config
+        .auth_url
+        .as_deref()
+        .ok_or("OAuth2 authorization_code grant requires an authorization URL")?;
+
+    let code_verifier = random_string(64);
+    let code_challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode(Sha256::digest(code_verifier.as_bytes()));
+    let state = random_string(24);
+
+    let listener = TcpListener::bind(("127.0.0.1", config.redirect_port.unwrap_or(0)))
+        .await
+        .map_err(|e| format!("Failed to bind OAuth2 redirect listener: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+
+    let (code_tx, code_rx) = oneshot::channel::<HashMap<String, String>>();
+    let code_tx = Arc::new(Mutex::new(Some(code_tx)));
+    let app = Router::new().route(
+        "/callback",
+        get(move |Query(params): Query<HashMap<String, String>>| {
+            let code_tx = Arc::clone(&code_tx);
+            async move {
+                if let Some(tx) = code_tx.lock().await.take() {
+                    let _ = tx.send(params);
+                }
+                Html("<h3>cURL-UI: authorization complete, you can close this window.</h3>")
+            }
+        }),
+    );
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let listener_handle = app_handle.clone();
+    tokio::spawn(async move {
+        let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(e) = server.await {
+            crate::rust_error!(&listener_handle, "OAuth2 redirect listener error: {}", e);
+        }
+    });
+
+    let mut query = vec![
+        ("response_type", "code".to_string()),
+        ("client_id", config.client_id.clone()),
+        ("redirect_uri", redirect_uri.clone()),
+        ("state", state.clone()),
+        ("code_challenge", code_challenge),
+        ("code_challenge_method", "S256".to_string()),
+    ];
+    if let Some(scope) = &config.scope {
+        query.push(("scope", scope.clone()));
+    }
+    let url = reqwest::Url::parse_with_params(auth_url, &query)
+        .map_err(|e| format!("Invalid OAuth2 authorization URL: {}", e))?;
+
+    crate::rust_info!(
+        app_handle,
+        "Opening browser for OAuth2 authorization ({})",
+        redirect_uri
+    );
+    if let Err(e) = tauri_plugin_opener::open_url(url.as_str(), None::<&str>) {
+        crate::rust_warn!(
+            app_handle,
+            "Failed to open browser, visit manually: {} ({})",
+            url,
+            e
+        );
+    }
+
+    let callback = tokio::time::timeout(INTERACTIVE_TIMEOUT, code_rx).await;
+    let _ = shutdown_tx.send(());
+    let params = callback
+        .map_err(|_| "Timed out waiting for OAuth2 authorization".to_string())?
+        .map_err(|_| "OAuth2 redirect listener closed unexpectedly".to_string())?;
+
+    if let Some(error) = params.get("error") {
+        return Err(format!("OAuth2 authorization denied: {}", error));
+    }
+    if params.get("state") != Some(&state) {
+        return Err("OAuth2 state mismatch in authorization response".to_string());
+    }
+    let code = params
+        .get("code")
+        .ok_or("OAuth2 authorization response has no code")?;
+
+    let params = vec![
+        ("grant_type", "authorization_code".to_string()),
+        ("code", code.clone()),
+        ("redirect_uri", redirect_uri),
+        ("code_verifier", code_verifier),
+    ];
+    token_request(client, config, params)
+        .await
+        .map_err(|e| match e {
+            TokenError::Failed(msg) => msg,
+            _ => "OAuth2 code exchange returned an unexpected pending state".to_string(),
+        })
+}
+
+/// Device authorization grant (RFC 8628). The user code is emitted to the frontend as an
+/// `oauth2-device-code` event while the token endpoint is polled.
+async fn device_code<R: tauri::Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    client: &reqwest::Client,
+    config: &OAuth2Config,
+) -> Result<OAuth2Token, String> {
+    let device_auth_url = config
+        .device_auth_url
+        .as_deref()
+        .ok_or("OAuth2 device_code grant requires a device authorization URL")?;
+
+    let mut params = vec![("client_id", config.client_id.clone())];
+    if let Some(scope) = &config.scope {
+        params.push(("scope", scope.clone()));
+    }
+    let json: serde_json::Value = client
+        .post(device_auth_url)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| format!("OAuth2 device authorization request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to parse OAuth2 device authorization response: {}",
+                e
+            )
+        })?;
+
+    let device_code = json["device_code"]
+        .as_str()
+        .ok_or("OAuth2 device authorization response has no device_code")?
+        .to_string();
+    let payload = DeviceCodePayload {
+        user_code: json["user_code"].as_str().unwrap_or_default().to_string(),
+        verification_uri: json["verification_uri"]
+            .as_str()
+            .or_else(|| json["verification_url"].as_str())
+            .unwrap_or_default()
+            .to_string(),
+        verification_uri_complete: json["verification_uri_complete"].as_str().map(String::from),
+    };
+    let mut interval = json["interval"].as_u64().unwrap_or(5);
+    let expires_in = json["expires_in"]
+        .as_u64()
+        .map(Duration::from_secs)
+        .unwrap_or(INTERACTIVE_TIMEOUT);
+
+    crate::rust_info!(
+        app_handle,
+        "OAuth2 device code: enter {} at {}",
+        payload.user_code,
+        payload.verification_uri
+    );
+    let _ = app_handle.emit("oauth2-device-code", payload.clone());
+    let open = payload
+        .verification_uri_complete
+        .as_deref()
+        .unwrap_or(&payload.verification_uri);
+    if !open.is_empty() {
+        let _ = tauri_plugin_opener::open_url(open, None::<&str>);
+    }
+
+    let deadline = tokio::time::Instant::now() + expires_in;
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if tokio::time::Instant::now() >= deadline {
+            return Err("OAuth2 device code expired before authorization".to_string());
+        }
+        let params = vec![
+            (
+                "grant_type",
+                "urn:ietf:params:oauth:grant-type:device_code".to_string(),
+            ),
+            ("device_code", device_code.clone()),
+        ];
+        match token_request(client, config, params).await {
+            Ok(token) => return Ok(token),
+            Err(TokenError::Pending) => {}
+            Err(TokenError::SlowDown) => interval += 5,
+            Err(TokenError::Failed(msg)) => return Err(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn config(grant_type: &str, client_secret: Option<&str>) -> OAuth2Config {
+        serde_json::from_value(serde_json::json!({
+            "grant_type": grant_type,
+            "token_url": "https://auth.example.com/token",
+            "client_id": "app",
+            "client_secret": client_secret,
+        }))
+        .unwrap()
+    }
+
+    fn token(access_token: &str, expires_at: Option<u64>) -> OAuth2Token {
+        OAuth2Token {
+            access_token: access_token.to_string(),
+            token_type: "Bearer".to_string(),
+            refresh_token: None,
+            scope: None,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn cache_key_covers_grant_type_and_secret() {
+        let base = config("client_credentials", Some("s1")).cache_key("p");
+        assert_ne!(
+            base,
+            config("client_credentials", Some("s2")).cache_key("p")
+        );
+        assert_ne!(base, config("password", Some("s1")).cache_key("p"));
+        assert!(!base.contains("s1"));
+    }
+
+    #[tokio::test]
+    async fn concurrent_callers_share_one_fetch() {
+        let cache = Arc::new(TokenCache::default());
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let mut callers = tokio::task::JoinSet::new();
+        for _ in 0..8 {
+            let (cache, fetches) = (Arc::clone(&cache), Arc::clone(&fetches));
+            callers.spawn(async move {
+                cache
+                    .get_or_obtain("p|key".to_string(), |_| async move {
+                        fetches.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(token("fetched", None))
+                    })
+                    .await
+            });
+        }
+        while let Some(result) = callers.join_next().await {
+            assert_eq!(result.unwrap().unwrap().access_token, "fetched");
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(cache.fetches.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn expired_tokens_are_passed_to_obtain() {
+        let cache = TokenCache::default();
+        cache
+            .insert("p|key".to_string(), token("old", Some(now_secs())))
+            .await;
+        let renewed = cache
+            .get_or_obtain("p|key".to_string(), |stale| async move {
+                assert_eq!(stale.unwrap().access_token, "old");
+                Ok(token("new", None))
+            })
+            .await
+            .unwrap();
+        assert_eq!(renewed.access_token, "new");
+
+        cache.clear(Some("p")).await;
+        assert!(cache.get("p|key").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_release_their_key() {
+        let cache = TokenCache::default();
+        let failed = cache
+            .get_or_obtain("p|key".to_string(), |_| async move {
+                Err("token endpoint down".to_string())
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(cache.fetches.lock().await.is_empty());
+    }
+}
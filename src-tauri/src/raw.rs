@@ -0,0 +1,190 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::command;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+
+/// Stop reading once this much has been received, in case the server never closes.
+const MAX_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+const DEFAULT_IDLE_TIMEOUT_MS: u64 = 2000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RawRequestArgs {
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+    /// Connect to this Unix domain socket instead of host:port
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// Wrap the TCP connection in TLS, with `host` as server name
+    #[serde(default)]
+    pub tls: bool,
+    /// Bytes to send, exactly as written
+    pub data: String,
+    /// Turn bare "\n" line endings into "\r\n" (editors rarely keep CRs)
+    #[serde(default)]
+    pub normalize_newlines: bool,
+    /// The response is complete once nothing arrives for this long, default 2000ms
+    #[serde(default)]
+    pub idle_timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RawResponse {
+    /// Response bytes as text (invalid UTF-8 replaced), unparsed
+    response: String,
+    response_base64: String,
+    bytes_sent: usize,
+    bytes_received: usize,
+    time_taken: u64,
+    /// "server" (connection closed), "idle_timeout" or "size_limit"
+    ended_by: String,
+    /// Read error after a partial response, e.g. a connection reset
+    error: Option<String>,
+}
+
+struct Exchange {
+    received: Vec<u8>,
+    ended_by: &'static str,
+    error: Option<String>,
+}
+
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    data: &[u8],
+    idle_timeout: Duration,
+) -> Result<Exchange, String> {
+    stream
+        .write_all(data)
+        .await
+        .map_err(|e| format!("Failed to send: {}", e))?;
+    stream
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to send: {}", e))?;
+
+    let mut received = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        match tokio::time::timeout(idle_timeout, stream.read(&mut buf)).await {
+            Err(_) => return Ok(Exchange::done(received, "idle_timeout", None)),
+            Ok(Ok(0)) => return Ok(Exchange::done(received, "server", None)),
+            Ok(Ok(n)) => {
+                received.extend_from_slice(&buf[..n]);
+                if received.len() >= MAX_RESPONSE_BYTES {
+                    return Ok(Exchange::done(received, "size_limit", None));
+                }
+            }
+            Ok(Err(e)) if received.is_empty() => {
+                return Err(format!("Failed to read response: {}", e))
+            }
+            Ok(Err(e)) => return Ok(Exchange::done(received, "server", Some(e.to_string()))),
+        }
+    }
+}
+
+impl Exchange {
+    fn done(received: Vec<u8>, ended_by: &'static str, error: Option<String>) -> Self {
+        Exchange {
+            received,
+            ended_by,
+            error,
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn unix_exchange(path: &str, data: &[u8], idle: Duration) -> Result<Exchange, String> {
+    let stream = tokio::net::UnixStream::connect(path)
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", path, e))?;
+    exchange(stream, data, idle).await
+}
+
+#[cfg(not(unix))]
+async fn unix_exchange(_path: &str, _data: &[u8], _idle: Duration) -> Result<Exchange, String> {
+    Err("Unix sockets are not supported on this platform".to_string())
+}
+
+async fn tcp_exchange(
+    args: &RawRequestArgs,
+    data: &[u8],
+    idle: Duration,
+) -> Result<Exchange, String> {
+    let stream = TcpStream::connect((args.host.as_str(), args.port))
+        .await
+        .map_err(|e| format!("Failed to connect to {}:{}: {}", args.host, args.port, e))?;
+    if !args.tls {
+        return exchange(stream, data, idle).await;
+    }
+    let connector =
+        native_tls::TlsConnector::new().map_err(|e| format!("Failed to initialize TLS: {}", e))?;
+    let stream = tokio_native_tls::TlsConnector::from(connector)
+        .connect(&args.host, stream)
+        .await
+        .map_err(|e| format!("TLS handshake failed: {}", e))?;
+    exchange(stream, data, idle).await
+}
+
+/// Send hand-written bytes over a plain socket and return whatever comes back, without any
+/// HTTP parsing. Meant for debugging malformed requests and responses.
+#[command]
+pub async fn raw_request(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::HttpRequestState>,
+    args: RawRequestArgs,
+) -> Result<RawResponse, String> {
+    let (tx, rx) = oneshot::channel::<()>();
+    let request_id = args.request_id.clone();
+
+    if let Some(id) = &request_id {
+        let mut handles = state.handles.lock().await;
+        handles.insert(id.clone(), tx);
+    }
+
+    let data = if args.normalize_newlines {
+        args.data.replace("\r\n", "\n").replace('\n', "\r\n")
+    } else {
+        args.data.clone()
+    };
+    let idle = Duration::from_millis(args.idle_timeout_ms.unwrap_or(DEFAULT_IDLE_TIMEOUT_MS));
+
+    let request_future = async {
+        let started = Instant::now();
+        let exchange = match &args.unix_socket {
+            Some(path) => unix_exchange(path, data.as_bytes(), idle).await,
+            None => tcp_exchange(&args, data.as_bytes(), idle).await,
+        }
+        .map_err(|msg| {
+            crate::rust_error!(&app_handle, "{}", msg);
+            msg
+        })?;
+
+        Ok(RawResponse {
+            response: String::from_utf8_lossy(&exchange.received).into_owned(),
+            response_base64: base64::engine::general_purpose::STANDARD.encode(&exchange.received),
+            bytes_sent: data.len(),
+            bytes_received: exchange.received.len(),
+            time_taken: started.elapsed().as_millis() as u64,
+            ended_by: exchange.ended_by.to_string(),
+            error: exchange.error,
+        })
+    };
+
+    let result = tokio::select! {
+        res = request_future => res,
+        _ = rx => Err("Canceled".to_string()),
+    };
+
+    if let Some(id) = &request_id {
+        let mut handles = state.handles.lock().await;
+        handles.remove(id);
+    }
+
+    result
+}
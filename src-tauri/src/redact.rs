@@ -0,0 +1,371 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Placeholder written in place of any masked value.
+pub const MASK: &str = "[REDACTED]";
+
+/// User-configurable description of what counts as a secret.
+/// Header and query param names are matched case-insensitively,
+/// `patterns` are regular expressions applied to free text (bodies, log lines); when a pattern
+/// has a capture group, the first group is kept and only the rest of the match is masked.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedactionConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub header_names: Vec<String>,
+    #[serde(default)]
+    pub query_params: Vec<String>,
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            header_names: [
+                "authorization",
+                "proxy-authorization",
+                "cookie",
+                "set-cookie",
+                "x-api-key",
+                "x-auth-token",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            query_params: [
+                "access_token",
+                "id_token",
+                "refresh_token",
+                "token",
+                "api_key",
+                "apikey",
+                "client_secret",
+                "password",
+                "signature",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            patterns: vec![
+                r"(?i)(\b(?:bearer|basic)\s+)[A-Za-z0-9\-._~+/]+=*".to_string(),
+                r#"(?i)("(?:password|client_secret|refresh_token|access_token)"\s*:\s*)"[^"]*""#
+                    .to_string(),
+            ],
+        }
+    }
+}
+
+/// Compiled form of a `RedactionConfig`, ready to be applied to headers, URLs and text.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    config: RedactionConfig,
+    patterns: Vec<Regex>,
+    query_param: Option<Regex>,
+    header_line: Option<Regex>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        // The default patterns are known to be valid.
+        Self::new(RedactionConfig::default()).unwrap()
+    }
+}
+
+impl Redactor {
+    pub fn new(config: RedactionConfig) -> Result<Self, String> {
+        let patterns = config
+            .patterns
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| format!("Invalid redaction pattern '{}': {}", p, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Matches `?name=value` / `&name=value` wherever a URL shows up, capturing the prefix to keep.
+        let query_param = if config.query_params.is_empty() {
+            None
+        } else {
+            let names: Vec<String> = config
+                .query_params
+                .iter()
+                .map(|n| regex::escape(n))
+                .collect();
+            Some(
+                Regex::new(&format!(r#"(?i)([?&](?:{})=)[^&#\s)"']*"#, names.join("|")))
+                    .map_err(|e| format!("Invalid redaction query params: {}", e))?,
+            )
+        };
+
+        // Matches `Name: value` of a sensitive header inside a raw request or a curl `-H '...'`.
+        let header_line = if config.header_names.is_empty() {
+            None
+        } else {
+            let names: Vec<String> = config
+                .header_names
+                .iter()
+                .map(|n| regex::escape(n.trim()))
+                .collect();
+            Some(
+                Regex::new(&format!(
+                    r#"(?im)(^|[\s'"])((?:{})\s*:\s*)[^'"\r\n]*"#,
+                    names.join("|")
+                ))
+                .map_err(|e| format!("Invalid redaction header names: {}", e))?,
+            )
+        };
+
+        Ok(Self {
+            config,
+            patterns,
+            query_param,
+            header_line,
+        })
+    }
+
+    pub fn config(&self) -> &RedactionConfig {
+        &self.config
+    }
+
+    fn is_secret_header(&self, name: &str) -> bool {
+        self.config
+            .header_names
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn mask_query(&self, text: &str) -> String {
+        match &self.query_param {
+            Some(re) => re.replace_all(text, format!("${{1}}{}", MASK)).into_owned(),
+            None => text.to_string(),
+        }
+    }
+
+    /// A copy that also masks the header `name`, e.g. a custom API key header.
+    pub fn with_header_name(&self, name: &str) -> Redactor {
+        if self.is_secret_header(name) {
+            return self.clone();
+        }
+        let mut config = self.config.clone();
+        config.header_names.push(name.to_string());
+        // Names are escaped and the patterns already compiled, so this cannot fail
+        Redactor::new(config).unwrap_or_else(|_| self.clone())
+    }
+
+    /// A copy that also masks the query param `name`, e.g. a custom API key param.
+    pub fn with_query_param(&self, name: &str) -> Redactor {
+        if self
+            .config
+            .query_params
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(name))
+        {
+            return self.clone();
+        }
+        let mut config = self.config.clone();
+        config.query_params.push(name.to_string());
+        // Names are escaped and the patterns already compiled, so this cannot fail
+        Redactor::new(config).unwrap_or_else(|_| self.clone())
+    }
+
+    /// Mask a header value if its name is listed as sensitive, otherwise run the text patterns on it.
+    pub fn header_value(&self, name: &str, value: &str) -> String {
+        if !self.config.enabled {
+            return value.to_string();
+        }
+        if self.is_secret_header(name) {
+            return MASK.to_string();
+        }
+        self.text(value)
+    }
+
+    /// Mask the values of sensitive query params and any userinfo password inside a URL.
+    pub fn url(&self, url: &str) -> String {
+        if !self.config.enabled {
+            return url.to_string();
+        }
+        let url = match reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.password().map(String::from))
+        {
+            Some(password) => url.replacen(&format!(":{}@", password), &format!(":{}@", MASK), 1),
+            None => url.to_string(),
+        };
+        self.mask_query(&url)
+    }
+
+    /// Apply the configured patterns and query param masking to free text such as a body or log line.
+    pub fn text(&self, text: &str) -> String {
+        if !self.config.enabled {
+            return text.to_string();
+        }
+        let mut out = self.mask_query(text);
+        for re in &self.patterns {
+            out = if re.captures_len() > 1 {
+                re.replace_all(&out, format!("${{1}}{}", MASK)).into_owned()
+            } else {
+                re.replace_all(&out, MASK).into_owned()
+            };
+        }
+        out
+    }
+
+    /// Like `text`, also masking sensitive headers written as `Name: value`, as in a raw
+    /// request or a curl command.
+    pub fn request_text(&self, text: &str) -> String {
+        if !self.config.enabled {
+            return text.to_string();
+        }
+        let text = match &self.header_line {
+            Some(re) => re.replace_all(text, format!("${{1}}${{2}}{}", MASK)),
+            None => text.into(),
+        };
+        self.text(&text)
+    }
+}
+
+/// `~/.curl-ui/settings/redaction.json`. Kept out of `~/.curl-ui` itself, where every JSON
+/// file is listed as a project.
+pub fn config_path(home_dir: &Path) -> PathBuf {
+    home_dir
+        .join(".curl-ui")
+        .join("settings")
+        .join("redaction.json")
+}
+
+/// The saved configuration, or `None` when there is none yet.
+pub fn load_config(path: &Path) -> Result<Option<RedactionConfig>, String> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| format!("Invalid redaction config {}: {}", path.display(), e))
+}
+
+pub fn save_config(path: &Path, config: &RedactionConfig) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Mask the responses kept with the executions of a saved collection: headers, body and the
+/// request as sent. Other fields, such as the auth settings of requests, are left alone.
+pub fn collection_history(redactor: &Redactor, collection: &mut serde_json::Value) {
+    let Some(executions) = collection
+        .get_mut("executions")
+        .and_then(|e| e.as_array_mut())
+    else {
+        return;
+    };
+    for response in executions
+        .iter_mut()
+        .filter_map(|e| e.get_mut("lastResponse"))
+    {
+        let Some(response) = response.as_object_mut() else {
+            continue;
+        };
+        match response.get_mut("headers") {
+            Some(serde_json::Value::Array(headers)) => {
+                for pair in headers.iter_mut().filter_map(|h| h.as_array_mut()) {
+                    if let [serde_json::Value::String(name), serde_json::Value::String(value)] =
+                        pair.as_mut_slice()
+                    {
+                        *value = redactor.header_value(name, value);
+                    }
+                }
+            }
+            Some(serde_json::Value::Object(headers)) => {
+                for (name, value) in headers.iter_mut() {
+                    if let serde_json::Value::String(v) = value {
+                        *v = redactor.header_value(name, v);
+                    }
+                }
+            }
+            _ => {}
+        }
+        for (key, apply) in [
+            ("body", Redactor::text as fn(&Redactor, &str) -> String),
+            ("requestUrl", Redactor::url),
+            ("requestRaw", Redactor::request_text),
+            ("requestCurl", Redactor::request_text),
+        ] {
+            if let Some(serde_json::Value::String(value)) = response.get_mut(key) {
+                *value = apply(redactor, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_text_masks_secret_headers_in_raw_and_curl() {
+        let redactor = Redactor::default().with_header_name("X-Custom-Key");
+        let raw = "GET / HTTP/1.1\r\nCookie: sid=abc\r\nX-Custom-Key: k1\r\nAccept: */*\r\n";
+        assert_eq!(
+            redactor.request_text(raw),
+            "GET / HTTP/1.1\r\nCookie: [REDACTED]\r\nX-Custom-Key: [REDACTED]\r\nAccept: */*\r\n"
+        );
+        let curl = "curl -X GET 'https://h/?token=t' -H 'cookie: sid=abc' -H 'Accept: */*'";
+        assert_eq!(
+            redactor.request_text(curl),
+            "curl -X GET 'https://h/?token=[REDACTED]' -H 'cookie: [REDACTED]' -H 'Accept: */*'"
+        );
+    }
+
+    #[test]
+    fn collection_history_masks_only_last_responses() {
+        let mut collection = json!({
+            "requests": [{ "auth": { "type": "bearer", "token": "keep-me" } }],
+            "executions": [{
+                "lastResponse": {
+                    "headers": [["Set-Cookie", "sid=abc"], ["Content-Type", "application/json"]],
+                    "body": "{\"access_token\": \"t1\"}",
+                    "requestUrl": "https://h/?api_key=k",
+                    "requestRaw": "GET / HTTP/1.1\r\nAuthorization: Bearer t2\r\n"
+                }
+            }, { "name": "never run" }]
+        });
+        collection_history(&Redactor::default(), &mut collection);
+        assert_eq!(collection["requests"][0]["auth"]["token"], "keep-me");
+        let response = &collection["executions"][0]["lastResponse"];
+        assert_eq!(response["headers"][0][1], MASK);
+        assert_eq!(response["headers"][1][1], "application/json");
+        assert_eq!(response["body"], "{\"access_token\": [REDACTED]}");
+        assert_eq!(response["requestUrl"], "https://h/?api_key=[REDACTED]");
+        assert_eq!(
+            response["requestRaw"],
+            "GET / HTTP/1.1\r\nAuthorization: [REDACTED]\r\n"
+        );
+    }
+
+    #[test]
+    fn config_round_trips_through_its_file() {
+        let dir = std::env::temp_dir().join(format!("curl-ui-redact-{}", std::process::id()));
+        let path = config_path(&dir);
+        assert!(load_config(&path).unwrap().is_none());
+
+        let config = RedactionConfig {
+            header_names: vec!["x-secret".to_string()],
+            ..RedactionConfig::default()
+        };
+        save_config(&path, &config).unwrap();
+        let loaded = load_config(&path).unwrap().unwrap();
+        assert_eq!(loaded.header_names, vec!["x-secret".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
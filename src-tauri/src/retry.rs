@@ -0,0 +1,250 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// When and how often a failed request is sent again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Statuses to retry, same syntax as success codes, e.g. "502,503,504,429"
+    #[serde(default = "default_status_codes")]
+    pub status_codes: String,
+    /// Transport errors to retry: "connect", "timeout", "reset", "request" and "body"
+    #[serde(default = "default_error_kinds")]
+    pub error_kinds: Vec<String>,
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    /// Growth factor of the delay between attempts
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+    /// Upper bound for any delay, including one requested by `Retry-After`
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Pick each delay at random between zero and the computed backoff ("full jitter")
+    #[serde(default = "default_true")]
+    pub jitter: bool,
+    #[serde(default = "default_true")]
+    pub respect_retry_after: bool,
+    /// Only retry transport errors of idempotent methods (GET, HEAD, PUT, DELETE, OPTIONS,
+    /// TRACE), as a POST or PATCH may have reached the server before failing. Connect errors
+    /// are always retried: the request was never sent.
+    #[serde(default = "default_true")]
+    pub idempotent_only: bool,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_status_codes() -> String {
+    "429,502,503,504".to_string()
+}
+
+fn default_error_kinds() -> Vec<String> {
+    vec![
+        "connect".to_string(),
+        "timeout".to_string(),
+        "reset".to_string(),
+    ]
+}
+
+fn default_initial_delay_ms() -> u64 {
+    500
+}
+
+fn default_multiplier() -> f64 {
+    2.0
+}
+
+fn default_max_delay_ms() -> u64 {
+    30_000
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// One try of a request sent with a retry policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryAttempt {
+    /// 1-based
+    pub attempt: u32,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub error_kind: Option<String>,
+    pub time_ms: u64,
+    /// Wait before the next attempt, 0 for the last one
+    pub delay_ms: u64,
+}
+
+/// A failed send, with the transport error kind when there is one.
+/// Errors without a kind (invalid method, auth setup...) are never retried.
+pub struct SendError {
+    pub kind: Option<&'static str>,
+    pub message: String,
+}
+
+impl From<String> for SendError {
+    fn from(message: String) -> Self {
+        SendError {
+            kind: None,
+            message,
+        }
+    }
+}
+
+/// Classify a reqwest error into one of the policy's error kinds.
+pub fn error_kind(error: &reqwest::Error) -> &'static str {
+    if error.is_timeout() {
+        return "timeout";
+    }
+    if error.is_connect() {
+        return "connect";
+    }
+    if error.is_body() || error.is_decode() {
+        return "body";
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            use std::io::ErrorKind::*;
+            if matches!(
+                io.kind(),
+                ConnectionReset | ConnectionAborted | BrokenPipe | UnexpectedEof
+            ) {
+                return "reset";
+            }
+        }
+        let text = err.to_string();
+        if text.contains("connection closed") || text.contains("reset") {
+            return "reset";
+        }
+        source = err.source();
+    }
+    "request"
+}
+
+/// Check a status against a list like "2xx,304", as use-case steps do. Shared with the
+/// success codes of batch runs.
+pub fn status_matches(codes: &str, status: u16) -> bool {
+    let status = status.to_string();
+    codes.split(',').map(|c| c.trim()).any(|c| {
+        if c.contains(['x', 'X']) {
+            status.starts_with(&c.replace(['x', 'X'], ""))
+        } else {
+            status == c
+        }
+    })
+}
+
+impl RetryPolicy {
+    pub fn retries_status(&self, status: u16) -> bool {
+        status_matches(&self.status_codes, status)
+    }
+
+    pub fn retries_error(&self, method: &str, kind: Option<&str>) -> bool {
+        let Some(kind) = kind else {
+            return false;
+        };
+        if self.idempotent_only && kind != "connect" && !is_idempotent(method) {
+            return false;
+        }
+        self.error_kinds.iter().any(|k| k == kind)
+    }
+
+    /// Delay before attempt `attempt + 1`, preferring the server's `Retry-After` if allowed.
+    pub fn delay(&self, attempt: u32, retry_after: Option<&str>) -> Duration {
+        let max = self.max_delay_ms as f64;
+        if self.respect_retry_after {
+            if let Some(ms) = retry_after.and_then(parse_retry_after) {
+                return Duration::from_millis(ms.min(self.max_delay_ms));
+            }
+        }
+        let backoff = (self.initial_delay_ms as f64
+            * self
+                .multiplier
+                .max(1.0)
+                .powi(attempt.saturating_sub(1) as i32))
+        .min(max);
+        let delay = if self.jitter {
+            rand::thread_rng().gen_range(0.0..=backoff)
+        } else {
+            backoff
+        };
+        Duration::from_millis(delay as u64)
+    }
+}
+
+fn is_idempotent(method: &str) -> bool {
+    ["GET", "HEAD", "PUT", "DELETE", "OPTIONS", "TRACE"]
+        .iter()
+        .any(|m| m.eq_ignore_ascii_case(method))
+}
+
+/// `Retry-After` as delay-seconds or an HTTP date, in milliseconds from now.
+fn parse_retry_after(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds.saturating_mul(1000));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let ms = (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_milliseconds();
+    Some(ms.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(idempotent_only: bool) -> RetryPolicy {
+        serde_json::from_value(serde_json::json!({
+            "error_kinds": ["connect", "timeout", "reset"],
+            "idempotent_only": idempotent_only,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn status_lists_take_codes_and_classes() {
+        assert!(status_matches("2xx", 204));
+        assert!(status_matches("200, 304", 304));
+        assert!(status_matches("5XX,429", 429));
+        assert!(!status_matches("2xx", 302));
+        assert!(!status_matches("20", 200));
+        assert!(!status_matches("", 200));
+    }
+
+    #[test]
+    fn idempotent_only_is_the_default() {
+        let policy: RetryPolicy = serde_json::from_str("{}").unwrap();
+        assert!(policy.idempotent_only);
+    }
+
+    #[test]
+    fn transport_errors_of_non_idempotent_methods_are_not_retried() {
+        let policy = policy(true);
+        assert!(policy.retries_error("GET", Some("reset")));
+        assert!(policy.retries_error("put", Some("timeout")));
+        assert!(!policy.retries_error("POST", Some("reset")));
+        assert!(!policy.retries_error("PATCH", Some("timeout")));
+        // The request never left, retrying cannot repeat it
+        assert!(policy.retries_error("POST", Some("connect")));
+        assert!(!policy.retries_error("GET", None));
+    }
+
+    #[test]
+    fn opting_out_retries_any_method() {
+        let policy = policy(false);
+        assert!(policy.retries_error("POST", Some("reset")));
+        assert!(!policy.retries_error("POST", Some("body")));
+    }
+
+    #[test]
+    fn retry_after_is_capped_by_max_delay() {
+        let policy: RetryPolicy = serde_json::from_str(r#"{"max_delay_ms": 2000}"#).unwrap();
+        assert_eq!(policy.delay(1, Some("1")), Duration::from_millis(1000));
+        assert_eq!(policy.delay(1, Some("120")), Duration::from_millis(2000));
+    }
+}
@@ -0,0 +1,665 @@
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::BTreeMap;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DigestConfig {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AwsSigV4Config {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    #[serde(default)]
+    pub session_token: Option<String>,
+    pub region: String,
+    pub service: String,
+    /// Sign with `UNSIGNED-PAYLOAD` instead of hashing the body (S3 streaming uploads).
+    #[serde(default)]
+    pub unsigned_payload: bool,
+}
+
+// --- HTTP Digest (RFC 7616) ---
+
+/// Parse the parameters of a `WWW-Authenticate: Digest ...` challenge.
+/// Returns None when the header is not a Digest challenge.
+fn parse_digest_challenge(header: &str) -> Option<BTreeMap<String, String>> {
+    let header = header.trim();
+    if header.len() < 7 || !header[..7].eq_ignore_ascii_case("digest ") {
+        return None;
+    }
+
+    let mut params = BTreeMap::new();
+    let mut chars = header[7..].chars().peekable();
+    loop {
+        while matches!(chars.peek(), Some(c) if *c == ',' || c.is_whitespace()) {
+            chars.next();
+        }
+        let key: String = chars
+            .by_ref()
+            .take_while(|c| *c != '=')
+            .collect::<String>()
+            .trim()
+            .to_lowercase();
+        if key.is_empty() {
+            break;
+        }
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some(escaped) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    '"' => break,
+                    _ => value.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.peek() {
+                if *c == ',' {
+                    break;
+                }
+                value.push(*c);
+                chars.next();
+            }
+            value = value.trim().to_string();
+        }
+        params.insert(key, value);
+    }
+    Some(params)
+}
+
+/// Pick the Digest challenge out of a 401 response's `WWW-Authenticate` headers,
+/// preferring SHA-256 when the server offers several algorithms.
+pub fn select_digest_challenge<'a>(headers: impl Iterator<Item = &'a str>) -> Option<String> {
+    let challenges: Vec<&str> = headers
+        .filter(|h| parse_digest_challenge(h).is_some())
+        .collect();
+    challenges
+        .iter()
+        .find(|h| h.to_uppercase().contains("SHA-256"))
+        .or_else(|| challenges.first())
+        .map(|h| h.to_string())
+}
+
+/// Compute the `Authorization` header answering a Digest challenge.
+/// `uri` is the request target (path + query) and `body` is only used for `qop=auth-int`.
+pub fn digest_authorization(
+    challenge: &str,
+    config: &DigestConfig,
+    method: &str,
+    uri: &str,
+    body: &[u8],
+    cnonce: &str,
+) -> Result<String, String> {
+    let params =
+        parse_digest_challenge(challenge).ok_or("Server did not send a Digest challenge")?;
+    let realm = params.get("realm").cloned().unwrap_or_default();
+    let nonce = params
+        .get("nonce")
+        .ok_or("Digest challenge is missing the nonce")?;
+    let algorithm = params
+        .get("algorithm")
+        .cloned()
+        .unwrap_or_else(|| "MD5".to_string());
+
+    let hash: fn(&[u8]) -> String = match algorithm.to_uppercase().trim_end_matches("-SESS") {
+        "MD5" => |data| hex::encode(Md5::digest(data)),
+        "SHA-256" => |data| hex::encode(Sha256::digest(data)),
+        other => return Err(format!("Unsupported Digest algorithm: {}", other)),
+    };
+    let is_sess = algorithm.to_uppercase().ends_with("-SESS");
+
+    // Prefer "auth" over "auth-int" when both are offered, and only answer with one of them.
+    let qop = match params.get("qop") {
+        Some(q) => {
+            let options: Vec<&str> = q.split(',').map(|o| o.trim()).collect();
+            if options.contains(&"auth") {
+                Some("auth")
+            } else if options.contains(&"auth-int") {
+                Some("auth-int")
+            } else {
+                return Err(format!("Unsupported Digest qop: {}", q));
+            }
+        }
+        None => None,
+    };
+    let nc = "00000001";
+
+    let mut ha1 = hash(format!("{}:{}:{}", config.username, realm, config.password).as_bytes());
+    if is_sess {
+        ha1 = hash(format!("{}:{}:{}", ha1, nonce, cnonce).as_bytes());
+    }
+    let ha2 = match qop {
+        Some("auth-int") => hash(format!("{}:{}:{}", method, uri, hash(body)).as_bytes()),
+        _ => hash(format!("{}:{}", method, uri).as_bytes()),
+    };
+    let response = match qop {
+        Some(qop) => {
+            hash(format!("{}:{}:{}:{}:{}:{}", ha1, nonce, nc, cnonce, qop, ha2).as_bytes())
+        }
+        None => hash(format!("{}:{}:{}", ha1, nonce, ha2).as_bytes()),
+    };
+
+    let mut header = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+        config.username, realm, nonce, uri, algorithm, response
+    );
+    if let Some(opaque) = params.get("opaque") {
+        header.push_str(&format!(", opaque=\"{}\"", opaque));
+    }
+    if let Some(qop) = qop {
+        header.push_str(&format!(", qop={}, nc={}, cnonce=\"{}\"", qop, nc, cnonce));
+    }
+    Ok(header)
+}
+
+// --- AWS Signature Version 4 ---
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// RFC 3986 encoding as AWS expects it: everything but unreserved characters is escaped.
+fn aws_uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            b'/' if !encode_slash => out.push('/'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Compute the SigV4 headers for a request. `headers` are the headers that will be sent
+/// (all of them are signed) and `body` is the final payload, None when it cannot be
+/// hashed up front (multipart), which forces `UNSIGNED-PAYLOAD`.
+/// Returns the headers to add: x-amz-date, x-amz-content-sha256 (S3 / unsigned payloads),
+/// the optional x-amz-security-token and Authorization.
+pub fn sigv4_headers(
+    config: &AwsSigV4Config,
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    body: Option<&[u8]>,
+    now: DateTime<Utc>,
+) -> Result<Vec<(String, String)>, String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL for SigV4: {}", e))?;
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err("SigV4 requires a URL with a host".to_string()),
+    };
+
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date_stamp = now.format("%Y%m%d").to_string();
+    let payload_hash = match body {
+        Some(body) if !config.unsigned_payload => hex::encode(Sha256::digest(body)),
+        _ => "UNSIGNED-PAYLOAD".to_string(),
+    };
+
+    let mut added = vec![("x-amz-date".to_string(), amz_date.clone())];
+    // Only S3 requires the payload hash header; it is also needed to announce an unsigned payload.
+    if config.service == "s3" || payload_hash == "UNSIGNED-PAYLOAD" {
+        added.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+    }
+    if let Some(token) = &config.session_token {
+        added.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+
+    // Canonical headers: lowercase names, trimmed values, duplicates joined with commas.
+    let mut canonical: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    canonical.insert("host".to_string(), vec![host]);
+    for (name, value) in headers
+        .iter()
+        .filter(|(name, _)| !name.eq_ignore_ascii_case("authorization"))
+        .chain(added.iter())
+    {
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        canonical
+            .entry(name.to_lowercase())
+            .or_default()
+            .push(value);
+    }
+    let canonical_headers: String = canonical
+        .iter()
+        .map(|(name, values)| format!("{}:{}\n", name, values.join(",")))
+        .collect();
+    let signed_headers = canonical.keys().cloned().collect::<Vec<_>>().join(";");
+
+    // S3 uses the path as-is, every other service expects each segment encoded again.
+    let path = if url.path().is_empty() {
+        "/"
+    } else {
+        url.path()
+    };
+    let canonical_uri = if config.service == "s3" {
+        path.to_string()
+    } else {
+        aws_uri_encode(path, false)
+    };
+
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (aws_uri_encode(&k, true), aws_uri_encode(&v, true)))
+        .collect();
+    query.sort();
+    let canonical_query = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method.to_uppercase(),
+        canonical_uri,
+        canonical_query,
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+
+    let scope = format!(
+        "{}/{}/{}/aws4_request",
+        date_stamp, config.region, config.service
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac_sha256(
+        format!("AWS4{}", config.secret_access_key).as_bytes(),
+        date_stamp.as_bytes(),
+    );
+    let k_region = hmac_sha256(&k_date, config.region.as_bytes());
+    let k_service = hmac_sha256(&k_region, config.service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    added.push((
+        "Authorization".to_string(),
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            config.access_key_id, scope, signed_headers, signature
+        ),
+    ));
+    Ok(added)
+}
+
+// --- Custom HMAC signatures ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HmacConfig {
+    pub secret: String,
+    #[serde(default)]
+    pub key_id: Option<String>,
+    /// "sha256" (default), "sha1" or "sha512"
+    #[serde(default = "default_hmac_algorithm")]
+    pub algorithm: String,
+    /// "hex" (default) or "base64"
+    #[serde(default = "default_hmac_encoding")]
+    pub encoding: String,
+    /// Canonical string to sign. Placeholders: {method} {path} {query} {host} {timestamp}
+    /// {nonce} {body} {body_sha256} {key_id}; a literal `\n` is turned into a newline.
+    #[serde(default = "default_hmac_template")]
+    pub template: String,
+    /// Header receiving the signature.
+    #[serde(default = "default_hmac_header")]
+    pub header: String,
+    /// Value written to `header`, `{signature}` plus the template placeholders. Defaults to the bare signature.
+    #[serde(default)]
+    pub header_value: Option<String>,
+    #[serde(default)]
+    pub timestamp_header: Option<String>,
+    /// "unix" (default), "unix_ms" or "iso8601"
+    #[serde(default = "default_timestamp_format")]
+    pub timestamp_format: String,
+    #[serde(default)]
+    pub nonce_header: Option<String>,
+}
+
+fn default_hmac_algorithm() -> String {
+    "sha256".to_string()
+}
+
+fn default_hmac_encoding() -> String {
+    "hex".to_string()
+}
+
+fn default_hmac_template() -> String {
+    "{method}\\n{path}\\n{timestamp}\\n{body}".to_string()
+}
+
+fn default_hmac_header() -> String {
+    "X-Signature".to_string()
+}
+
+fn default_timestamp_format() -> String {
+    "unix".to_string()
+}
+
+fn hmac_digest(algorithm: &str, key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    fn run<M: Mac + hmac::digest::KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+    match algorithm.to_lowercase().replace('-', "").as_str() {
+        "sha1" => Ok(run::<Hmac<Sha1>>(key, data)),
+        "sha256" => Ok(run::<Hmac<Sha256>>(key, data)),
+        "sha512" => Ok(run::<Hmac<Sha512>>(key, data)),
+        other => Err(format!("Unsupported HMAC algorithm: {}", other)),
+    }
+}
+
+/// Compute the headers for a custom HMAC signature: the signature header plus the optional
+/// timestamp and nonce headers, so the server can rebuild the same canonical string.
+pub fn hmac_headers(
+    config: &HmacConfig,
+    method: &str,
+    url: &str,
+    body: &[u8],
+    now: DateTime<Utc>,
+    nonce: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL for HMAC: {}", e))?;
+    let timestamp = match config.timestamp_format.as_str() {
+        "unix_ms" => now.timestamp_millis().to_string(),
+        "iso8601" => now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        _ => now.timestamp().to_string(),
+    };
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => String::new(),
+    };
+    let key_id = config.key_id.clone().unwrap_or_default();
+
+    let fill = |template: &str| {
+        template
+            .replace("\\n", "\n")
+            .replace("{method}", &method.to_uppercase())
+            .replace("{path}", url.path())
+            .replace("{query}", url.query().unwrap_or(""))
+            .replace("{host}", &host)
+            .replace("{timestamp}", &timestamp)
+            .replace("{nonce}", nonce)
+            .replace("{key_id}", &key_id)
+            .replace("{body_sha256}", &hex::encode(Sha256::digest(body)))
+            // Last, so placeholders inside the body are left alone.
+            .replace("{body}", &String::from_utf8_lossy(body))
+    };
+
+    let digest = hmac_digest(
+        &config.algorithm,
+        config.secret.as_bytes(),
+        fill(&config.template).as_bytes(),
+    )?;
+    let signature = match config.encoding.as_str() {
+        "base64" => base64::engine::general_purpose::STANDARD.encode(digest),
+        _ => hex::encode(digest),
+    };
+
+    let mut headers = vec![(
+        config.header.clone(),
+        match &config.header_value {
+            Some(template) => fill(template).replace("{signature}", &signature),
+            None => signature,
+        },
+    )];
+    if let Some(name) = &config.timestamp_header {
+        headers.push((name.clone(), timestamp.clone()));
+    }
+    if let Some(name) = &config.nonce_header {
+        headers.push((name.clone(), nonce.to_string()));
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// The challenge of RFC 2617 section 3.5, with `extra` parameters appended.
+    fn rfc2617_challenge(extra: &str) -> String {
+        format!(
+            "Digest realm=\"testrealm@host.com\", nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", \
+             opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"{}",
+            extra
+        )
+    }
+
+    fn mufasa(password: &str) -> DigestConfig {
+        DigestConfig {
+            username: "Mufasa".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    /// The `response` parameter of an Authorization header.
+    fn response_of(header: &str) -> String {
+        parse_digest_challenge(header).unwrap()["response"].clone()
+    }
+
+    fn digest(challenge: &str, body: &[u8], cnonce: &str) -> Result<String, String> {
+        digest_authorization(
+            challenge,
+            &mufasa("Circle Of Life"),
+            "GET",
+            "/dir/index.html",
+            body,
+            cnonce,
+        )
+    }
+
+    #[test]
+    fn digest_md5_with_qop_matches_rfc2617() {
+        let header = digest(
+            &rfc2617_challenge(", qop=\"auth,auth-int\""),
+            b"",
+            "0a4f113b",
+        )
+        .unwrap();
+        assert_eq!(response_of(&header), "6629fae49393a05397450978507c4ef1");
+        assert!(header.contains("qop=auth, nc=00000001, cnonce=\"0a4f113b\""));
+        assert!(header.contains("opaque=\"5ccc069c403ebaf9f0171e9517f40e41\""));
+    }
+
+    #[test]
+    fn digest_md5_without_qop() {
+        // RFC 2069 compatibility: response = MD5(HA1:nonce:HA2)
+        let header = digest(&rfc2617_challenge(""), b"", "0a4f113b").unwrap();
+        assert_eq!(response_of(&header), "670fd8c2df070c60b045671b8b24ff02");
+        assert!(!header.contains("qop="));
+    }
+
+    #[test]
+    fn digest_md5_sess() {
+        let challenge = rfc2617_challenge(", algorithm=MD5-sess, qop=\"auth\"");
+        let header = digest(&challenge, b"", "0a4f113b").unwrap();
+        assert_eq!(response_of(&header), "8e3825c57e897f5a0dec6c2d4e5059d0");
+        assert!(header.contains("algorithm=MD5-sess"));
+
+        let challenge = rfc2617_challenge(", algorithm=MD5-sess");
+        let header = digest(&challenge, b"", "0a4f113b").unwrap();
+        assert_eq!(response_of(&header), "4726bc10c33fa6cb357eb27807b1cce8");
+    }
+
+    #[test]
+    fn digest_auth_int_hashes_the_body() {
+        let header = digest(
+            &rfc2617_challenge(", qop=\"auth-int\""),
+            b"hello",
+            "0a4f113b",
+        )
+        .unwrap();
+        assert_eq!(response_of(&header), "4b9dff6a3247bddd2fed3d63a302e8dc");
+        assert!(header.contains("qop=auth-int"));
+    }
+
+    #[test]
+    fn digest_only_answers_with_an_offered_qop() {
+        let error = digest(&rfc2617_challenge(", qop=\"auth-conf\""), b"", "0a4f113b").unwrap_err();
+        assert_eq!(error, "Unsupported Digest qop: auth-conf");
+    }
+
+    /// RFC 7616 section 3.9.1, for MD5 and SHA-256.
+    #[test]
+    fn digest_matches_rfc7616() {
+        let config = mufasa("Circle of Life");
+        let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+        for (algorithm, expected) in [
+            ("MD5", "8ca523f5e9506fed4657c9700eebdbec"),
+            (
+                "SHA-256",
+                "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1",
+            ),
+        ] {
+            let challenge = format!(
+                "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", \
+                 algorithm={}, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+                 opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+                algorithm
+            );
+            let header =
+                digest_authorization(&challenge, &config, "GET", "/dir/index.html", b"", cnonce)
+                    .unwrap();
+            assert_eq!(response_of(&header), expected, "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn prefers_the_sha256_challenge() {
+        let headers = [
+            "Basic realm=\"x\"",
+            "Digest realm=\"x\", nonce=\"1\", algorithm=MD5",
+            "Digest realm=\"x\", nonce=\"1\", algorithm=SHA-256",
+        ];
+        let challenge = select_digest_challenge(headers.into_iter()).unwrap();
+        assert!(challenge.ends_with("SHA-256"));
+    }
+
+    /// Credentials and date of the AWS SigV4 test suite.
+    fn aws_test_suite() -> (AwsSigV4Config, DateTime<Utc>) {
+        let config = AwsSigV4Config {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+            region: "us-east-1".to_string(),
+            service: "service".to_string(),
+            unsigned_payload: false,
+        };
+        (
+            config,
+            Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap(),
+        )
+    }
+
+    fn sigv4_authorization(
+        method: &str,
+        url: &str,
+        headers: &[(String, String)],
+        body: &[u8],
+    ) -> String {
+        let (config, now) = aws_test_suite();
+        let signed = sigv4_headers(&config, method, url, headers, Some(body), now).unwrap();
+        assert!(signed.contains(&("x-amz-date".to_string(), "20150830T123600Z".to_string())));
+        signed
+            .into_iter()
+            .find(|(name, _)| name == "Authorization")
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn sigv4_get_vanilla() {
+        assert_eq!(
+            sigv4_authorization("GET", "https://example.amazonaws.com/", &[], b""),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn sigv4_get_vanilla_query_order_key_case() {
+        let authorization = sigv4_authorization(
+            "GET",
+            "https://example.amazonaws.com/?Param2=value2&Param1=value1",
+            &[],
+            b"",
+        );
+        assert!(authorization.ends_with(
+            "Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        ));
+    }
+
+    #[test]
+    fn sigv4_post_vanilla() {
+        let authorization = sigv4_authorization("POST", "https://example.amazonaws.com/", &[], b"");
+        assert!(authorization.ends_with(
+            "Signature=5da7c1a2acd57cee7505fc6676e4e544621c30862966e37dddb68e92efbe5d6b"
+        ));
+    }
+
+    #[test]
+    fn sigv4_post_x_www_form_urlencoded() {
+        let headers = [(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        )];
+        let authorization = sigv4_authorization(
+            "POST",
+            "https://example.amazonaws.com/",
+            &headers,
+            b"Param1=value1",
+        );
+        assert!(authorization.contains("SignedHeaders=content-type;host;x-amz-date,"));
+        assert!(authorization.ends_with(
+            "Signature=ff11897932ad3f4e8b18135d722051e5ac45fc38421b1da7b9d196a0fe09473a"
+        ));
+    }
+
+    #[test]
+    fn sigv4_unsigned_payload_is_announced() {
+        let (mut config, now) = aws_test_suite();
+        config.unsigned_payload = true;
+        let signed = sigv4_headers(
+            &config,
+            "PUT",
+            "https://example.amazonaws.com/",
+            &[],
+            Some(b"x"),
+            now,
+        )
+        .unwrap();
+        assert!(signed.contains(&(
+            "x-amz-content-sha256".to_string(),
+            "UNSIGNED-PAYLOAD".to_string()
+        )));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::commands::{
+    active_redactor, build_request, generate_request_data, project_client, version_label,
+    with_http_version, ClientOptions, HttpRequestArgs,
+};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{command, Emitter};
+use tokio::sync::oneshot;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SseEvent {
+    pub id: Option<String>,
+    pub event: String,
+    pub data: String,
+    pub retry: Option<u64>,
+    /// Milliseconds since the connection was opened
+    pub elapsed_ms: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SseEventPayload {
+    pub request_id: String,
+    pub event: SseEvent,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SseResponse {
+    status: u16,
+    headers: Vec<Vec<String>>,
+    /// Captured event log, in arrival order
+    events: Vec<SseEvent>,
+    /// Body of a non event-stream response (e.g. an error page), empty otherwise
+    body: String,
+    /// "server" when the stream ended, "client" when it was closed from the UI, "error" when
+    /// the connection broke
+    closed_by: String,
+    /// Why the stream was interrupted; the events received until then are kept
+    error: Option<String>,
+    request_raw: String,
+    request_curl: String,
+    http_version: String,
+}
+
+/// Incremental `text/event-stream` parser following the WHATWG algorithm.
+/// Works on bytes so chunks splitting a UTF-8 sequence or a CRLF are handled.
+#[derive(Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    pending_cr: bool,
+    data: String,
+    event_type: String,
+    last_event_id: Option<String>,
+    retry: Option<u64>,
+}
+
+impl SseParser {
+    /// Feed a chunk and return the events completed by it.
+    pub fn feed(&mut self, chunk: &[u8], elapsed_ms: u64) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if self.pending_cr {
+                self.pending_cr = false;
+                if byte == b'\n' {
+                    continue;
+                }
+            }
+            match byte {
+                b'\r' | b'\n' => {
+                    self.pending_cr = byte == b'\r';
+                    let line = String::from_utf8_lossy(&self.line).into_owned();
+                    self.line.clear();
+                    if let Some(event) = self.process_line(&line, elapsed_ms) {
+                        events.push(event);
+                    }
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &str, elapsed_ms: u64) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch(elapsed_ms);
+        }
+        if line.starts_with(':') {
+            return None; // comment / keep-alive
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "event" => self.event_type = value.to_string(),
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self, elapsed_ms: u64) -> Option<SseEvent> {
+        let event_type = std::mem::take(&mut self.event_type);
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop(); // trailing newline
+        Some(SseEvent {
+            id: self.last_event_id.clone(),
+            event: if event_type.is_empty() {
+                "message".to_string()
+            } else {
+                event_type
+            },
+            data,
+            retry: self.retry.take(),
+            elapsed_ms,
+        })
+    }
+}
+
+/// Open a Server-Sent Events stream. Every event is emitted to the frontend as an `sse-event`
+/// as soon as it is parsed; the command resolves with the full event log once the server
+/// ends the stream or `close_sse_stream` is called with the same `request_id`.
+#[command]
+pub async fn sse_request(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::HttpRequestState>,
+    redaction: tauri::State<'_, crate::RedactionState>,
+    args: HttpRequestArgs,
+) -> Result<SseResponse, String> {
+    let redactor = active_redactor(&redaction, args.reveal_secrets);
+    let (tx, rx) = oneshot::channel::<()>();
+    let request_id = args.request_id.clone();
+
+    if let Some(id) = &request_id {
+        let mut streams = state.streams.lock().await;
+        streams.insert(id.clone(), tx);
+    }
+
+    let p_name = args
+        .project_name
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let tokens = Arc::clone(&state.tokens);
+    let mut args = args;
+    crate::dns::merge_project(&state, &p_name, &mut args).await;
+    let (client, jar) = project_client(&state, &p_name, &ClientOptions::from_args(&args)).await?;
+
+    // Filled once the response headers are in, so that closing keeps the events received
+    let mut received: Option<SseResponse> = None;
+    let stream_future = async {
+        let method = Method::from_str(&args.method.to_uppercase())
+            .map_err(|e| format!("Invalid method: {}", e))?;
+        if !crate::commands::has_header(&args, "accept") {
+            args.headers
+                .push(vec!["Accept".to_string(), "text/event-stream".to_string()]);
+        }
+        // Events are parsed as they arrive, which a compressed stream would not allow
+        if !crate::commands::has_header(&args, "accept-encoding") {
+            args.headers
+                .push(vec!["Accept-Encoding".to_string(), "identity".to_string()]);
+        }
+        crate::auth::apply(&app_handle, &client, &tokens, &p_name, &mut args).await?;
+        let (request_raw, request_curl) = generate_request_data(&args, &jar, redactor.as_ref());
+
+        let mut response = build_request(&client, method, &args)
+            .await?
+            .send()
+            .await
+            .map_err(|e| {
+                let msg = format!("Request failed: {}", e);
+                crate::rust_error!(&app_handle, "{}", msg);
+                msg
+            })?;
+
+        let status = response.status().as_u16();
+        let http_version = version_label(response.version());
+        let request_raw = with_http_version(&request_raw, &http_version);
+        let mut headers: Vec<Vec<String>> = Vec::new();
+        for (key, value) in response.headers() {
+            if let Ok(v) = value.to_str() {
+                headers.push(vec![key.to_string(), v.to_string()]);
+            }
+        }
+        let is_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+        let content_encoding = response
+            .headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .filter(|encoding| !encoding.eq_ignore_ascii_case("identity"));
+        if let (true, Some(encoding)) = (is_stream, content_encoding) {
+            return Err(format!(
+                "Compressed event streams are not supported (Content-Encoding: {}), \
+                 remove the Accept-Encoding header",
+                encoding
+            ));
+        }
+
+        let mut result = SseResponse {
+            status,
+            headers,
+            events: Vec::new(),
+            body: String::new(),
+            closed_by: "server".to_string(),
+            error: None,
+            request_raw,
+            request_curl,
+            http_version,
+        };
+
+        if !is_stream {
+            result.body = response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read body: {}", e))?;
+            received = Some(result);
+            return Ok(());
+        }
+
+        let result = received.insert(result);
+        let started = Instant::now();
+        let mut parser = SseParser::default();
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    let msg = format!("Stream interrupted: {}", e);
+                    crate::rust_warn!(&app_handle, "{}", msg);
+                    result.closed_by = "error".to_string();
+                    result.error = Some(msg);
+                    break;
+                }
+            };
+            for event in parser.feed(&chunk, started.elapsed().as_millis() as u64) {
+                if let Some(id) = &request_id {
+                    let _ = app_handle.emit(
+                        "sse-event",
+                        SseEventPayload {
+                            request_id: id.clone(),
+                            event: event.clone(),
+                        },
+                    );
+                }
+                result.events.push(event);
+            }
+        }
+        Ok(())
+    };
+
+    // Closing can happen at any point, including while connecting or authenticating
+    let outcome = tokio::select! {
+        res = stream_future => res.map(|()| false),
+        _ = rx => Ok(true),
+    };
+
+    if let Some(id) = &request_id {
+        let mut streams = state.streams.lock().await;
+        streams.remove(id);
+    }
+
+    let closed_by_client = outcome?;
+    let mut result = received.ok_or_else(|| "Canceled".to_string())?;
+    if closed_by_client {
+        result.closed_by = "client".to_string();
+    }
+    Ok(result)
+}
+
+#[command]
+pub async fn close_sse_stream(
+    state: tauri::State<'_, crate::HttpRequestState>,
+    request_id: String,
+) -> Result<(), String> {
+    let mut streams = state.streams.lock().await;
+    if let Some(tx) = streams.remove(&request_id) {
+        let _ = tx.send(());
+    }
+    Ok(())
+}
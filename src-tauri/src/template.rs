@@ -0,0 +1,275 @@
+use axum::http::HeaderMap;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The incoming mock request, as seen by response templates.
+pub struct RequestContext<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    /// Path and query string, as received
+    pub uri: &'a str,
+    /// Values of the `{name}` segments of the matched endpoint path
+    pub path_params: HashMap<String, String>,
+    pub query: Vec<(String, String)>,
+    pub headers: &'a HeaderMap,
+    pub body: &'a str,
+    json: Option<serde_json::Value>,
+}
+
+impl<'a> RequestContext<'a> {
+    pub fn new(
+        method: &'a str,
+        uri: &'a axum::http::Uri,
+        headers: &'a HeaderMap,
+        body: &'a str,
+    ) -> Self {
+        RequestContext {
+            method,
+            path: uri.path(),
+            uri: uri
+                .path_and_query()
+                .map(|pq| pq.as_str())
+                .unwrap_or(uri.path()),
+            path_params: HashMap::new(),
+            query: parse_query(uri.query().unwrap_or("")),
+            headers,
+            body,
+            json: serde_json::from_str(body).ok(),
+        }
+    }
+
+    pub fn query_value(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// The body parsed as JSON, if it is JSON.
+    pub fn json(&self) -> Option<&serde_json::Value> {
+        self.json.as_ref()
+    }
+}
+
+/// Decode `a=1&b=x%20y` into pairs, keeping order and repeated keys.
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    reqwest::Url::parse(&format!("http://localhost/?{}", query))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default()
+}
+
+/// Render `{{...}}` expressions in a mock body or header value:
+///
+/// - `request.method`, `request.path`, `request.url`, `request.body`
+/// - `request.path.<name>` (from `{name}` segments), `request.query.<name>`,
+///   `request.headers.<name>`, `request.json.<path>` (e.g. `request.json.user.id`)
+/// - `now` or `now "%Y-%m-%d"`, `timestamp` (Unix ms), `uuid`, `randomInt 1 100`,
+///   `randomString 12`
+///
+/// Expressions that cannot be resolved are left in place as written.
+pub fn render(template: &str, ctx: &RequestContext) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match evaluate(after[..end].trim(), ctx) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split `name "quoted arg" 12` into words, dropping the quotes.
+fn words(expr: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for c in expr.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => {
+                words.push(std::mem::take(&mut current));
+                quote = None;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c.is_whitespace() => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            }
+            (None, c) => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn evaluate(expr: &str, ctx: &RequestContext) -> Option<String> {
+    let words = words(expr);
+    let (name, args) = words.split_first()?;
+    let number = |i: usize, default: i64| {
+        args.get(i)
+            .and_then(|a| a.parse::<i64>().ok())
+            .unwrap_or(default)
+    };
+
+    match name.as_str() {
+        "now" => {
+            let now = chrono::Utc::now();
+            match args.first() {
+                // An invalid format string fails here instead of panicking in `to_string`
+                Some(format) => {
+                    let mut out = String::new();
+                    write!(out, "{}", now.format(format)).ok().map(|_| out)
+                }
+                None => Some(now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            }
+        }
+        "timestamp" => Some(chrono::Utc::now().timestamp_millis().to_string()),
+        "uuid" => Some(uuid_v4()),
+        "randomInt" => {
+            let (min, max) = (number(0, 0), number(1, 1000));
+            Some(rand::thread_rng().gen_range(min..=max.max(min)).to_string())
+        }
+        "randomString" => {
+            let len = number(0, 16).clamp(0, 4096) as usize;
+            Some(
+                rand::thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(len)
+                    .map(char::from)
+                    .collect(),
+            )
+        }
+        _ => request_value(name, ctx),
+    }
+}
+
+fn request_value(expr: &str, ctx: &RequestContext) -> Option<String> {
+    let rest = expr.strip_prefix("request.")?;
+    match rest {
+        "method" => return Some(ctx.method.to_string()),
+        "path" => return Some(ctx.path.to_string()),
+        "url" => return Some(ctx.uri.to_string()),
+        "body" => return Some(ctx.body.to_string()),
+        _ => {}
+    }
+    let (scope, key) = rest.split_once('.')?;
+    match scope {
+        "path" => ctx.path_params.get(key).cloned(),
+        "query" => ctx.query_value(key).map(String::from),
+        "headers" => ctx.header(key).map(String::from),
+        "json" => crate::jsonpath::select(ctx.json()?, key).map(crate::jsonpath::to_text),
+        _ => None,
+    }
+}
+
+pub fn uuid_v4() -> String {
+    let mut bytes: [u8; 16] = rand::thread_rng().gen();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Render `template` for a POST to `uri` with an `X-Id` header and a JSON body.
+    fn render_for(template: &str, uri: &str) -> String {
+        let uri: axum::http::Uri = uri.parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-id", "abc".parse().unwrap());
+        let body = r#"{"user": {"id": 7, "tags": ["a", "b"]}}"#;
+        let mut ctx = RequestContext::new("POST", &uri, &headers, body);
+        ctx.path_params
+            .insert("petId".to_string(), "42".to_string());
+        render(template, &ctx)
+    }
+
+    #[test]
+    fn request_values() {
+        let uri = "/pets/42?name=Rex%20Jr&name=Max";
+        assert_eq!(
+            render_for("{{request.method}} {{request.path}}", uri),
+            "POST /pets/42"
+        );
+        assert_eq!(render_for("{{ request.url }}", uri), uri);
+        assert_eq!(render_for("{{request.path.petId}}", uri), "42");
+        // The first of repeated params, decoded
+        assert_eq!(render_for("{{request.query.name}}", uri), "Rex Jr");
+        assert_eq!(render_for("{{request.headers.X-Id}}", uri), "abc");
+        assert_eq!(render_for("{{request.json.user.id}}", uri), "7");
+        assert_eq!(render_for("{{request.json.user.tags[1]}}", uri), "b");
+        assert!(render_for("{{request.body}}", uri).starts_with(r#"{"user""#));
+    }
+
+    #[test]
+    fn unresolved_expressions_stay_as_written() {
+        let uri = "/pets";
+        for template in [
+            "{{request.query.missing}}",
+            "{{request.json.user.email}}",
+            "{{request.cookies.id}}",
+            "{{unknown}}",
+            "open {{request.method",
+        ] {
+            assert_eq!(render_for(template, uri), template);
+        }
+    }
+
+    #[test]
+    fn generated_values() {
+        let now = render_for("{{now}}", "/");
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(&now).is_ok(),
+            "{}",
+            now
+        );
+        assert_eq!(render_for(r#"{{now "%Y"}}"#, "/").len(), 4);
+        assert!(render_for("{{timestamp}}", "/").parse::<i64>().is_ok());
+
+        let uuid = render_for("{{uuid}}", "/");
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(uuid, render_for("{{uuid}}", "/"));
+
+        for _ in 0..20 {
+            let n: i64 = render_for("{{randomInt 5 7}}", "/").parse().unwrap();
+            assert!((5..=7).contains(&n));
+        }
+        let s = render_for("{{randomString 12}}", "/");
+        assert_eq!(s.len(), 12);
+        assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn words_keep_quoted_arguments_together() {
+        assert_eq!(words(r#"now "%d %b""#), vec!["now", "%d %b"]);
+        assert_eq!(words("  randomInt  1 9 "), vec!["randomInt", "1", "9"]);
+    }
+}
@@ -0,0 +1,480 @@
+use crate::auth::AuthConfig;
+use crate::commands::{
+    active_redactor, generate_request_data, has_header, project_client, ClientOptions,
+    HttpRequestArgs,
+};
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use reqwest::cookie::CookieStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{command, Emitter};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How long `ws_close` waits for the server to acknowledge the close frame.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sessions by id. An id maps to None while its connection is being opened.
+pub type WsSessions = Arc<Mutex<HashMap<String, Option<WsSession>>>>;
+
+/// An open connection: frames to send go through `sender`, the background task owns the socket.
+pub struct WsSession {
+    sender: mpsc::UnboundedSender<Message>,
+    transcript: Arc<Mutex<Vec<WsMessage>>>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WsMessage {
+    /// "sent" or "received"
+    pub direction: String,
+    /// "text", "binary", "ping", "pong" or "close"
+    pub kind: String,
+    /// Text payload, base64 for binary/ping/pong frames, the reason for close frames
+    pub data: String,
+    #[serde(default)]
+    pub close_code: Option<u16>,
+    /// Milliseconds since the connection was opened
+    pub elapsed_ms: u64,
+}
+
+impl WsMessage {
+    fn from_message(direction: &str, message: &Message, elapsed_ms: u64) -> Option<Self> {
+        let b64 = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+        let (kind, data, close_code) = match message {
+            Message::Text(text) => ("text", text.clone(), None),
+            Message::Binary(bytes) => ("binary", b64(bytes), None),
+            Message::Ping(bytes) => ("ping", b64(bytes), None),
+            Message::Pong(bytes) => ("pong", b64(bytes), None),
+            Message::Close(frame) => (
+                "close",
+                frame
+                    .as_ref()
+                    .map(|f| f.reason.to_string())
+                    .unwrap_or_default(),
+                frame.as_ref().map(|f| u16::from(f.code)),
+            ),
+            Message::Frame(_) => return None,
+        };
+        Some(WsMessage {
+            direction: direction.to_string(),
+            kind: kind.to_string(),
+            data,
+            close_code,
+            elapsed_ms,
+        })
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct WsMessagePayload {
+    pub session_id: String,
+    pub message: WsMessage,
+}
+
+#[derive(Clone, Serialize)]
+pub struct WsClosedPayload {
+    pub session_id: String,
+    pub code: Option<u16>,
+    pub reason: String,
+    pub error: Option<String>,
+    /// Every frame of the session, so that it can be saved once the connection is gone
+    pub transcript: Vec<WsMessage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WsConnectArgs {
+    /// Id chosen by the frontend, used by the other ws_* commands and in events
+    pub session_id: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<Vec<String>>,
+    #[serde(default)]
+    pub project_name: Option<String>,
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    #[serde(default)]
+    pub reveal_secrets: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WsConnectResponse {
+    /// Handshake response status (101)
+    status: u16,
+    headers: Vec<Vec<String>>,
+    request_raw: String,
+    request_curl: String,
+}
+
+/// Cookies are stored against http(s) URLs, so look them up with the matching scheme.
+fn cookie_url(url: &str) -> Result<reqwest::Url, String> {
+    let mut url = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let scheme = match url.scheme() {
+        "ws" => "http",
+        "wss" => "https",
+        other => return Err(format!("Unsupported WebSocket scheme: {}", other)),
+    };
+    let _ = url.set_scheme(scheme);
+    Ok(url)
+}
+
+/// Take `session_id` for a connection about to be opened.
+async fn reserve(sessions: &WsSessions, session_id: &str) -> Result<(), String> {
+    let mut sessions = sessions.lock().await;
+    if sessions.contains_key(session_id) {
+        return Err(format!("WebSocket session {} is already open", session_id));
+    }
+    sessions.insert(session_id.to_string(), None);
+    Ok(())
+}
+
+/// Forget a session whose connection ended, unless the id was since reused by another one.
+async fn release(sessions: &WsSessions, session_id: &str, transcript: &Arc<Mutex<Vec<WsMessage>>>) {
+    let mut sessions = sessions.lock().await;
+    let owned = match sessions.get(session_id) {
+        // Ended before `ws_connect` could register it
+        Some(None) => true,
+        Some(Some(session)) => Arc::ptr_eq(&session.transcript, transcript),
+        None => false,
+    };
+    if owned {
+        sessions.remove(session_id);
+    }
+}
+
+/// Open a WS/WSS connection with the project's cookies and auth. Frames are emitted as
+/// `ws-message` events and the end of the connection as `ws-closed`, with the transcript.
+#[command]
+pub async fn ws_connect(
+    app_handle: tauri::AppHandle,
+    http_state: tauri::State<'_, crate::HttpRequestState>,
+    state: tauri::State<'_, crate::WebSocketState>,
+    redaction: tauri::State<'_, crate::RedactionState>,
+    args: WsConnectArgs,
+) -> Result<WsConnectResponse, String> {
+    reserve(&state.sessions, &args.session_id).await?;
+    let result = open(&app_handle, &http_state, &state.sessions, &redaction, &args).await;
+    if result.is_err() {
+        let mut sessions = state.sessions.lock().await;
+        if matches!(sessions.get(&args.session_id), Some(None)) {
+            sessions.remove(&args.session_id);
+        }
+    }
+    result
+}
+
+async fn open(
+    app_handle: &tauri::AppHandle,
+    http_state: &crate::HttpRequestState,
+    sessions: &WsSessions,
+    redaction: &crate::RedactionState,
+    args: &WsConnectArgs,
+) -> Result<WsConnectResponse, String> {
+    let p_name = args
+        .project_name
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+    let (client, jar) = project_client(http_state, &p_name, &ClientOptions::default()).await?;
+
+    // Go through the regular request args so auth and the raw/cURL views behave as for HTTP.
+    let mut handshake = HttpRequestArgs {
+        method: "GET".to_string(),
+        url: args.url.clone(),
+        headers: args.headers.clone(),
+        project_name: Some(p_name.clone()),
+        auth: args.auth.clone(),
+        ..Default::default()
+    };
+    crate::auth::apply(
+        app_handle,
+        &client,
+        &http_state.tokens,
+        &p_name,
+        &mut handshake,
+    )
+    .await?;
+    let redactor = active_redactor(redaction, args.reveal_secrets);
+    let (request_raw, request_curl) = generate_request_data(&handshake, &jar, redactor.as_ref());
+
+    let cookie_url = cookie_url(&handshake.url)?;
+    let mut request = handshake
+        .url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| format!("Invalid WebSocket request: {}", e))?;
+    for pair in &handshake.headers {
+        if pair.len() == 2 {
+            let name = HeaderName::from_bytes(pair[0].as_bytes())
+                .map_err(|e| format!("Invalid header name {}: {}", pair[0], e))?;
+            let value = HeaderValue::from_str(&pair[1])
+                .map_err(|e| format!("Invalid header value for {}: {}", pair[0], e))?;
+            request.headers_mut().append(name, value);
+        }
+    }
+    if !has_header(&handshake, "cookie") {
+        if let Some(cookies) = jar.cookies(&cookie_url) {
+            request
+                .headers_mut()
+                .insert(reqwest::header::COOKIE, cookies);
+        }
+    }
+
+    // tungstenite resolves the host itself: DNS overrides do not apply to WebSockets
+    let (socket, response) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| {
+            let msg = format!("WebSocket connection failed: {}", e);
+            crate::rust_error!(app_handle, "{}", msg);
+            msg
+        })?;
+
+    let mut set_cookies = response
+        .headers()
+        .get_all(reqwest::header::SET_COOKIE)
+        .iter();
+    jar.set_cookies(&mut set_cookies, &cookie_url);
+
+    let mut headers: Vec<Vec<String>> = Vec::new();
+    for (key, value) in response.headers() {
+        if let Ok(v) = value.to_str() {
+            headers.push(vec![key.to_string(), v.to_string()]);
+        }
+    }
+
+    let (sender, mut outgoing) = mpsc::unbounded_channel::<Message>();
+    let transcript = Arc::new(Mutex::new(Vec::new()));
+    let task_transcript = Arc::clone(&transcript);
+    let session_id = args.session_id.clone();
+    let task_app = app_handle.clone();
+    let task_sessions = Arc::clone(sessions);
+
+    let task = tokio::spawn(async move {
+        let started = Instant::now();
+        let (mut sink, mut stream) = socket.split();
+        let mut closed = WsClosedPayload {
+            session_id: session_id.clone(),
+            code: None,
+            reason: String::new(),
+            error: None,
+            transcript: Vec::new(),
+        };
+
+        // Keep reading after the last sender is dropped so the server's close reply is recorded.
+        let mut sending = true;
+        loop {
+            let (direction, message) = tokio::select! {
+                message = outgoing.recv(), if sending => {
+                    let Some(message) = message else {
+                        sending = false;
+                        continue;
+                    };
+                    if let Err(e) = sink.send(message.clone()).await {
+                        closed.error = Some(format!("Send failed: {}", e));
+                        break;
+                    }
+                    ("sent", message)
+                }
+                message = stream.next() => match message {
+                    Some(Ok(message)) => ("received", message),
+                    Some(Err(e)) => {
+                        closed.error = Some(e.to_string());
+                        break;
+                    }
+                    None => break,
+                }
+            };
+
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+            let Some(entry) = WsMessage::from_message(direction, &message, elapsed_ms) else {
+                continue;
+            };
+            if direction == "received" && entry.kind == "close" {
+                closed.code = entry.close_code;
+                closed.reason = entry.data.clone();
+            }
+            task_transcript.lock().await.push(entry.clone());
+            let _ = task_app.emit(
+                "ws-message",
+                WsMessagePayload {
+                    session_id: session_id.clone(),
+                    message: entry,
+                },
+            );
+        }
+
+        if let Some(error) = &closed.error {
+            crate::rust_warn!(
+                &task_app,
+                "WebSocket {} closed with error: {}",
+                session_id,
+                error
+            );
+        }
+        release(&task_sessions, &session_id, &task_transcript).await;
+        closed.transcript = task_transcript.lock().await.clone();
+        let _ = task_app.emit("ws-closed", closed);
+    });
+
+    // Fill the reservation, unless the connection already ended and released it
+    if let Some(slot @ None) = sessions.lock().await.get_mut(&args.session_id) {
+        *slot = Some(WsSession {
+            sender,
+            transcript,
+            task,
+        });
+    }
+    crate::rust_info!(
+        app_handle,
+        "WebSocket {} connected to {}",
+        args.session_id,
+        args.url
+    );
+
+    Ok(WsConnectResponse {
+        status: response.status().as_u16(),
+        headers,
+        request_raw,
+        request_curl,
+    })
+}
+
+async fn queue(
+    state: &crate::WebSocketState,
+    session_id: &str,
+    message: Message,
+) -> Result<(), String> {
+    let sessions = state.sessions.lock().await;
+    let session = sessions
+        .get(session_id)
+        .and_then(Option::as_ref)
+        .ok_or_else(|| format!("No open WebSocket session {}", session_id))?;
+    session
+        .sender
+        .send(message)
+        .map_err(|_| format!("WebSocket session {} is closed", session_id))
+}
+
+/// Send a text frame, or a binary one when `binary` is set (`data` is then base64).
+#[command]
+pub async fn ws_send(
+    state: tauri::State<'_, crate::WebSocketState>,
+    session_id: String,
+    data: String,
+    binary: Option<bool>,
+) -> Result<(), String> {
+    let message = if binary.unwrap_or(false) {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map_err(|e| format!("Binary payload must be base64: {}", e))?;
+        Message::Binary(bytes)
+    } else {
+        Message::Text(data)
+    };
+    queue(&state, &session_id, message).await
+}
+
+#[command]
+pub async fn ws_ping(
+    state: tauri::State<'_, crate::WebSocketState>,
+    session_id: String,
+    payload: Option<String>,
+) -> Result<(), String> {
+    let payload = payload.unwrap_or_default().into_bytes();
+    queue(&state, &session_id, Message::Ping(payload)).await
+}
+
+/// Close the session (default code 1000) and return its transcript. Sessions closed by the
+/// server are already gone: their transcript comes with the `ws-closed` event.
+#[command]
+pub async fn ws_close(
+    state: tauri::State<'_, crate::WebSocketState>,
+    session_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+) -> Result<Vec<WsMessage>, String> {
+    let session = {
+        let mut sessions = state.sessions.lock().await;
+        match sessions.get(&session_id) {
+            Some(Some(_)) => sessions.remove(&session_id).flatten(),
+            Some(None) => {
+                return Err(format!(
+                    "WebSocket session {} is still connecting",
+                    session_id
+                ))
+            }
+            None => None,
+        }
+    }
+    .ok_or_else(|| format!("No open WebSocket session {}", session_id))?;
+
+    let frame = CloseFrame {
+        code: CloseCode::from(code.unwrap_or(1000)),
+        reason: reason.unwrap_or_default().into(),
+    };
+    let _ = session.sender.send(Message::Close(Some(frame)));
+    drop(session.sender);
+
+    let mut task = session.task;
+    if tokio::time::timeout(CLOSE_TIMEOUT, &mut task)
+        .await
+        .is_err()
+    {
+        task.abort();
+    }
+
+    let transcript = session.transcript.lock().await.clone();
+    Ok(transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(transcript: &Arc<Mutex<Vec<WsMessage>>>) -> WsSession {
+        let (sender, _) = mpsc::unbounded_channel();
+        WsSession {
+            sender,
+            transcript: Arc::clone(transcript),
+            task: tokio::spawn(async {}),
+        }
+    }
+
+    #[tokio::test]
+    async fn reserve_refuses_an_id_being_connected() {
+        let sessions: WsSessions = Arc::default();
+        reserve(&sessions, "a").await.unwrap();
+        assert!(reserve(&sessions, "a").await.is_err());
+        assert!(reserve(&sessions, "b").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn release_only_removes_its_own_session() {
+        let sessions: WsSessions = Arc::default();
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        let reused = Arc::new(Mutex::new(Vec::new()));
+
+        // The id was closed and opened again: the old connection must leave the new one alone
+        sessions
+            .lock()
+            .await
+            .insert("a".to_string(), Some(session(&reused)));
+        release(&sessions, "a", &ended).await;
+        assert!(sessions.lock().await.contains_key("a"));
+
+        release(&sessions, "a", &reused).await;
+        assert!(!sessions.lock().await.contains_key("a"));
+
+        // A connection ending before it was registered releases its reservation
+        reserve(&sessions, "b").await.unwrap();
+        release(&sessions, "b", &ended).await;
+        assert!(!sessions.lock().await.contains_key("b"));
+    }
+}
@@ -1,8 +1,63 @@
-import { Signal } from "@preact/signals";
-import { AuthConfig, AuthType, navigateToItem } from "../store";
+import { Signal, useSignal } from "@preact/signals";
+import { invoke } from "@tauri-apps/api/core";
+import { AuthConfig, AuthType, OAuth2Config, AwsSigV4Config, ApiKeyConfig, HmacConfig, navigateToItem, requests, executions, activeProjectName, addLog } from "../store";
+import { substituteAuthFields } from "../utils/execution";
 import { OverrideIndicator } from "./OverrideIndicator";
 import { VariableInput } from "./VariableInput";
 
+const AUTH_TYPE_LABELS: Record<string, string> = {
+    basic: 'Basic Auth',
+    bearer: 'Bearer Token',
+    oauth2: 'OAuth 2.0',
+    digest: 'Digest Auth',
+    aws_sigv4: 'AWS Signature v4',
+    api_key: 'API Key',
+    hmac: 'HMAC Signature'
+};
+
+const labelStyle = { fontSize: '0.8rem', color: 'var(--text-muted)' };
+
+const selectStyle = (isReadOnly: boolean) => ({
+    padding: '8px',
+    backgroundColor: isReadOnly ? 'transparent' : 'var(--bg-input)',
+    border: isReadOnly ? '1px solid transparent' : '1px solid var(--border-color)',
+    borderRadius: 'var(--radius-sm)',
+    color: 'var(--text-primary)',
+    outline: 'none',
+    cursor: isReadOnly ? 'default' : 'pointer'
+});
+
+interface AuthFieldProps {
+    label: string;
+    value: string;
+    onInput: (value: string) => void;
+    placeholder?: string;
+    type?: 'text' | 'password';
+    isReadOnly: boolean;
+    isOverridden?: boolean;
+    parentId?: string | null;
+}
+
+function AuthField({ label, value, onInput, placeholder, type, isReadOnly, isOverridden, parentId }: AuthFieldProps) {
+    return (
+        <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+            <label style={labelStyle}>{label}</label>
+            <div style={{ display: 'flex', gap: '4px', alignItems: 'center' }}>
+                {isOverridden && <OverrideIndicator />}
+                <VariableInput
+                    type={type}
+                    placeholder={placeholder ?? label}
+                    value={value}
+                    readOnly={isReadOnly}
+                    onInput={onInput}
+                    parentId={parentId}
+                    style={{ flex: 1 }}
+                />
+            </div>
+        </div>
+    );
+}
+
 interface AuthEditorProps {
     auth: Signal<AuthConfig | undefined>;
     onChange: (newAuth: AuthConfig) => void;
@@ -34,6 +89,71 @@ export function AuthEditor({ auth, onChange, showInherit = true, inheritedAuth,
         });
     };
 
+    const updateDigest = (field: 'username' | 'password', value: string) => {
+        onChange({
+            ...currentAuth,
+            digest: { ...(currentAuth.digest || { username: '', password: '' }), [field]: value }
+        });
+    };
+
+    const updateOAuth2 = (patch: Partial<OAuth2Config>) => {
+        onChange({
+            ...currentAuth,
+            oauth2: { ...(currentAuth.oauth2 || { grant_type: 'client_credentials', token_url: '', client_id: '' }), ...patch }
+        });
+    };
+
+    const updateSigV4 = (patch: Partial<AwsSigV4Config>) => {
+        onChange({
+            ...currentAuth,
+            aws_sigv4: { ...(currentAuth.aws_sigv4 || { access_key_id: '', secret_access_key: '', region: '', service: '' }), ...patch }
+        });
+    };
+
+    const updateApiKey = (patch: Partial<ApiKeyConfig>) => {
+        onChange({
+            ...currentAuth,
+            api_key: { ...(currentAuth.api_key || { key: '', value: '' }), ...patch }
+        });
+    };
+
+    const updateHmac = (patch: Partial<HmacConfig>) => {
+        onChange({
+            ...currentAuth,
+            hmac: { ...(currentAuth.hmac || { secret: '' }), ...patch }
+        });
+    };
+
+    // Result of the last "Get Token", shown under the OAuth 2.0 fields
+    const tokenStatus = useSignal<string | null>(null);
+
+    const handleGetToken = async () => {
+        if (!currentAuth.oauth2) return;
+        // Variables resolve against the request the editor belongs to, when there is one
+        const requestId = requests.peek().find(r => r.id === parentId)?.id
+            ?? executions.peek().find(e => e.id === parentId)?.requestId;
+        const config = requestId ? substituteAuthFields(currentAuth.oauth2, requestId) : currentAuth.oauth2;
+        tokenStatus.value = 'Requesting token...';
+        try {
+            const token = await invoke<{ token_type: string, expires_at: number | null }>('oauth2_get_token', {
+                projectName: activeProjectName.peek(),
+                config
+            });
+            const expiry = token.expires_at ? `, expires ${new Date(token.expires_at * 1000).toLocaleTimeString()}` : '';
+            tokenStatus.value = `Got a ${token.token_type} token${expiry}`;
+        } catch (e) {
+            tokenStatus.value = `Error: ${e}`;
+            addLog('error', `OAuth 2.0 token request failed: ${e}`, 'Auth');
+        }
+    };
+
+    const handleClearTokens = async () => {
+        await invoke('oauth2_clear_tokens', { projectName: activeProjectName.peek() });
+        tokenStatus.value = 'Cached tokens cleared';
+    };
+
+    const fieldProps = { isReadOnly, isOverridden, parentId };
+
     return (
         <div style={{ display: 'flex', flexDirection: 'column', gap: '16px', padding: '16px' }}>
             <div style={{ display: 'flex', flexDirection: 'column', gap: '8px' }}>
@@ -42,20 +162,13 @@ export function AuthEditor({ auth, onChange, showInherit = true, inheritedAuth,
                     value={currentAuth.type}
                     onChange={(e) => handleTypeChange(e.currentTarget.value as AuthType)}
                     disabled={isReadOnly}
-                    style={{
-                        padding: '8px',
-                        backgroundColor: isReadOnly ? 'transparent' : 'var(--bg-input)',
-                        border: isReadOnly ? '1px solid transparent' : '1px solid var(--border-color)',
-                        borderRadius: 'var(--radius-sm)',
-                        color: 'var(--text-primary)',
-                        outline: 'none',
-                        cursor: isReadOnly ? 'default' : 'pointer'
-                    }}
+                    style={selectStyle(isReadOnly)}
                 >
                     {showInherit && <option value="inherit">Inherit from Parent</option>}
                     <option value="none">No Auth</option>
-                    <option value="basic">Basic Auth</option>
-                    <option value="bearer">Bearer Token</option>
+                    {Object.entries(AUTH_TYPE_LABELS).map(([type, label]) => (
+                        <option key={type} value={type}>{label}</option>
+                    ))}
                 </select>
             </div>
 
@@ -110,12 +223,161 @@ export function AuthEditor({ auth, onChange, showInherit = true, inheritedAuth,
                 </div>
             )}
 
+            {currentAuth.type === 'digest' && (
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                    <AuthField label="Username" value={currentAuth.digest?.username || ''} onInput={(v) => updateDigest('username', v)} {...fieldProps} />
+                    <AuthField label="Password" type="password" value={currentAuth.digest?.password || ''} onInput={(v) => updateDigest('password', v)} {...fieldProps} />
+                </div>
+            )}
+
+            {currentAuth.type === 'oauth2' && (() => {
+                const oauth2 = currentAuth.oauth2 || { grant_type: 'client_credentials', token_url: '', client_id: '' };
+                return (
+                    <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                        <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                            <label style={labelStyle}>Grant Type</label>
+                            <select
+                                value={oauth2.grant_type}
+                                onChange={(e) => updateOAuth2({ grant_type: e.currentTarget.value as OAuth2Config['grant_type'] })}
+                                disabled={isReadOnly}
+                                style={selectStyle(isReadOnly)}
+                            >
+                                <option value="client_credentials">Client Credentials</option>
+                                <option value="password">Password</option>
+                                <option value="authorization_code">Authorization Code (PKCE)</option>
+                                <option value="device_code">Device Code</option>
+                                <option value="refresh_token">Refresh Token</option>
+                            </select>
+                        </div>
+                        <AuthField label="Token URL" value={oauth2.token_url} onInput={(v) => updateOAuth2({ token_url: v })} placeholder="https://auth.example.com/oauth/token" {...fieldProps} />
+                        {oauth2.grant_type === 'authorization_code' && (
+                            <AuthField label="Authorization URL" value={oauth2.auth_url || ''} onInput={(v) => updateOAuth2({ auth_url: v })} placeholder="https://auth.example.com/authorize" {...fieldProps} />
+                        )}
+                        {oauth2.grant_type === 'device_code' && (
+                            <AuthField label="Device Authorization URL" value={oauth2.device_auth_url || ''} onInput={(v) => updateOAuth2({ device_auth_url: v })} placeholder="https://auth.example.com/device/code" {...fieldProps} />
+                        )}
+                        <AuthField label="Client ID" value={oauth2.client_id} onInput={(v) => updateOAuth2({ client_id: v })} {...fieldProps} />
+                        <AuthField label="Client Secret" type="password" value={oauth2.client_secret || ''} onInput={(v) => updateOAuth2({ client_secret: v })} placeholder="Empty for public clients" {...fieldProps} />
+                        <AuthField label="Scope" value={oauth2.scope || ''} onInput={(v) => updateOAuth2({ scope: v })} placeholder="e.g. read write" {...fieldProps} />
+                        {oauth2.grant_type === 'password' && (
+                            <>
+                                <AuthField label="Username" value={oauth2.username || ''} onInput={(v) => updateOAuth2({ username: v })} {...fieldProps} />
+                                <AuthField label="Password" type="password" value={oauth2.password || ''} onInput={(v) => updateOAuth2({ password: v })} {...fieldProps} />
+                            </>
+                        )}
+                        {oauth2.grant_type === 'refresh_token' && (
+                            <AuthField label="Refresh Token" value={oauth2.refresh_token || ''} onInput={(v) => updateOAuth2({ refresh_token: v })} {...fieldProps} />
+                        )}
+                        <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                            <label style={labelStyle}>Client Authentication</label>
+                            <select
+                                value={oauth2.client_auth || 'body'}
+                                onChange={(e) => updateOAuth2({ client_auth: e.currentTarget.value as 'body' | 'basic' })}
+                                disabled={isReadOnly}
+                                style={selectStyle(isReadOnly)}
+                            >
+                                <option value="body">Credentials in body</option>
+                                <option value="basic">Basic Auth header</option>
+                            </select>
+                        </div>
+                        {!isReadOnly && (
+                            <div style={{ display: 'flex', gap: '8px', alignItems: 'center' }}>
+                                <button onClick={handleGetToken} style={{ padding: '6px 12px', backgroundColor: 'var(--accent-primary)', color: 'white', border: 'none', borderRadius: 'var(--radius-sm)', cursor: 'pointer' }}>
+                                    Get Token
+                                </button>
+                                <button onClick={handleClearTokens} style={{ padding: '6px 12px', backgroundColor: 'transparent', color: 'var(--text-primary)', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', cursor: 'pointer' }}>
+                                    Clear Cached Tokens
+                                </button>
+                                {tokenStatus.value && <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{tokenStatus.value}</span>}
+                            </div>
+                        )}
+                    </div>
+                );
+            })()}
+
+            {currentAuth.type === 'aws_sigv4' && (
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                    <AuthField label="Access Key ID" value={currentAuth.aws_sigv4?.access_key_id || ''} onInput={(v) => updateSigV4({ access_key_id: v })} {...fieldProps} />
+                    <AuthField label="Secret Access Key" type="password" value={currentAuth.aws_sigv4?.secret_access_key || ''} onInput={(v) => updateSigV4({ secret_access_key: v })} {...fieldProps} />
+                    <AuthField label="Session Token" value={currentAuth.aws_sigv4?.session_token || ''} onInput={(v) => updateSigV4({ session_token: v || undefined })} placeholder="Only for temporary credentials" {...fieldProps} />
+                    <AuthField label="Region" value={currentAuth.aws_sigv4?.region || ''} onInput={(v) => updateSigV4({ region: v })} placeholder="e.g. eu-west-1" {...fieldProps} />
+                    <AuthField label="Service" value={currentAuth.aws_sigv4?.service || ''} onInput={(v) => updateSigV4({ service: v })} placeholder="e.g. execute-api, s3" {...fieldProps} />
+                    <label style={{ ...labelStyle, display: 'flex', alignItems: 'center', gap: '6px' }}>
+                        <input
+                            type="checkbox"
+                            checked={!!currentAuth.aws_sigv4?.unsigned_payload}
+                            disabled={isReadOnly}
+                            onChange={(e) => updateSigV4({ unsigned_payload: e.currentTarget.checked })}
+                        />
+                        Unsigned payload
+                    </label>
+                </div>
+            )}
+
+            {currentAuth.type === 'api_key' && (
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                    <AuthField label="Key" value={currentAuth.api_key?.key || ''} onInput={(v) => updateApiKey({ key: v })} placeholder="e.g. X-API-Key" {...fieldProps} />
+                    <AuthField label="Value" value={currentAuth.api_key?.value || ''} onInput={(v) => updateApiKey({ value: v })} {...fieldProps} />
+                    <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                        <label style={labelStyle}>Add To</label>
+                        <select
+                            value={currentAuth.api_key?.location || 'header'}
+                            onChange={(e) => updateApiKey({ location: e.currentTarget.value as 'header' | 'query' })}
+                            disabled={isReadOnly}
+                            style={selectStyle(isReadOnly)}
+                        >
+                            <option value="header">Header</option>
+                            <option value="query">Query Params</option>
+                        </select>
+                    </div>
+                </div>
+            )}
+
+            {currentAuth.type === 'hmac' && (
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                    <AuthField label="Secret" type="password" value={currentAuth.hmac?.secret || ''} onInput={(v) => updateHmac({ secret: v })} {...fieldProps} />
+                    <AuthField label="Key ID" value={currentAuth.hmac?.key_id || ''} onInput={(v) => updateHmac({ key_id: v || undefined })} placeholder="Available as {key_id} in the header value" {...fieldProps} />
+                    <div style={{ display: 'flex', gap: '12px' }}>
+                        <div style={{ flex: 1, display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                            <label style={labelStyle}>Algorithm</label>
+                            <select
+                                value={currentAuth.hmac?.algorithm || 'sha256'}
+                                onChange={(e) => updateHmac({ algorithm: e.currentTarget.value as HmacConfig['algorithm'] })}
+                                disabled={isReadOnly}
+                                style={selectStyle(isReadOnly)}
+                            >
+                                <option value="sha1">HMAC-SHA1</option>
+                                <option value="sha256">HMAC-SHA256</option>
+                                <option value="sha512">HMAC-SHA512</option>
+                            </select>
+                        </div>
+                        <div style={{ flex: 1, display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                            <label style={labelStyle}>Encoding</label>
+                            <select
+                                value={currentAuth.hmac?.encoding || 'hex'}
+                                onChange={(e) => updateHmac({ encoding: e.currentTarget.value as HmacConfig['encoding'] })}
+                                disabled={isReadOnly}
+                                style={selectStyle(isReadOnly)}
+                            >
+                                <option value="hex">Hex</option>
+                                <option value="base64">Base64</option>
+                            </select>
+                        </div>
+                    </div>
+                    <AuthField label="String to Sign" value={currentAuth.hmac?.template || ''} onInput={(v) => updateHmac({ template: v || undefined })} placeholder="Default: {method}\n{path}\n{timestamp}\n{body}" {...fieldProps} />
+                    <AuthField label="Header" value={currentAuth.hmac?.header || ''} onInput={(v) => updateHmac({ header: v || undefined })} placeholder="Default: X-Signature" {...fieldProps} />
+                    <AuthField label="Header Value" value={currentAuth.hmac?.header_value || ''} onInput={(v) => updateHmac({ header_value: v || undefined })} placeholder="Default: {signature}" {...fieldProps} />
+                    <AuthField label="Timestamp Header" value={currentAuth.hmac?.timestamp_header || ''} onInput={(v) => updateHmac({ timestamp_header: v || undefined })} placeholder="e.g. X-Timestamp" {...fieldProps} />
+                    <AuthField label="Nonce Header" value={currentAuth.hmac?.nonce_header || ''} onInput={(v) => updateHmac({ nonce_header: v || undefined })} placeholder="e.g. X-Nonce" {...fieldProps} />
+                </div>
+            )}
+
             {currentAuth.type === 'inherit' && (
                 <div style={{ display: 'flex', flexDirection: 'column', gap: '8px', padding: '12px', backgroundColor: 'var(--bg-input)', borderRadius: 'var(--radius-sm)', border: '1px dashed var(--border-color)' }}>
                     {inheritedAuth ? (
                         <>
                             <div style={{ fontSize: '0.85rem', color: 'var(--text-muted)' }}>
-                                Inheriting <strong>{inheritedAuth.config.type === 'basic' ? 'Basic Auth' : inheritedAuth.config.type === 'bearer' ? 'Bearer Token' : inheritedAuth.config.type}</strong> from{' '}
+                                Inheriting <strong>{AUTH_TYPE_LABELS[inheritedAuth.config.type] ?? inheritedAuth.config.type}</strong> from{' '}
                                 <span
                                     style={{
                                         fontStyle: 'italic',
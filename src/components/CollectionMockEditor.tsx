@@ -2,6 +2,7 @@ import { useState, useMemo } from 'preact/hooks';
 import { Play, Square, Search, ChevronDown, ChevronRight, Plus, Trash2 } from 'lucide-preact';
 import { collections, requests, activeTabId, unsavedItemIds, MockResponse } from '../store';
 import { invoke } from '@tauri-apps/api/core';
+import { toBackendRules } from '../utils/mock';
 
 export function CollectionMockEditor() {
     const collectionId = activeTabId.value;
@@ -26,7 +27,7 @@ export function CollectionMockEditor() {
     );
 
     const isRunning = collection.mockConfig?.enabled || false;
-    const port = collection.mockConfig?.port || 3000;
+    const port = collection.mockConfig?.port ?? 3000;
 
     const updateMockConfig = (updates: any) => {
         collections.value = collections.value.map(c =>
@@ -73,19 +74,23 @@ export function CollectionMockEditor() {
                             response: {
                                 status_code: r.mockResponse?.statusCode || 200,
                                 headers: (r.mockResponse?.headers || []).flatMap(h => h.values.map(v => [h.key, v])),
-                                body: r.mockResponse?.body || ''
-                            }
+                                body: r.mockResponse?.body || '',
+                                templated: r.mockResponse?.templated ?? false
+                            },
+                            rules: toBackendRules(r.mockResponse?.rules)
                         };
                     });
 
-                await invoke('start_mock_server', {
+                const boundPort = await invoke<number>('start_mock_server', {
                     args: {
                         collection_id: collectionId,
+                        name: collection.name,
+                        bind_address: collection.mockConfig?.bindAddress || null,
                         port: port,
                         requests: mockRequests
                     }
                 });
-                updateMockConfig({ enabled: true });
+                updateMockConfig({ enabled: true, runningPort: boundPort });
             }
         } catch (e) {
             alert("Mock Server Error: " + e);
@@ -101,7 +106,7 @@ export function CollectionMockEditor() {
                         <h2 style={{ margin: 0, fontSize: '1.2rem' }}>Mock Manager: {collection.name}</h2>
                         <div style={{ display: 'flex', alignItems: 'center', gap: '8px', marginTop: '4px' }}>
                             <div style={{ width: '8px', height: '8px', borderRadius: '50%', backgroundColor: isRunning ? 'var(--success)' : 'var(--text-muted)' }} />
-                            <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{isRunning ? `Running on port ${port}` : 'Server Stopped'}</span>
+                            <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{isRunning ? `Running on port ${collection.mockConfig?.runningPort ?? port}` : 'Server Stopped'}</span>
                         </div>
                     </div>
 
@@ -110,7 +115,10 @@ export function CollectionMockEditor() {
                         <input
                             type="number"
                             value={port}
-                            onInput={(e) => updateMockConfig({ port: parseInt(e.currentTarget.value) || 3000 })}
+                            onInput={(e) => {
+                                const value = parseInt(e.currentTarget.value);
+                                updateMockConfig({ port: Number.isNaN(value) ? 3000 : value });
+                            }}
                             disabled={isRunning}
                             style={{ width: '80px', padding: '4px 8px', backgroundColor: 'var(--bg-input)', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', color: 'var(--text-primary)' }}
                         />
@@ -190,6 +198,14 @@ export function CollectionMockEditor() {
                                             style={{ width: '100px', padding: '4px 8px', backgroundColor: 'var(--bg-input)', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', color: 'var(--text-primary)' }}
                                         />
                                     </div>
+                                    <label style={{ display: 'flex', alignItems: 'center', gap: '6px', fontSize: '0.8rem', color: 'var(--text-secondary)', alignSelf: 'flex-end' }} title="Render {{request.*}}, {{uuid}}, {{now}}... in the body and header values">
+                                        <input
+                                            type="checkbox"
+                                            checked={req.mockResponse?.templated ?? false}
+                                            onChange={(e) => updateRequestMock(req.id, { templated: e.currentTarget.checked })}
+                                        />
+                                        Templated
+                                    </label>
                                 </div>
 
                                 <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
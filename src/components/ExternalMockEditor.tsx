@@ -3,6 +3,8 @@ import { Play, Square, Search, ChevronDown, ChevronRight, Plus, Trash2, FileJson
 import { externalMocks, activeTabId, saveExternalMockToDisk, ExternalMockEndpoint, activeExternalMockId, importModal, TableRow } from '../store';
 import { invoke } from '@tauri-apps/api/core';
 import { MethodSelect } from './MethodSelect';
+import { MockJournalPanel } from './MockJournalPanel';
+import { toBackendFaults, toBackendResources, toBackendRules, toBackendStateful, toBackendTls } from '../utils/mock';
 
 export function ExternalMockEditor() {
     const mockId = activeTabId.value || activeExternalMockId.value;
@@ -34,7 +36,7 @@ export function ExternalMockEditor() {
         const newEndpoints = [...mock.endpoints];
 
         // Check if updates belong to response or the endpoint itself
-        if ('statusCode' in updates || 'body' in updates || 'headers' in updates) {
+        if ('statusCode' in updates || 'body' in updates || 'headers' in updates || 'templated' in updates) {
             newEndpoints[index] = {
                 ...newEndpoints[index],
                 response: { ...newEndpoints[index].response, ...updates as any }
@@ -62,6 +64,13 @@ export function ExternalMockEditor() {
         setExpandedIndexes(new Set([...expandedIndexes, newEndpoints.length - 1]));
     };
 
+    // Adds the endpoint built from an unmatched journal request and opens it for editing
+    const promoteEndpoint = (endpoint: ExternalMockEndpoint) => {
+        const newEndpoints = [...mock.endpoints, endpoint];
+        updateMock({ endpoints: newEndpoints });
+        setExpandedIndexes(new Set([...expandedIndexes, newEndpoints.length - 1]));
+    };
+
     const removeEndpoint = (index: number) => {
         if (!confirm("Remove this endpoint?")) return;
         const newEndpoints = mock.endpoints.filter((_, i) => i !== index);
@@ -86,18 +95,28 @@ export function ExternalMockEditor() {
                     response: {
                         status_code: ep.response.statusCode,
                         headers: ep.response.headers.flatMap(h => h.values.map(v => [h.key, v])),
-                        body: ep.response.body
-                    }
+                        body: ep.response.body,
+                        templated: ep.response.templated ?? false
+                    },
+                    rules: toBackendRules(ep.rules),
+                    ...toBackendStateful(ep),
+                    faults: toBackendFaults(ep.faults)
                 }));
 
-                await invoke('start_mock_server', {
+                const boundPort = await invoke<number>('start_mock_server', {
                     args: {
                         collection_id: mockId, // Reusing collection_id for ID
+                        name: mock.name,
+                        bind_address: mock.bindAddress || null,
                         port: port,
-                        requests: mockRequests
+                        requests: mockRequests,
+                        resources: toBackendResources(mock.resources),
+                        faults: toBackendFaults(mock.faults),
+                        proxy: mock.proxy?.upstream ? { upstream: mock.proxy.upstream, record: mock.proxy.record ?? true } : null,
+                        tls: toBackendTls(mock.tls)
                     }
                 });
-                updateMock({ serverStatus: 'running' });
+                updateMock({ serverStatus: 'running', runningPort: boundPort });
             }
         } catch (e) {
             alert("Mock Server Error: " + e);
@@ -114,7 +133,7 @@ export function ExternalMockEditor() {
                         <h2 style={{ margin: 0, fontSize: '1.2rem' }}>External Mock: {mock.name}</h2>
                         <div style={{ display: 'flex', alignItems: 'center', gap: '8px', marginTop: '4px' }}>
                             <div style={{ width: '8px', height: '8px', borderRadius: '50%', backgroundColor: isRunning ? 'var(--success)' : 'var(--text-muted)' }} />
-                            <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{isRunning ? `Running on port ${port}` : 'Server Stopped'}</span>
+                            <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{isRunning ? `Running on port ${mock.runningPort ?? port}` : 'Server Stopped'}</span>
                         </div>
                     </div>
 
@@ -123,7 +142,10 @@ export function ExternalMockEditor() {
                         <input
                             type="number"
                             value={port}
-                            onInput={(e) => updateMock({ port: parseInt(e.currentTarget.value) || 3000 })}
+                            onInput={(e) => {
+                                const value = parseInt(e.currentTarget.value);
+                                updateMock({ port: Number.isNaN(value) ? 3000 : value });
+                            }}
                             disabled={isRunning}
                             style={{ width: '80px', padding: '4px 8px', backgroundColor: 'var(--bg-input)', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', color: 'var(--text-primary)' }}
                         />
@@ -264,6 +286,14 @@ export function ExternalMockEditor() {
                                             style={{ width: '100px', padding: '4px 8px', backgroundColor: 'var(--bg-input)', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', color: 'var(--text-primary)' }}
                                         />
                                     </div>
+                                    <label style={{ display: 'flex', alignItems: 'center', gap: '6px', fontSize: '0.8rem', color: 'var(--text-secondary)', alignSelf: 'flex-end' }} title="Render {{request.*}}, {{uuid}}, {{now}}... in the body and header values">
+                                        <input
+                                            type="checkbox"
+                                            checked={ep.response.templated ?? false}
+                                            onChange={(e) => updateEndpoint(index, { templated: e.currentTarget.checked })}
+                                        />
+                                        Templated
+                                    </label>
                                 </div>
 
                                 <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
@@ -360,6 +390,8 @@ export function ExternalMockEditor() {
                     </div>
                 )}
             </div>
+
+            {isRunning && <MockJournalPanel mockId={mockId!} onPromote={promoteEndpoint} />}
         </div>
     );
 }
@@ -6,6 +6,7 @@ import { Modal } from './Modal';
 import { EnvironmentManager } from './EnvironmentManager';
 import { ConsolePanel } from './ConsolePanel';
 import { AboutModal } from './AboutModal';
+import { BatchRunModal } from './tools/BatchRunModal';
 import { Settings, Terminal } from 'lucide-preact';
 import { useEffect } from 'preact/hooks';
 import { listen } from '@tauri-apps/api/event';
@@ -276,6 +277,7 @@ export function MainLayout({ children }: LayoutProps) {
                 onClose={() => isEnvManagerOpen.value = false}
             />
             <AboutModal />
+            <BatchRunModal />
 
             {/* Global Confirmation Modal - Rendered last with higher z-index to overlay other modals */}
             <Modal
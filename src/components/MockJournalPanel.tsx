@@ -0,0 +1,107 @@
+import { useEffect, useState } from 'preact/hooks';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { ArrowUpRight, RefreshCw, Trash2 } from 'lucide-preact';
+import { ExternalMockEndpoint, MockJournalEntry } from '../store';
+import { fromBackendEndpoint } from '../utils/mock';
+
+// Default journal size of the backend
+const MAX_ENTRIES = 500;
+
+interface MockJournalPanelProps {
+    mockId: string;
+    onPromote: (endpoint: ExternalMockEndpoint) => void;
+}
+
+export function MockJournalPanel({ mockId, onPromote }: MockJournalPanelProps) {
+    const [entries, setEntries] = useState<MockJournalEntry[]>([]);
+    const [unmatchedOnly, setUnmatchedOnly] = useState(false);
+
+    const load = async () => {
+        try {
+            setEntries(await invoke<MockJournalEntry[]>('get_mock_journal', { collectionId: mockId, limit: MAX_ENTRIES }));
+        } catch (e) {
+            console.error("Failed to load mock journal", e);
+        }
+    };
+
+    useEffect(() => {
+        load();
+        const unlisten = listen<MockJournalEntry>('mock-request', (e) => {
+            if (e.payload.collection_id !== mockId) return;
+            setEntries(prev => [...prev.slice(-(MAX_ENTRIES - 1)), e.payload]);
+        });
+        return () => { unlisten.then(f => f()); };
+    }, [mockId]);
+
+    const handleClear = async () => {
+        try {
+            await invoke('clear_mock_journal', { collectionId: mockId });
+            setEntries([]);
+        } catch (e) {
+            alert("Mock Server Error: " + e);
+        }
+    };
+
+    const handlePromote = async (entry: MockJournalEntry) => {
+        try {
+            const def = await invoke<Parameters<typeof fromBackendEndpoint>[0]>('promote_mock_request', { collectionId: mockId, entryId: entry.id });
+            onPromote(fromBackendEndpoint(def));
+        } catch (e) {
+            alert("Mock Server Error: " + e);
+        }
+    };
+
+    const visible = (unmatchedOnly ? entries.filter(e => e.matched === null) : entries).slice().reverse();
+
+    return (
+        <div style={{ display: 'flex', flexDirection: 'column', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-md)', backgroundColor: 'var(--bg-sidebar)' }}>
+            <div style={{ display: 'flex', alignItems: 'center', gap: '12px', padding: '10px 12px', borderBottom: '1px solid var(--border-color)' }}>
+                <h3 style={{ margin: 0, fontSize: '0.95rem', flex: 1 }}>Request Journal ({entries.length})</h3>
+                <label style={{ display: 'flex', alignItems: 'center', gap: '6px', fontSize: '0.8rem', color: 'var(--text-secondary)' }}>
+                    <input
+                        type="checkbox"
+                        checked={unmatchedOnly}
+                        onChange={(e) => setUnmatchedOnly(e.currentTarget.checked)}
+                    />
+                    Unmatched only
+                </label>
+                <button onClick={load} title="Reload" style={{ background: 'none', border: 'none', color: 'var(--text-muted)', cursor: 'pointer', padding: '4px' }}>
+                    <RefreshCw size={14} />
+                </button>
+                <button onClick={handleClear} title="Clear Journal" style={{ background: 'none', border: 'none', color: 'var(--text-muted)', cursor: 'pointer', padding: '4px' }}>
+                    <Trash2 size={14} />
+                </button>
+            </div>
+
+            <div style={{ maxHeight: '300px', overflowY: 'auto', fontFamily: 'var(--font-mono)', fontSize: '0.8rem' }}>
+                {visible.map(entry => (
+                    <div key={entry.id} style={{ display: 'flex', alignItems: 'center', gap: '12px', padding: '6px 12px', borderBottom: '1px solid var(--border-color)' }}>
+                        <span style={{ color: 'var(--text-muted)', minWidth: '70px' }}>{new Date(entry.timestamp).toLocaleTimeString()}</span>
+                        <span style={{ minWidth: '60px', fontWeight: 'bold' }}>{entry.method}</span>
+                        <span style={{ flex: 1, minWidth: 0, overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }} title={entry.matched ?? 'No matching endpoint'}>{entry.uri}</span>
+                        <span style={{ color: entry.status === null || entry.status >= 400 ? 'var(--error)' : 'var(--success)' }}>
+                            {entry.status ?? entry.fault}
+                        </span>
+                        <span style={{ color: 'var(--text-muted)', minWidth: '50px', textAlign: 'right' }}>{entry.latency_ms}ms</span>
+                        {entry.matched === null ? (
+                            <button
+                                onClick={() => handlePromote(entry)}
+                                title="Add an endpoint for this request"
+                                style={{ display: 'flex', alignItems: 'center', gap: '4px', background: 'none', border: 'none', color: 'var(--accent-primary)', cursor: 'pointer', fontSize: '0.75rem' }}
+                            >
+                                <ArrowUpRight size={14} /> Promote
+                            </button>
+                        ) : <span style={{ width: '72px' }} />}
+                    </div>
+                ))}
+
+                {visible.length === 0 && (
+                    <div style={{ padding: '20px', textAlign: 'center', color: 'var(--text-muted)', fontFamily: 'inherit' }}>
+                        No requests received yet.
+                    </div>
+                )}
+            </div>
+        </div>
+    );
+}
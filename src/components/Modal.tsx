@@ -7,9 +7,10 @@ interface ModalProps {
     title: string;
     children: ComponentChildren;
     zIndex?: number;
+    width?: string;
 }
 
-export function Modal({ isOpen, onClose, title, children, zIndex = 1100 }: ModalProps) {
+export function Modal({ isOpen, onClose, title, children, zIndex = 1100, width = '500px' }: ModalProps) {
     if (!isOpen) return null;
 
     return (
@@ -30,7 +31,7 @@ export function Modal({ isOpen, onClose, title, children, zIndex = 1100 }: Modal
                 backgroundColor: 'var(--bg-sidebar)',
                 borderRadius: 'var(--radius-lg)',
                 border: '1px solid var(--border-color)',
-                width: '500px',
+                width,
                 maxWidth: '90vw',
                 maxHeight: '80vh',
                 display: 'flex',
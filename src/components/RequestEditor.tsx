@@ -1,6 +1,6 @@
 import { useSignal, useSignalEffect, useComputed } from "@preact/signals";
 import { useRef, useCallback, useEffect } from "preact/hooks";
-import { activeRequestId, requests, folders, environments, activeEnvName, unsavedItemIds, AuthConfig, resolveAuth, resolveHeaders, ScriptItem, executions, executionProgressMap, TableRow } from "../store";
+import { activeRequestId, requests, folders, environments, activeEnvName, unsavedItemIds, AuthConfig, resolveAuth, resolveHeaders, ScriptItem, executions, executionProgressMap, TableRow, GraphQLOptions } from "../store";
 import { RequestPanel } from "./RequestPanel";
 import { MethodSelect } from "./MethodSelect";
 import { VariableInput } from "./VariableInput";
@@ -41,7 +41,7 @@ export function RequestEditor() {
         (currentRequest.headers || []).map(h => ({ key: h.key, values: [...(h.values || [])], enabled: true }))
     );
     const body = useSignal(currentRequest.body || '');
-    const bodyType = useSignal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml'>(
+    const bodyType = useSignal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql'>(
         currentRequest.bodyType || ((currentRequest.body && (currentRequest.body as any).startsWith('{')) ? 'json' : 'none')
     );
     const preScripts = useSignal<ScriptItem[]>(currentRequest.preScripts || []);
@@ -73,6 +73,7 @@ export function RequestEditor() {
     const queryParams = useSignal<TableRow[]>(initialParams);
     const pathParams = useSignal<Record<string, string>>(currentRequest.pathParams || {});
     const formData = useSignal<{ key: string, type: 'text' | 'file', values: string[], enabled: boolean, contentTypes?: string[] }[]>(currentRequest.formData || []);
+    const graphql = useSignal<GraphQLOptions>(currentRequest.graphql || {});
 
     // URL sync effect removed - handled by onInput and initial state
 
@@ -149,6 +150,7 @@ export function RequestEditor() {
         const currentBodyType = bodyType.value;
         const currentBody = body.value;
         const currentFormData = formData.value;
+        const currentGraphql = graphql.value;
         const currentAuth = auth.value;
         const currentPreScripts = preScripts.value;
         const currentPostScripts = postScripts.value;
@@ -168,9 +170,10 @@ export function RequestEditor() {
             const postScriptsChanged = JSON.stringify(req.postScripts) !== JSON.stringify(currentPostScripts);
             const bodyTypeChanged = req.bodyType !== currentBodyType;
             const formDataChanged = JSON.stringify(req.formData || []) !== JSON.stringify(currentFormData);
+            const graphqlChanged = JSON.stringify(req.graphql || {}) !== JSON.stringify(currentGraphql);
             const pathParamsChanged = JSON.stringify(req.pathParams || {}) !== JSON.stringify(currentPathParams);
 
-            if (req.name !== currentName || req.method !== currentMethod || req.url !== currentUrl || headersChanged || req.body !== currentBody || bodyTypeChanged || formDataChanged || authChanged || preScriptsChanged || postScriptsChanged || pathParamsChanged) {
+            if (req.name !== currentName || req.method !== currentMethod || req.url !== currentUrl || headersChanged || req.body !== currentBody || bodyTypeChanged || formDataChanged || graphqlChanged || authChanged || preScriptsChanged || postScriptsChanged || pathParamsChanged) {
                 const newRequests = [...allRequests];
                 newRequests[idx] = {
                     ...req,
@@ -181,6 +184,7 @@ export function RequestEditor() {
                     bodyType: currentBodyType,
                     body: currentBody,
                     formData: currentFormData,
+                    graphql: currentGraphql,
                     auth: currentAuth,
                     preScripts: currentPreScripts,
                     postScripts: currentPostScripts,
@@ -343,6 +347,7 @@ export function RequestEditor() {
             preScripts: preScripts.peek(),
             postScripts: postScripts.peek(),
             formData: formData.peek(),
+            graphql: graphql.peek(),
             pathParams: pathParams.peek()
         });
     };
@@ -484,6 +489,7 @@ export function RequestEditor() {
                         queryParams={queryParams}
                         pathParams={pathParams}
                         formData={formData}
+                        graphql={graphql}
                         detectedPathKeys={detectedPathKeys}
                         updateUrlFromParams={updateUrlFromParams}
                         inheritedAuth={inheritedAuth.value}
@@ -1,10 +1,11 @@
 import { useState } from 'preact/hooks';
-import { Plus, Trash2, Play, ListTree, Database, Eye, EyeOff, CheckCircle, XCircle, X, Code, Search, ChevronDown } from 'lucide-preact';
+import { Plus, Trash2, Play, ListTree, Database, Eye, EyeOff, CheckCircle, XCircle, X, Code, Search, ChevronDown, RotateCcw } from 'lucide-preact';
 import { useCases, executions, syncProjectManifest, activeProjectName, activeUseCaseId, UseCase, UseCaseStep, resolveVariables, requests, useCaseBlackboards } from '../store';
 import { runExecution } from '../utils/execution';
 import { CodeEditor } from './CodeEditor';
 import { ResponseData } from '../store';
 import { ResponsePanel } from './response/ResponsePanel';
+import { RetryPolicyEditor } from './request/RetryPolicyEditor';
 import { t } from '../i18n';
 
 const WOW_STYLES = `
@@ -43,6 +44,7 @@ function UseCaseManagerContent() {
     const [runLogs, setRunLogs] = useState<{ stepIdx: number, status: 'running' | 'success' | 'error', message?: string, response?: ResponseData }[]>([]);
     const [openResponses, setOpenResponses] = useState<Record<number, boolean>>({});
     const [openScripts, setOpenScripts] = useState<Record<string, boolean>>({});
+    const [openRetries, setOpenRetries] = useState<Record<string, boolean>>({});
     const [activeSelectorStepId, setActiveSelectorStepId] = useState<string | null>(null);
     const [isSaving, setIsSaving] = useState(false);
     const blackboard = useCaseBlackboards.value[activeUseCase?.id || ''] || {};
@@ -150,7 +152,8 @@ function UseCaseManagerContent() {
                             name: 'Use Case Step Script',
                             content: step.script || '',
                             enabled: true
-                        }]
+                        }],
+                        retry: step.retry
                     },
                     variableMap,
                     true,
@@ -601,6 +604,28 @@ function UseCaseManagerContent() {
                                                             />
                                                         </div>
                                                     )}
+
+                                                    <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', margin: '8px 0' }}>
+                                                        <div style={{ fontSize: '0.85rem', fontWeight: 'bold', color: 'var(--text-muted)', display: 'flex', alignItems: 'center', gap: '6px' }}>
+                                                            <RotateCcw size={16} /> {t('useCaseManager.retryTitle')}
+                                                            {step.retry && <span style={{ fontSize: '0.75rem', color: 'var(--accent-primary)' }}>{t('useCaseManager.retryAttempts', { count: step.retry.max_attempts ?? 3 })}</span>}
+                                                        </div>
+                                                        <button
+                                                            onClick={() => setOpenRetries((prev: any) => ({ ...prev, [step.id]: !prev[step.id] }))}
+                                                            style={{ background: 'none', border: 'none', color: 'var(--accent-primary)', fontSize: '0.85rem', cursor: 'pointer', display: 'flex', alignItems: 'center', gap: '4px', fontWeight: '600' }}
+                                                        >
+                                                            {openRetries[step.id] ? t('useCaseManager.minimizeEditorBtn') : t('useCaseManager.editRetryBtn')}
+                                                        </button>
+                                                    </div>
+
+                                                    {openRetries[step.id] && (
+                                                        <div style={{ border: '1px solid var(--border-color)', borderRadius: 'var(--radius-md)', animation: 'slideIn 0.2s' }}>
+                                                            <RetryPolicyEditor
+                                                                retry={step.retry}
+                                                                onChange={(retry) => handleUpdateStep(activeUseCase.id, step.id, { retry })}
+                                                            />
+                                                        </div>
+                                                    )}
                                                 </div>
                                             </div>
                                         </div>
@@ -89,6 +89,8 @@ export function UserGuideView() {
         { id: 'collections', label: 'Collections' },
         { id: 'request-editor', label: 'Request Editor' },
         { id: 'execution-editor', label: 'Execution Editor' },
+        { id: 'authentication', label: 'Authentication' },
+        { id: 'tools', label: 'Execution Tools' },
         { id: 'folder-editor', label: 'Folder Editor' },
         { id: 'environments', label: 'Environments' },
         { id: 'scripting', label: 'Scripting' },
@@ -1,13 +1,17 @@
 import { useSignal, useSignalEffect, useComputed, batch } from "@preact/signals";
 import { useRef, useEffect, useCallback } from "preact/hooks";
-import { ArrowLeft, Play, XCircle } from "lucide-preact";
-import { activeExecutionId, activeRequestId, executions, requests, folders, environments, activeEnvName, unsavedItemIds, AuthConfig, resolveAuth, resolveHeaders, ScriptItem, openTabs, activeTabId, executionProgressMap, TableRow } from "../../store";
-import { runExecution, cancelExecution } from "../../utils/execution";
+import { ArrowLeft, Play, XCircle, Radio, Plug, Network, Gauge } from "lucide-preact";
+import { activeExecutionId, activeRequestId, executions, requests, folders, environments, activeEnvName, unsavedItemIds, AuthConfig, resolveAuth, resolveHeaders, ScriptItem, openTabs, activeTabId, executionProgressMap, TableRow, GraphQLOptions, RetryPolicy } from "../../store";
+import { runExecution, cancelExecution, ExecutionOverrides } from "../../utils/execution";
 import { ExecutionRequestPanel } from "./ExecutionRequestPanel";
 import { ExecutionProgress } from "./ExecutionProgress";
 import { ResponsePanel } from "../response/ResponsePanel";
 import { MethodSelect } from "../MethodSelect";
 import { VariableInput } from "../VariableInput";
+import { SseModal } from "../tools/SseModal";
+import { WebSocketModal } from "../tools/WebSocketModal";
+import { GrpcModal } from "../tools/GrpcModal";
+import { LoadTestModal } from "../tools/LoadTestModal";
 import { t } from "../../i18n";
 
 export function ExecutionEditor() {
@@ -79,7 +83,7 @@ export function ExecutionEditor() {
     const url = useSignal(initialBase);
     const method = useSignal(currentExecution.method ?? parentRequest.method);
     const body = useSignal(currentExecution.body ?? parentRequest.body ?? '');
-    const bodyType = useSignal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml'>(
+    const bodyType = useSignal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql'>(
         currentExecution.bodyType || parentRequest.bodyType || ((currentExecution.body ?? parentRequest.body ?? '') !== '' ? 'json' : 'none')
     );
     const preScripts = useSignal<ScriptItem[]>(currentExecution.preScripts ?? parentRequest.preScripts ?? []);
@@ -89,7 +93,10 @@ export function ExecutionEditor() {
     const queryParams = useSignal<TableRow[]>(getMergedQueryParams(parentRequest.url, currentExecution.queryParams));
     const pathParams = useSignal<Record<string, string>>(currentExecution.pathParams || {});
     const formData = useSignal<{ key: string, type: 'text' | 'file', values: string[], enabled: boolean, contentTypes?: string[] }[]>(currentExecution.formData ?? parentRequest.formData ?? []);
+    const graphql = useSignal<GraphQLOptions>(currentExecution.graphql ?? parentRequest.graphql ?? {});
+    const retry = useSignal<RetryPolicy | undefined>(currentExecution.retry);
     const detectedPathKeys = useSignal<string[]>([]);
+    const openTool = useSignal<'sse' | 'ws' | 'grpc' | 'load' | null>(null);
     const lastLoadedId = useRef<string | null>(null);
 
     // --- Effects & Sync Logic ---
@@ -118,6 +125,8 @@ export function ExecutionEditor() {
             pathParams.value = cExec.pathParams ?? {};
             headers.value = getMergedHeaders();
             formData.value = cExec.formData ?? parentRequest.formData ?? [];
+            graphql.value = cExec.graphql ?? parentRequest.graphql ?? {};
+            retry.value = cExec.retry;
         });
     }, [activeExecutionId.value, parentRequest?.id]);
 
@@ -241,12 +250,16 @@ export function ExecutionEditor() {
         return result;
     }
 
+    // The editor's current values, also used by the tools that send the request themselves
+    const getOverrides = (): ExecutionOverrides => ({
+        url: url.peek(), method: method.peek(), headers: headers.peek(), queryParams: queryParams.peek(),
+        body: body.peek(), bodyType: bodyType.peek(), auth: auth.peek(), preScripts: preScripts.peek(),
+        postScripts: postScripts.peek(), formData: formData.peek(), graphql: graphql.peek(), pathParams: pathParams.peek(),
+        retry: retry.peek()
+    });
+
     const handleSend = () => {
-        runExecution(activeExecutionId.peek()!, {
-            url: url.peek(), method: method.peek(), headers: headers.peek(), queryParams: queryParams.peek(),
-            body: body.peek(), bodyType: bodyType.peek(), auth: auth.peek(), preScripts: preScripts.peek(),
-            postScripts: postScripts.peek(), formData: formData.peek(), pathParams: pathParams.peek()
-        });
+        runExecution(activeExecutionId.peek()!, getOverrides());
     };
 
     const handleCancel = () => cancelExecution(activeExecutionId.peek()!);
@@ -300,11 +313,14 @@ export function ExecutionEditor() {
         const finalBody = body.value === (parentRequest.body ?? '') ? undefined : body.value;
         const finalBodyType = bodyType.value === (parentRequest.bodyType || 'json') ? undefined : bodyType.value;
         const finalAuth = JSON.stringify(auth.value) === JSON.stringify(parentRequest.auth ?? { type: 'inherit' }) ? undefined : auth.value;
+        const finalGraphql = JSON.stringify(graphql.value) === JSON.stringify(parentRequest.graphql ?? {}) ? undefined : graphql.value;
 
-        if (exec.name !== name.value || JSON.stringify(exec.headers) !== JSON.stringify(finalHeaders) || JSON.stringify(exec.queryParams) !== JSON.stringify(finalQueryParams) || exec.body !== finalBody || exec.auth !== finalAuth) {
+        const finalRetry = retry.value;
+
+        if (exec.name !== name.value || JSON.stringify(exec.headers) !== JSON.stringify(finalHeaders) || JSON.stringify(exec.queryParams) !== JSON.stringify(finalQueryParams) || exec.body !== finalBody || exec.auth !== finalAuth || JSON.stringify(exec.graphql) !== JSON.stringify(finalGraphql) || JSON.stringify(exec.retry) !== JSON.stringify(finalRetry)) {
             batch(() => {
                 const newExecs = [...allExecs];
-                newExecs[idx] = { ...exec, name: name.value, headers: finalHeaders, queryParams: finalQueryParams, body: finalBody, bodyType: finalBodyType, auth: finalAuth };
+                newExecs[idx] = { ...exec, name: name.value, headers: finalHeaders, queryParams: finalQueryParams, body: finalBody, bodyType: finalBodyType, auth: finalAuth, graphql: finalGraphql, retry: finalRetry };
                 executions.value = newExecs;
                 const newUnsaved = new Set(unsavedItemIds.peek());
                 newUnsaved.add(execId);
@@ -360,6 +376,28 @@ export function ExecutionEditor() {
                 </button>
             </div>
 
+            {/* Tools */}
+            <div style={{ display: 'flex', gap: '4px', alignItems: 'center' }}>
+                {([
+                    ['sse', Radio, t('executionEditor.tools.sse')],
+                    ['ws', Plug, t('executionEditor.tools.ws')],
+                    ['grpc', Network, t('executionEditor.tools.grpc')],
+                    ['load', Gauge, t('executionEditor.tools.load')]
+                ] as const).map(([tool, Icon, label]) => (
+                    <button
+                        key={tool}
+                        onClick={() => openTool.value = tool}
+                        style={{ display: 'flex', alignItems: 'center', gap: '4px', padding: '4px 8px', background: 'transparent', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', color: 'var(--text-secondary)', fontSize: '0.8rem', cursor: 'pointer' }}
+                    >
+                        <Icon size={14} /> {label}
+                    </button>
+                ))}
+            </div>
+            <SseModal isOpen={openTool.value === 'sse'} onClose={() => openTool.value = null} executionId={currentExecution.id} getOverrides={getOverrides} />
+            <WebSocketModal isOpen={openTool.value === 'ws'} onClose={() => openTool.value = null} executionId={currentExecution.id} getOverrides={getOverrides} />
+            <GrpcModal isOpen={openTool.value === 'grpc'} onClose={() => openTool.value = null} executionId={currentExecution.id} getOverrides={getOverrides} />
+            <LoadTestModal isOpen={openTool.value === 'load'} onClose={() => openTool.value = null} executionId={currentExecution.id} getOverrides={getOverrides} />
+
             {/* Progress */}
             {(progress.value.isLoading || progress.value.steps.length > 0) && (
                 <ExecutionProgress isLoading={useSignal(progress.value.isLoading)} executionSteps={useSignal(progress.value.steps)} startTime={useSignal(progress.value.startTime)} totalExecutionTime={useSignal(progress.value.totalTime)} lastResponseTime={useSignal(progress.value.lastResponseTime)} responseSize={useSignal(progress.value.responseSize)} responseStatus={useSignal(progress.value.responseStatus)} compact={false} />
@@ -370,7 +408,7 @@ export function ExecutionEditor() {
                 <div style={{ width: `${leftPanelWidth.value}%`, display: 'flex', flexDirection: 'column', minWidth: 0 }}>
                     <ExecutionRequestPanel
                         id={currentExecution.id} headers={headers} bodyType={bodyType} body={body} auth={auth}
-                        queryParams={queryParams} pathParams={pathParams} formData={formData} detectedPathKeys={detectedPathKeys}
+                        queryParams={queryParams} pathParams={pathParams} formData={formData} graphql={graphql} retry={retry} detectedPathKeys={detectedPathKeys}
                         updateUrlFromParams={updateUrlFromParams} inheritedAuth={inheritedAuth.value} inheritedHeaders={inheritedHeaders.value}
                         preScripts={preScripts} postScripts={postScripts} overriddenHeaders={overriddenHeaders.value}
                         overriddenQueryParams={overriddenQueryParams.value} parentHeaderKeys={parentHeaderKeys.value}
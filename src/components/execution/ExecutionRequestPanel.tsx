@@ -1,17 +1,19 @@
 import { Signal } from "@preact/signals";
 import { RequestPropertyTabs } from "../request/RequestPropertyTabs";
-import { AuthConfig, ScriptItem, TableRow, InheritedRow } from "../../store";
+import { AuthConfig, ScriptItem, TableRow, InheritedRow, GraphQLOptions, RetryPolicy } from "../../store";
 import { t } from "../../i18n";
 
 interface ExecutionRequestPanelProps {
     id: string;
     headers: Signal<TableRow[]>;
-    bodyType: Signal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml'>;
+    bodyType: Signal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql'>;
     body: Signal<string>;
     auth: Signal<AuthConfig | undefined>;
     queryParams: Signal<TableRow[]>;
     pathParams: Signal<Record<string, string>>;
     formData: Signal<{ key: string, type: 'text' | 'file', values: string[], enabled: boolean, contentTypes?: string[] }[]>;
+    graphql?: Signal<GraphQLOptions>;
+    retry?: Signal<RetryPolicy | undefined>;
     detectedPathKeys: Signal<string[]>;
     updateUrlFromParams: (newParams: TableRow[]) => void;
     inheritedAuth?: { config: AuthConfig, source: string, sourceId?: string };
@@ -4,12 +4,15 @@ import { open } from '@tauri-apps/plugin-dialog';
 import { OverrideIndicator } from "../OverrideIndicator";
 import { VariableInput } from "../VariableInput";
 import { CodeEditor } from "../CodeEditor";
+import { GraphQLOptions } from "../../store";
 import { t } from "../../i18n";
 
 interface RequestBodyEditorProps {
-    bodyType: Signal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml'>;
+    bodyType: Signal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql'>;
     body: Signal<string>;
     formData: Signal<{ key: string, type: 'text' | 'file', values: string[], enabled: boolean, contentTypes?: string[] }[]>;
+    /** Variables, operation name and persisted flag of the 'graphql' body type */
+    graphql?: Signal<GraphQLOptions>;
     isReadOnly?: boolean;
     isOverridden?: boolean;
     isTypeReadOnly?: boolean;
@@ -36,7 +39,7 @@ const getMimeType = (path: string) => {
     }
 };
 
-export function RequestBodyEditor({ bodyType, body, formData, isReadOnly, isOverridden, isTypeReadOnly, parentId }: RequestBodyEditorProps) {
+export function RequestBodyEditor({ bodyType, body, formData, graphql, isReadOnly, isOverridden, isTypeReadOnly, parentId }: RequestBodyEditorProps) {
     return (
         <div style={{ display: 'flex', flexDirection: 'column', gap: '8px', flex: 1, minHeight: 0, minWidth: 0, height: '100%' }}>
             <select
@@ -59,6 +62,7 @@ export function RequestBodyEditor({ bodyType, body, formData, isReadOnly, isOver
                 <option value="text">{t('requestEditor.body.types.text')}</option>
                 <option value="javascript">{t('requestEditor.body.types.javascript')}</option>
                 <option value="yaml">{t('requestEditor.body.types.yaml')}</option>
+                <option value="graphql">{t('requestEditor.body.types.graphql')}</option>
             </select>
 
             {bodyType.value === 'multipart' || bodyType.value === 'form_urlencoded' ? (
@@ -279,6 +283,39 @@ export function RequestBodyEditor({ bodyType, body, formData, isReadOnly, isOver
                                 parentId={parentId}
                             />
                         )}
+                        {bodyType.value === 'graphql' && graphql && (
+                            <div style={{ display: 'flex', flexDirection: 'column', gap: '8px' }}>
+                                <label style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{t('requestEditor.body.graphqlOperationName')}</label>
+                                <input
+                                    value={graphql.value.operationName || ''}
+                                    placeholder={t('requestEditor.body.graphqlOperationNamePlaceholder')}
+                                    readOnly={isReadOnly}
+                                    onInput={(e) => graphql.value = { ...graphql.value, operationName: e.currentTarget.value || undefined }}
+                                    style={{
+                                        backgroundColor: isReadOnly ? 'transparent' : 'var(--bg-input)',
+                                        border: isReadOnly ? '1px solid transparent' : '1px solid var(--border-color)'
+                                    }}
+                                />
+                                <label style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{t('requestEditor.body.graphqlVariables')}</label>
+                                <CodeEditor
+                                    value={graphql.value.variables || ''}
+                                    onChange={(val) => graphql.value = { ...graphql.value, variables: val || undefined }}
+                                    language="json"
+                                    readOnly={isReadOnly}
+                                    height="120px"
+                                    parentId={parentId}
+                                />
+                                <label style={{ display: 'flex', alignItems: 'center', gap: '6px', fontSize: '0.8rem' }}>
+                                    <input
+                                        type="checkbox"
+                                        checked={!!graphql.value.persisted}
+                                        disabled={isReadOnly}
+                                        onChange={(e) => graphql.value = { ...graphql.value, persisted: e.currentTarget.checked }}
+                                    />
+                                    {t('requestEditor.body.graphqlPersisted')}
+                                </label>
+                            </div>
+                        )}
                     </div>
                 )
             )}
@@ -4,19 +4,22 @@ import { RequestBodyEditor } from "./RequestBodyEditor";
 import { RequestHeadersEditor } from "./RequestHeadersEditor";
 import { ScriptListEditor } from "./ScriptListEditor";
 import { AuthEditor } from "../AuthEditor";
+import { RetryPolicyEditor } from "./RetryPolicyEditor";
 import { Tabs } from "../ui/Tabs";
-import { itemRequestTabStates, itemScriptTabStates, AuthConfig, ScriptItem, TableRow, InheritedRow } from "../../store";
+import { itemRequestTabStates, itemScriptTabStates, AuthConfig, ScriptItem, TableRow, InheritedRow, GraphQLOptions, RetryPolicy } from "../../store";
 import { t } from "../../i18n";
 
 interface RequestPropertyTabsProps {
     id: string;
     headers: Signal<TableRow[]>;
-    bodyType: Signal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml'>;
+    bodyType: Signal<'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql'>;
     body: Signal<string>;
     auth: Signal<AuthConfig | undefined>;
     queryParams: Signal<TableRow[]>;
     pathParams: Signal<Record<string, string>>;
     formData: Signal<{ key: string, type: 'text' | 'file', values: string[], enabled: boolean, contentTypes?: string[] }[]>;
+    graphql?: Signal<GraphQLOptions>;
+    retry?: Signal<RetryPolicy | undefined>;
     detectedPathKeys: Signal<string[]>;
     onUpdateParams: (newParams: TableRow[]) => void;
     inheritedAuth?: { config: AuthConfig, source: string, sourceId?: string };
@@ -44,6 +47,7 @@ export function RequestPropertyTabs(props: RequestPropertyTabsProps) {
         { id: 'headers', label: t('requestEditor.tabs.headers'), badge: props.headers.value.filter(h => h.key).length || undefined },
         { id: 'auth', label: t('requestEditor.tabs.auth') },
         { id: 'scripts', label: t('requestEditor.tabs.scripts'), badge: (props.preScripts.value.length + props.postScripts.value.length) || undefined },
+        ...(props.retry ? [{ id: 'retry', label: t('requestEditor.tabs.retry') }] : []),
     ];
 
     const handleTabChange = (tabId: string) => {
@@ -76,6 +80,7 @@ export function RequestPropertyTabs(props: RequestPropertyTabsProps) {
                         bodyType={props.bodyType}
                         body={props.body}
                         formData={props.formData}
+                        graphql={props.graphql}
                         isReadOnly={props.isReadOnly}
                         isOverridden={props.isBodyOverridden}
                         isTypeReadOnly={false}
@@ -102,6 +107,13 @@ export function RequestPropertyTabs(props: RequestPropertyTabsProps) {
                         parentId={props.parentId}
                     />
                 )}
+                {activeTab === 'retry' && props.retry && (
+                    <RetryPolicyEditor
+                        retry={props.retry.value}
+                        onChange={(v) => props.retry!.value = v}
+                        isReadOnly={props.isReadOnly}
+                    />
+                )}
                 {activeTab === 'scripts' && (
                     <div style={{ display: 'flex', flexDirection: 'column', gap: '16px', height: '100%' }}>
                         <Tabs
@@ -0,0 +1,124 @@
+import { RetryPolicy } from "../../store";
+import { t } from "../../i18n";
+
+interface RetryPolicyEditorProps {
+    retry: RetryPolicy | undefined;
+    onChange: (retry: RetryPolicy | undefined) => void;
+    isReadOnly?: boolean;
+}
+
+// Backend defaults, shown as placeholders so that unset fields keep following them
+const ERROR_KINDS = ['connect', 'timeout', 'reset', 'request', 'body'];
+const DEFAULT_ERROR_KINDS = ['connect', 'timeout', 'reset'];
+
+const labelStyle = { fontSize: '0.8rem', color: 'var(--text-muted)' };
+const inputStyle = {
+    padding: '6px 8px',
+    backgroundColor: 'var(--bg-input)',
+    border: '1px solid var(--border-color)',
+    borderRadius: 'var(--radius-sm)',
+    color: 'var(--text-primary)',
+    outline: 'none'
+};
+
+export function RetryPolicyEditor({ retry, onChange, isReadOnly = false }: RetryPolicyEditorProps) {
+    const update = (patch: Partial<RetryPolicy>) => onChange({ ...(retry || {}), ...patch });
+
+    const numberField = (key: 'max_attempts' | 'initial_delay_ms' | 'max_delay_ms' | 'multiplier', placeholder: string) => (
+        <div style={{ display: 'flex', flexDirection: 'column', gap: '4px', flex: 1 }}>
+            <label style={labelStyle}>{t(`requestEditor.retry.${key}`)}</label>
+            <input
+                type="number"
+                min={key === 'max_attempts' ? 1 : 0}
+                step={key === 'multiplier' ? 0.5 : 1}
+                value={retry?.[key] ?? ''}
+                placeholder={placeholder}
+                readOnly={isReadOnly}
+                onInput={(e) => {
+                    const value = e.currentTarget.value;
+                    update({ [key]: value === '' ? undefined : Number(value) } as Partial<RetryPolicy>);
+                }}
+                style={inputStyle}
+            />
+        </div>
+    );
+
+    const checkbox = (key: 'jitter' | 'respect_retry_after' | 'idempotent_only') => (
+        <label style={{ ...labelStyle, display: 'flex', alignItems: 'center', gap: '6px' }}>
+            <input
+                type="checkbox"
+                checked={retry?.[key] ?? true}
+                disabled={isReadOnly}
+                onChange={(e) => update({ [key]: e.currentTarget.checked } as Partial<RetryPolicy>)}
+            />
+            {t(`requestEditor.retry.${key}`)}
+        </label>
+    );
+
+    const errorKinds = retry?.error_kinds ?? DEFAULT_ERROR_KINDS;
+
+    return (
+        <div style={{ display: 'flex', flexDirection: 'column', gap: '16px', padding: '16px' }}>
+            <label style={{ display: 'flex', alignItems: 'center', gap: '8px', fontSize: '0.9rem' }}>
+                <input
+                    type="checkbox"
+                    checked={!!retry}
+                    disabled={isReadOnly}
+                    onChange={(e) => onChange(e.currentTarget.checked ? {} : undefined)}
+                />
+                {t('requestEditor.retry.enabled')}
+            </label>
+
+            {retry && (
+                <>
+                    <div style={{ display: 'flex', gap: '12px' }}>
+                        {numberField('max_attempts', '3')}
+                        <div style={{ display: 'flex', flexDirection: 'column', gap: '4px', flex: 2 }}>
+                            <label style={labelStyle}>{t('requestEditor.retry.status_codes')}</label>
+                            <input
+                                value={retry.status_codes ?? ''}
+                                placeholder="429,502,503,504"
+                                readOnly={isReadOnly}
+                                onInput={(e) => update({ status_codes: e.currentTarget.value || undefined })}
+                                style={inputStyle}
+                            />
+                        </div>
+                    </div>
+
+                    <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                        <label style={labelStyle}>{t('requestEditor.retry.error_kinds')}</label>
+                        <div style={{ display: 'flex', gap: '12px', flexWrap: 'wrap' }}>
+                            {ERROR_KINDS.map(kind => (
+                                <label key={kind} style={{ ...labelStyle, display: 'flex', alignItems: 'center', gap: '4px' }}>
+                                    <input
+                                        type="checkbox"
+                                        checked={errorKinds.includes(kind)}
+                                        disabled={isReadOnly}
+                                        onChange={(e) => update({
+                                            error_kinds: e.currentTarget.checked
+                                                ? [...errorKinds, kind]
+                                                : errorKinds.filter(k => k !== kind)
+                                        })}
+                                    />
+                                    {kind}
+                                </label>
+                            ))}
+                        </div>
+                    </div>
+
+                    <div style={{ display: 'flex', gap: '12px' }}>
+                        {numberField('initial_delay_ms', '500')}
+                        {numberField('multiplier', '2')}
+                        {numberField('max_delay_ms', '30000')}
+                    </div>
+
+                    <div style={{ display: 'flex', flexDirection: 'column', gap: '8px' }}>
+                        {checkbox('jitter')}
+                        {checkbox('respect_retry_after')}
+                        {checkbox('idempotent_only')}
+                    </div>
+                </>
+            )}
+        </div>
+    );
+}
@@ -94,6 +94,11 @@ export function ResponsePanel({ id, response }: ResponsePanelProps) {
                                 {formatBytes(response.size)}
                             </span>
                         )}
+                        {response.decodeWarning && (
+                            <span style={{ fontSize: '0.75rem', color: 'var(--warning)' }} title={response.decodeWarning}>
+                                {response.decodeWarning}
+                            </span>
+                        )}
                     </div>
                 )}
             </div>
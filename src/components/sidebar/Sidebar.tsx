@@ -616,7 +616,11 @@ export function Sidebar({ width = 250 }: SidebarProps) {
                                 )}
 
                                 <button
-                                    onClick={(e) => { e.stopPropagation(); deleteExternalMock(mock.id); }}
+                                    onClick={(e) => {
+                                        e.stopPropagation();
+                                        invoke('remove_mock_server', { collectionId: mock.id }).catch(err => console.error('Failed to remove mock server:', err));
+                                        deleteExternalMock(mock.id);
+                                    }}
                                     className="delete-btn"
                                     style={{
                                         background: 'transparent', border: 'none', color: 'var(--text-muted)', cursor: 'pointer', opacity: 0
@@ -1,6 +1,6 @@
 import { useEffect, useRef } from 'preact/hooks';
-import { contextMenu, requests, folders, executions, activeRequestId, activeExecutionId, activeFolderId, openTabs, activeTabId, importModal, collections, showPrompt, createNewRequest, environments } from '../../store';
-import { Edit2, Trash2, FilePlus, FolderPlus, Copy, Save, X, Play, Download, ServerCog, ExternalLink } from 'lucide-preact';
+import { contextMenu, requests, folders, executions, activeRequestId, activeExecutionId, activeFolderId, openTabs, activeTabId, importModal, collections, showPrompt, createNewRequest, environments, batchRunTarget } from '../../store';
+import { Edit2, Trash2, FilePlus, FolderPlus, Copy, Save, X, Play, Download, ServerCog, ExternalLink, ListChecks } from 'lucide-preact';
 import { exportToPostman } from '../../utils/postmanUtils';
 import { invoke } from '@tauri-apps/api/core';
 import { save } from '@tauri-apps/plugin-dialog';
@@ -309,6 +309,16 @@ export function SidebarContextMenu() {
 
 
 
+    const handleRunInBackend = () => {
+        const name = menu.type === 'collection'
+            ? collections.value.find(c => c.id === menu.collectionId)?.name
+            : folders.value.find(f => f.id === menu.itemId)?.name;
+        batchRunTarget.value = menu.type === 'collection'
+            ? { kind: 'collection', id: menu.collectionId, name: name || '' }
+            : { kind: 'folder', id: menu.itemId, name: name || '' };
+        contextMenu.value = null;
+    };
+
     const handleCloseAllTabs = () => {
         openTabs.value = [];
         activeTabId.value = null;
@@ -406,6 +416,13 @@ export function SidebarContextMenu() {
                     >
                         <ServerCog size={14} /> {t('contextMenu.mockManager')}
                     </div>
+                    <div
+                        className="context-menu-item"
+                        onClick={handleRunInBackend}
+                        style={itemStyle}
+                    >
+                        <ListChecks size={14} /> {t('contextMenu.runInBackend')}
+                    </div>
                     <div
                         className="context-menu-item"
                         onClick={async () => {
@@ -555,6 +572,13 @@ export function SidebarContextMenu() {
                             >
                                 <Download size={14} /> {t('contextMenu.import')}
                             </div>
+                            <div
+                                className="context-menu-item"
+                                onClick={handleRunInBackend}
+                                style={itemStyle}
+                            >
+                                <ListChecks size={14} /> {t('contextMenu.runInBackend')}
+                            </div>
                         </>
                     )}
 
@@ -0,0 +1,170 @@
+import { useSignal } from "@preact/signals";
+import { useEffect, useRef } from "preact/hooks";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { activeProjectName, batchRunTarget, executions, folders, requests } from "../../store";
+import { prepareExecutionArgs } from "../../utils/execution";
+import { Modal } from "../Modal";
+import { buttonStyle, inputStyle, labelStyle } from "./toolStyles";
+import { t } from "../../i18n";
+
+interface BatchProgress {
+    request_id: string;
+    item_id: string;
+    state: string;
+    status: number | null;
+    time_ms: number;
+}
+
+interface BatchReport {
+    total: number;
+    passed: number;
+    failed: number;
+    skipped: number;
+    cancelled: boolean;
+    duration_ms: number;
+}
+
+const STATE_COLORS: Record<string, string> = {
+    passed: 'var(--success)',
+    failed: 'var(--error)',
+    error: 'var(--error)',
+    running: 'var(--accent-primary)'
+};
+
+/** Executions of the requests under a folder (sub-folders included) or in a collection */
+const executionsInScope = (kind: 'folder' | 'collection', id: string) => {
+    let inScope: (parentId: string | null | undefined, collectionId: string) => boolean;
+    if (kind === 'collection') {
+        inScope = (_, collectionId) => collectionId === id;
+    } else {
+        const folderIds = new Set([id]);
+        let grew = true;
+        while (grew) {
+            grew = false;
+            folders.peek().forEach(f => {
+                if (f.parentId && folderIds.has(f.parentId) && !folderIds.has(f.id)) {
+                    folderIds.add(f.id);
+                    grew = true;
+                }
+            });
+        }
+        inScope = (parentId) => !!parentId && folderIds.has(parentId);
+    }
+    return requests.peek()
+        .filter(r => inScope(r.parentId, r.collectionId))
+        .flatMap(r => executions.peek()
+            .filter(e => e.requestId === r.id)
+            .sort((a, b) => (a.sortIndex ?? 0) - (b.sortIndex ?? 0))
+            .map(e => ({ execution: e, name: `${r.name} > ${e.name}` })));
+};
+
+export function BatchRunModal() {
+    const target = batchRunTarget.value;
+    const concurrency = useSignal('1');
+    const stopOnFailure = useSignal(false);
+    const states = useSignal<Record<string, BatchProgress>>({});
+    const report = useSignal<BatchReport | null>(null);
+    const error = useSignal<string | null>(null);
+    const isRunning = useSignal(false);
+    const requestId = useRef<string | null>(null);
+
+    useEffect(() => {
+        const unlisten = listen<BatchProgress>('batch-progress', (e) => {
+            if (e.payload.request_id === requestId.current) {
+                states.value = { ...states.value, [e.payload.item_id]: e.payload };
+            }
+        });
+        return () => { unlisten.then(f => f()); };
+    }, []);
+
+    if (!target) return null;
+    const items = executionsInScope(target.kind, target.id);
+
+    const handleRun = async () => {
+        const id = crypto.randomUUID();
+        requestId.current = id;
+        states.value = {};
+        report.value = null;
+        error.value = null;
+        isRunning.value = true;
+        try {
+            const batchItems = items.flatMap(({ execution, name }) => {
+                const request = prepareExecutionArgs(execution.id);
+                return request ? [{ id: execution.id, name, request: { ...request, project_name: activeProjectName.peek() } }] : [];
+            });
+            report.value = await invoke<BatchReport>('run_batch', {
+                args: {
+                    items: batchItems,
+                    concurrency: Number(concurrency.peek()) || 1,
+                    stop_on_failure: stopOnFailure.peek(),
+                    request_id: id
+                }
+            });
+        } catch (e) {
+            error.value = String(e);
+        } finally {
+            isRunning.value = false;
+        }
+    };
+
+    const handleStop = () => {
+        if (requestId.current && isRunning.peek()) invoke('cancel_http_request', { requestId: requestId.current });
+    };
+
+    const handleClose = () => {
+        handleStop();
+        batchRunTarget.value = null;
+        states.value = {};
+        report.value = null;
+    };
+
+    return (
+        <Modal isOpen={true} onClose={handleClose} title={t('tools.batch.title', { name: target.name })} width="640px">
+            <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                <div style={{ display: 'flex', gap: '12px', alignItems: 'flex-end' }}>
+                    <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                        <label style={labelStyle}>{t('tools.batch.concurrency')}</label>
+                        <input type="number" min={1} value={concurrency.value} onInput={(e) => concurrency.value = e.currentTarget.value} style={{ ...inputStyle, width: '80px' }} />
+                    </div>
+                    <label style={{ ...labelStyle, display: 'flex', alignItems: 'center', gap: '6px', paddingBottom: '6px' }}>
+                        <input type="checkbox" checked={stopOnFailure.value} onChange={(e) => stopOnFailure.value = e.currentTarget.checked} />
+                        {t('tools.batch.stopOnFailure')}
+                    </label>
+                    <div style={{ flex: 1 }} />
+                    {isRunning.value
+                        ? <button onClick={handleStop} style={buttonStyle(false, true)}>{t('tools.batch.stopBtn')}</button>
+                        : <button onClick={handleRun} disabled={items.length === 0} style={buttonStyle(true)}>{t('tools.batch.runBtn', { count: items.length })}</button>}
+                </div>
+                {error.value && <div style={{ color: 'var(--error)', fontSize: '0.85rem' }}>{error.value}</div>}
+                {report.value && (
+                    <div style={{ fontSize: '0.85rem' }}>
+                        {t('tools.batch.summary', {
+                            passed: report.value.passed,
+                            failed: report.value.failed,
+                            skipped: report.value.skipped,
+                            seconds: (report.value.duration_ms / 1000).toFixed(1)
+                        })}
+                        {report.value.cancelled ? ` — ${t('tools.load.cancelled')}` : ''}
+                    </div>
+                )}
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '4px', maxHeight: '320px', overflowY: 'auto' }}>
+                    {items.length === 0 && <span style={{ color: 'var(--text-muted)', fontSize: '0.85rem' }}>{t('tools.batch.noExecutions')}</span>}
+                    {items.map(({ execution, name }) => {
+                        const state = states.value[execution.id];
+                        return (
+                            <div key={execution.id} style={{ display: 'flex', gap: '8px', fontSize: '0.85rem', alignItems: 'center' }}>
+                                <span style={{ flex: 1, overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>{name}</span>
+                                {state && (
+                                    <span style={{ color: STATE_COLORS[state.state] ?? 'var(--text-muted)', fontWeight: 'bold' }}>
+                                        {state.state}{state.status ? ` ${state.status}` : ''}{state.time_ms ? ` · ${state.time_ms} ms` : ''}
+                                    </span>
+                                )}
+                            </div>
+                        );
+                    })}
+                </div>
+            </div>
+        </Modal>
+    );
+}
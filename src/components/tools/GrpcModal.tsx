@@ -0,0 +1,166 @@
+import { useSignal } from "@preact/signals";
+import { useEffect, useRef } from "preact/hooks";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { activeProjectName } from "../../store";
+import { ExecutionOverrides, prepareExecutionArgs } from "../../utils/execution";
+import { Modal } from "../Modal";
+import { CodeEditor } from "../CodeEditor";
+import { buttonStyle, inputStyle, labelStyle, logStyle } from "./toolStyles";
+import { t } from "../../i18n";
+
+interface GrpcMethod {
+    name: string;
+    client_streaming: boolean;
+    server_streaming: boolean;
+    input_example: unknown;
+}
+
+interface GrpcService {
+    name: string;
+    methods: GrpcMethod[];
+}
+
+interface GrpcResponse {
+    status_code: number;
+    status_name: string;
+    status_message: string;
+    messages: unknown[];
+}
+
+interface GrpcModalProps {
+    isOpen: boolean;
+    onClose: () => void;
+    executionId: string;
+    getOverrides: () => ExecutionOverrides;
+}
+
+export function GrpcModal({ isOpen, onClose, executionId, getOverrides }: GrpcModalProps) {
+    const url = useSignal('');
+    const protoFiles = useSignal('');
+    const services = useSignal<GrpcService[]>([]);
+    // "<service>/<method>"
+    const selected = useSignal('');
+    const message = useSignal('{}');
+    const web = useSignal(false);
+    const streamed = useSignal<unknown[]>([]);
+    const status = useSignal('');
+    const isCalling = useSignal(false);
+    const requestId = useRef<string | null>(null);
+
+    // The server address defaults to the origin of the execution's URL
+    useEffect(() => {
+        if (!isOpen) return;
+        const args = prepareExecutionArgs(executionId, getOverrides());
+        if (args && !url.peek()) {
+            try { url.value = new URL(args.url).origin; } catch { url.value = args.url; }
+        }
+    }, [isOpen, executionId]);
+
+    useEffect(() => {
+        const unlisten = listen<{ request_id: string, message: unknown }>('grpc-message', (e) => {
+            if (e.payload.request_id === requestId.current) {
+                streamed.value = [...streamed.value, e.payload.message];
+            }
+        });
+        return () => { unlisten.then(f => f()); };
+    }, []);
+
+    const schemaArgs = () => {
+        const args = prepareExecutionArgs(executionId, getOverrides());
+        return {
+            url: url.peek(),
+            proto_files: protoFiles.peek().split(',').map(f => f.trim()).filter(Boolean),
+            headers: args?.headers ?? []
+        };
+    };
+
+    const handleLoadServices = async () => {
+        status.value = t('tools.grpc.loadingServices');
+        try {
+            services.value = await invoke<GrpcService[]>('grpc_list_services', { args: { ...schemaArgs(), refresh: true } });
+            status.value = t('tools.grpc.servicesLoaded', { count: services.value.length });
+        } catch (e) {
+            status.value = `Error: ${e}`;
+        }
+    };
+
+    const handleSelect = (value: string) => {
+        selected.value = value;
+        const [service, method] = value.split('/');
+        const example = services.peek().find(s => s.name === service)?.methods.find(m => m.name === method)?.input_example;
+        if (example !== undefined) message.value = JSON.stringify(example, null, 2);
+    };
+
+    const handleCall = async () => {
+        const [service, method] = selected.peek().split('/');
+        if (!service || !method) return;
+        const id = crypto.randomUUID();
+        requestId.current = id;
+        streamed.value = [];
+        isCalling.value = true;
+        status.value = t('tools.grpc.calling');
+        try {
+            const res = await invoke<GrpcResponse>('grpc_request', {
+                args: {
+                    ...schemaArgs(),
+                    service,
+                    method,
+                    message: message.peek(),
+                    web: web.peek(),
+                    request_id: id,
+                    project_name: activeProjectName.peek()
+                }
+            });
+            streamed.value = res.messages;
+            status.value = `${res.status_code} ${res.status_name}${res.status_message ? `: ${res.status_message}` : ''}`;
+        } catch (e) {
+            status.value = `Error: ${e}`;
+        } finally {
+            isCalling.value = false;
+        }
+    };
+
+    const handleCancel = () => {
+        if (requestId.current) invoke('cancel_http_request', { requestId: requestId.current });
+    };
+
+    return (
+        <Modal isOpen={isOpen} onClose={onClose} title={t('tools.grpc.title')} width="720px">
+            <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                <div style={{ display: 'flex', gap: '8px' }}>
+                    <input value={url.value} onInput={(e) => url.value = e.currentTarget.value} placeholder="http://localhost:50051" style={{ ...inputStyle, flex: 1 }} />
+                    <button onClick={handleLoadServices} style={buttonStyle(false)}>{t('tools.grpc.loadServicesBtn')}</button>
+                </div>
+                <div style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                    <label style={labelStyle}>{t('tools.grpc.protoFiles')}</label>
+                    <input value={protoFiles.value} onInput={(e) => protoFiles.value = e.currentTarget.value} placeholder={t('tools.grpc.protoFilesPlaceholder')} style={inputStyle} />
+                </div>
+                <div style={{ display: 'flex', gap: '8px', alignItems: 'center' }}>
+                    <select value={selected.value} onChange={(e) => handleSelect(e.currentTarget.value)} style={{ ...inputStyle, flex: 1 }}>
+                        <option value="">{t('tools.grpc.selectMethod')}</option>
+                        {services.value.map(s => s.methods.map(m => (
+                            <option key={`${s.name}/${m.name}`} value={`${s.name}/${m.name}`} disabled={m.client_streaming}>
+                                {s.name}/{m.name}{m.server_streaming ? ' (stream)' : ''}
+                            </option>
+                        )))}
+                    </select>
+                    <label style={{ ...labelStyle, display: 'flex', alignItems: 'center', gap: '4px' }}>
+                        <input type="checkbox" checked={web.value} onChange={(e) => web.value = e.currentTarget.checked} />
+                        gRPC-Web
+                    </label>
+                    {isCalling.value
+                        ? <button onClick={handleCancel} style={buttonStyle(false, true)}>{t('common.cancel')}</button>
+                        : <button onClick={handleCall} disabled={!selected.value} style={buttonStyle(true)}>{t('tools.grpc.callBtn')}</button>}
+                </div>
+                <div style={{ height: '160px', border: '1px solid var(--border-color)', borderRadius: 'var(--radius-sm)', overflow: 'hidden' }}>
+                    <CodeEditor value={message.value} onChange={(v) => message.value = v} language="json" />
+                </div>
+                <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{status.value}</span>
+                <div style={logStyle}>
+                    {streamed.value.map((m, i) => <div key={i}>{JSON.stringify(m, null, 2)}</div>)}
+                </div>
+            </div>
+        </Modal>
+    );
+}
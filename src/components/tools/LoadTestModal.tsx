@@ -0,0 +1,141 @@
+import { useSignal } from "@preact/signals";
+import { useEffect, useRef } from "preact/hooks";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { activeProjectName } from "../../store";
+import { ExecutionOverrides, prepareExecutionArgs } from "../../utils/execution";
+import { Modal } from "../Modal";
+import { buttonStyle, inputStyle, labelStyle } from "./toolStyles";
+import { t } from "../../i18n";
+
+interface LoadTestProgress {
+    completed: number;
+    failed: number;
+    active_workers: number;
+    elapsed_ms: number;
+    throughput: number;
+}
+
+interface LoadTestSummary {
+    total_requests: number;
+    successful: number;
+    failed: number;
+    cancelled: boolean;
+    duration_ms: number;
+    throughput: number;
+    latency: { min: number, mean: number, p50: number, p90: number, p99: number, max: number };
+    status_histogram: Record<string, number>;
+    errors: Record<string, number>;
+}
+
+interface LoadTestModalProps {
+    isOpen: boolean;
+    onClose: () => void;
+    executionId: string;
+    getOverrides: () => ExecutionOverrides;
+}
+
+const optionalNumber = (value: string) => value === '' ? undefined : Number(value);
+
+export function LoadTestModal({ isOpen, onClose, executionId, getOverrides }: LoadTestModalProps) {
+    const concurrency = useSignal('10');
+    const totalRequests = useSignal('100');
+    const durationSecs = useSignal('');
+    const rampUpSecs = useSignal('');
+    const progress = useSignal<LoadTestProgress | null>(null);
+    const summary = useSignal<LoadTestSummary | null>(null);
+    const error = useSignal<string | null>(null);
+    const isRunning = useSignal(false);
+    const requestId = useRef<string | null>(null);
+
+    useEffect(() => {
+        const unlisten = listen<LoadTestProgress & { request_id: string }>('load-test-progress', (e) => {
+            if (e.payload.request_id === requestId.current) progress.value = e.payload;
+        });
+        return () => { unlisten.then(f => f()); };
+    }, []);
+
+    const handleRun = async () => {
+        const request = prepareExecutionArgs(executionId, getOverrides());
+        if (!request) return;
+        const id = crypto.randomUUID();
+        requestId.current = id;
+        progress.value = null;
+        summary.value = null;
+        error.value = null;
+        isRunning.value = true;
+        try {
+            summary.value = await invoke<LoadTestSummary>('load_test', {
+                args: {
+                    request: { ...request, project_name: activeProjectName.peek() },
+                    concurrency: Number(concurrency.peek()) || 1,
+                    total_requests: optionalNumber(totalRequests.peek()),
+                    duration_secs: optionalNumber(durationSecs.peek()),
+                    ramp_up_secs: optionalNumber(rampUpSecs.peek()),
+                    request_id: id
+                }
+            });
+        } catch (e) {
+            error.value = String(e);
+        } finally {
+            isRunning.value = false;
+        }
+    };
+
+    const handleStop = () => {
+        if (requestId.current && isRunning.peek()) invoke('cancel_http_request', { requestId: requestId.current });
+    };
+
+    const field = (label: string, value: typeof concurrency, placeholder?: string) => (
+        <div style={{ display: 'flex', flexDirection: 'column', gap: '4px', flex: 1 }}>
+            <label style={labelStyle}>{label}</label>
+            <input type="number" min={0} value={value.value} placeholder={placeholder} onInput={(e) => value.value = e.currentTarget.value} style={inputStyle} />
+        </div>
+    );
+
+    const s = summary.value;
+    const ms = (v: number) => `${v.toFixed(1)} ms`;
+
+    return (
+        <Modal isOpen={isOpen} onClose={() => { handleStop(); onClose(); }} title={t('tools.load.title')} width="640px">
+            <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                <div style={{ display: 'flex', gap: '12px' }}>
+                    {field(t('tools.load.concurrency'), concurrency)}
+                    {field(t('tools.load.totalRequests'), totalRequests, t('tools.load.unbounded'))}
+                    {field(t('tools.load.durationSecs'), durationSecs, t('tools.load.unbounded'))}
+                    {field(t('tools.load.rampUpSecs'), rampUpSecs, '0')}
+                </div>
+                <div style={{ display: 'flex', gap: '8px', alignItems: 'center' }}>
+                    {isRunning.value
+                        ? <button onClick={handleStop} style={buttonStyle(false, true)}>{t('tools.load.stopBtn')}</button>
+                        : <button onClick={handleRun} style={buttonStyle(true)}>{t('tools.load.runBtn')}</button>}
+                    {progress.value && (
+                        <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>
+                            {t('tools.load.progress', {
+                                completed: progress.value.completed,
+                                failed: progress.value.failed,
+                                workers: progress.value.active_workers,
+                                rps: progress.value.throughput.toFixed(1)
+                            })}
+                        </span>
+                    )}
+                </div>
+                {error.value && <div style={{ color: 'var(--error)', fontSize: '0.85rem' }}>{error.value}</div>}
+                {s && (
+                    <table style={{ fontSize: '0.85rem', borderCollapse: 'collapse' }}>
+                        <tbody>
+                            <tr><td style={labelStyle}>{t('tools.load.requests')}</td><td>{s.total_requests} ({s.successful} ok, {s.failed} failed){s.cancelled ? ` — ${t('tools.load.cancelled')}` : ''}</td></tr>
+                            <tr><td style={labelStyle}>{t('tools.load.duration')}</td><td>{(s.duration_ms / 1000).toFixed(1)} s</td></tr>
+                            <tr><td style={labelStyle}>{t('tools.load.throughput')}</td><td>{s.throughput.toFixed(1)} req/s</td></tr>
+                            <tr><td style={labelStyle}>{t('tools.load.latency')}</td><td>min {ms(s.latency.min)} · p50 {ms(s.latency.p50)} · p90 {ms(s.latency.p90)} · p99 {ms(s.latency.p99)} · max {ms(s.latency.max)}</td></tr>
+                            <tr><td style={labelStyle}>{t('tools.load.statuses')}</td><td>{Object.entries(s.status_histogram).map(([code, count]) => `${code}: ${count}`).join(', ') || '-'}</td></tr>
+                            {Object.keys(s.errors).length > 0 && (
+                                <tr><td style={labelStyle}>{t('tools.load.errors')}</td><td>{Object.entries(s.errors).map(([msg, count]) => `${msg} (${count})`).join(', ')}</td></tr>
+                            )}
+                        </tbody>
+                    </table>
+                )}
+            </div>
+        </Modal>
+    );
+}
@@ -0,0 +1,90 @@
+import { useSignal } from "@preact/signals";
+import { useEffect, useRef } from "preact/hooks";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { activeProjectName } from "../../store";
+import { ExecutionOverrides, prepareExecutionArgs } from "../../utils/execution";
+import { Modal } from "../Modal";
+import { buttonStyle, logStyle } from "./toolStyles";
+import { t } from "../../i18n";
+
+interface SseEvent {
+    id: string | null;
+    event: string;
+    data: string;
+    elapsed_ms: number;
+}
+
+interface SseModalProps {
+    isOpen: boolean;
+    onClose: () => void;
+    executionId: string;
+    getOverrides: () => ExecutionOverrides;
+}
+
+export function SseModal({ isOpen, onClose, executionId, getOverrides }: SseModalProps) {
+    const events = useSignal<SseEvent[]>([]);
+    const status = useSignal<string>('');
+    const isStreaming = useSignal(false);
+    const requestId = useRef<string | null>(null);
+
+    useEffect(() => {
+        const unlisten = listen<{ request_id: string, event: SseEvent }>('sse-event', (e) => {
+            if (e.payload.request_id === requestId.current) {
+                events.value = [...events.value, e.payload.event];
+            }
+        });
+        return () => { unlisten.then(f => f()); };
+    }, []);
+
+    const handleOpen = async () => {
+        const args = prepareExecutionArgs(executionId, getOverrides());
+        if (!args) return;
+        const id = crypto.randomUUID();
+        requestId.current = id;
+        events.value = [];
+        status.value = t('tools.sse.connecting');
+        isStreaming.value = true;
+        try {
+            const res = await invoke<{ status: number, body: string, closed_by: string, error: string | null }>('sse_request', {
+                args: { ...args, request_id: id, project_name: activeProjectName.peek() }
+            });
+            status.value = res.error
+                ? t('tools.sse.interrupted', { status: res.status, error: res.error })
+                : t('tools.sse.closed', { status: res.status, by: res.closed_by });
+            if (res.body) events.value = [...events.value, { id: null, event: 'body', data: res.body, elapsed_ms: 0 }];
+        } catch (e) {
+            status.value = `Error: ${e}`;
+        } finally {
+            isStreaming.value = false;
+        }
+    };
+
+    const handleClose = () => {
+        if (requestId.current) invoke('close_sse_stream', { requestId: requestId.current });
+    };
+
+    return (
+        <Modal isOpen={isOpen} onClose={() => { handleClose(); onClose(); }} title={t('tools.sse.title')} width="720px">
+            <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                <div style={{ display: 'flex', gap: '8px', alignItems: 'center' }}>
+                    {isStreaming.value
+                        ? <button onClick={handleClose} style={buttonStyle(false, true)}>{t('tools.sse.closeBtn')}</button>
+                        : <button onClick={handleOpen} style={buttonStyle(true)}>{t('tools.sse.openBtn')}</button>}
+                    <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{status.value}</span>
+                </div>
+                <div style={logStyle}>
+                    {events.value.length === 0 && <span style={{ color: 'var(--text-muted)' }}>{t('tools.sse.noEvents')}</span>}
+                    {events.value.map((ev, i) => (
+                        <div key={i} style={{ marginBottom: '6px' }}>
+                            <span style={{ color: 'var(--text-muted)' }}>+{ev.elapsed_ms}ms </span>
+                            <strong style={{ color: 'var(--accent-primary)' }}>{ev.event}</strong>
+                            {ev.id && <span style={{ color: 'var(--text-muted)' }}> #{ev.id}</span>}
+                            <div>{ev.data}</div>
+                        </div>
+                    ))}
+                </div>
+            </div>
+        </Modal>
+    );
+}
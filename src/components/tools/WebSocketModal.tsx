@@ -0,0 +1,196 @@
+import { useSignal } from "@preact/signals";
+import { useEffect, useRef } from "preact/hooks";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { activeProjectName, executions } from "../../store";
+import { ExecutionOverrides, prepareExecutionArgs } from "../../utils/execution";
+import { Modal } from "../Modal";
+import { buttonStyle, inputStyle, logStyle } from "./toolStyles";
+import { t } from "../../i18n";
+
+interface WsMessage {
+    direction: 'sent' | 'received';
+    kind: string;
+    data: string;
+    close_code?: number | null;
+    elapsed_ms: number;
+}
+
+interface WsConnectResponse {
+    status: number;
+    headers: string[][];
+    request_raw: string;
+    request_curl: string;
+}
+
+interface WebSocketModalProps {
+    isOpen: boolean;
+    onClose: () => void;
+    executionId: string;
+    getOverrides: () => ExecutionOverrides;
+}
+
+export function WebSocketModal({ isOpen, onClose, executionId, getOverrides }: WebSocketModalProps) {
+    const url = useSignal('');
+    const messages = useSignal<WsMessage[]>([]);
+    const draft = useSignal('');
+    const status = useSignal('');
+    const isConnected = useSignal(false);
+    const sessionId = useRef<string | null>(null);
+    const handshake = useRef<WsConnectResponse | null>(null);
+    // Transcript of a session the server closed before ws_connect returned
+    const earlyTranscript = useRef<WsMessage[] | null>(null);
+
+    // Start from the execution's URL with its scheme switched to ws(s)
+    useEffect(() => {
+        if (!isOpen) return;
+        const args = prepareExecutionArgs(executionId, getOverrides());
+        if (args && !url.peek()) url.value = args.url.replace(/^http/, 'ws');
+    }, [isOpen, executionId]);
+
+    useEffect(() => {
+        const unlistenMessage = listen<{ session_id: string, message: WsMessage }>('ws-message', (e) => {
+            if (e.payload.session_id === sessionId.current) {
+                messages.value = [...messages.value, e.payload.message];
+            }
+        });
+        const unlistenClosed = listen<{ session_id: string, code: number | null, reason: string, error: string | null, transcript: WsMessage[] }>('ws-closed', (e) => {
+            if (e.payload.session_id !== sessionId.current) return;
+            isConnected.value = false;
+            messages.value = e.payload.transcript;
+            status.value = e.payload.error
+                ? `Error: ${e.payload.error}`
+                : t('tools.ws.closed', { code: e.payload.code ?? '-', reason: e.payload.reason });
+            saveTranscript(e.payload.transcript);
+        });
+        return () => {
+            unlistenMessage.then(f => f());
+            unlistenClosed.then(f => f());
+        };
+    }, []);
+
+    // Keeps the handshake and the transcript as the execution's last response
+    const saveTranscript = (transcript: WsMessage[]) => {
+        const res = handshake.current;
+        if (!res) {
+            earlyTranscript.current = transcript;
+            return;
+        }
+        const body = JSON.stringify(transcript, null, 2);
+        executions.value = executions.peek().map(e =>
+            e.id === executionId ? {
+                ...e,
+                lastResponse: {
+                    status: res.status,
+                    headers: res.headers,
+                    body,
+                    time: transcript.length ? transcript[transcript.length - 1].elapsed_ms : 0,
+                    size: new TextEncoder().encode(body).length,
+                    requestUrl: url.peek(),
+                    requestMethod: 'GET',
+                    requestRaw: res.request_raw,
+                    requestCurl: res.request_curl,
+                },
+                resultsVisible: true
+            } : e
+        );
+    };
+
+    const handleConnect = async () => {
+        const args = prepareExecutionArgs(executionId, getOverrides());
+        if (!args) return;
+        const id = crypto.randomUUID();
+        sessionId.current = id;
+        messages.value = [];
+        status.value = t('tools.ws.connecting');
+        try {
+            handshake.current = null;
+            earlyTranscript.current = null;
+            const res = await invoke<WsConnectResponse>('ws_connect', {
+                args: {
+                    session_id: id,
+                    url: url.peek(),
+                    headers: args.headers,
+                    auth: args.auth,
+                    project_name: activeProjectName.peek()
+                }
+            });
+            handshake.current = res;
+            if (earlyTranscript.current) {
+                saveTranscript(earlyTranscript.current);
+                return;
+            }
+            isConnected.value = true;
+            status.value = t('tools.ws.connected', { status: res.status });
+        } catch (e) {
+            status.value = `Error: ${e}`;
+        }
+    };
+
+    const handleDisconnect = async () => {
+        if (!sessionId.current || !isConnected.peek()) return;
+        try {
+            const transcript = await invoke<WsMessage[]>('ws_close', { sessionId: sessionId.current });
+            messages.value = transcript;
+            saveTranscript(transcript);
+        } catch (e) {
+            status.value = `Error: ${e}`;
+        }
+        isConnected.value = false;
+    };
+
+    const handleSend = async () => {
+        if (!sessionId.current) return;
+        try {
+            await invoke('ws_send', { sessionId: sessionId.current, data: draft.peek() });
+            draft.value = '';
+        } catch (e) {
+            status.value = `Error: ${e}`;
+        }
+    };
+
+    const handlePing = () => {
+        if (sessionId.current) invoke('ws_ping', { sessionId: sessionId.current }).catch(e => status.value = `Error: ${e}`);
+    };
+
+    return (
+        <Modal isOpen={isOpen} onClose={() => { handleDisconnect(); onClose(); }} title={t('tools.ws.title')} width="720px">
+            <div style={{ display: 'flex', flexDirection: 'column', gap: '12px' }}>
+                <div style={{ display: 'flex', gap: '8px' }}>
+                    <input
+                        value={url.value}
+                        onInput={(e) => url.value = e.currentTarget.value}
+                        placeholder="wss://example.com/socket"
+                        readOnly={isConnected.value}
+                        style={{ ...inputStyle, flex: 1 }}
+                    />
+                    {isConnected.value
+                        ? <button onClick={handleDisconnect} style={buttonStyle(false, true)}>{t('tools.ws.disconnectBtn')}</button>
+                        : <button onClick={handleConnect} style={buttonStyle(true)}>{t('tools.ws.connectBtn')}</button>}
+                </div>
+                <span style={{ fontSize: '0.8rem', color: 'var(--text-muted)' }}>{status.value}</span>
+                <div style={logStyle}>
+                    {messages.value.map((m, i) => (
+                        <div key={i} style={{ marginBottom: '4px', color: m.direction === 'sent' ? 'var(--text-secondary)' : 'var(--text-primary)' }}>
+                            <span style={{ color: 'var(--text-muted)' }}>+{m.elapsed_ms}ms </span>
+                            {m.direction === 'sent' ? '↑' : '↓'} <strong>{m.kind}</strong>{m.close_code != null ? ` ${m.close_code}` : ''} {m.data}
+                        </div>
+                    ))}
+                </div>
+                <div style={{ display: 'flex', gap: '8px' }}>
+                    <textarea
+                        value={draft.value}
+                        onInput={(e) => draft.value = e.currentTarget.value}
+                        placeholder={t('tools.ws.messagePlaceholder')}
+                        rows={3}
+                        style={{ ...inputStyle, flex: 1, fontFamily: 'var(--font-mono)', resize: 'vertical' }}
+                    />
+                    <div style={{ display: 'flex', flexDirection: 'column', gap: '6px' }}>
+                        <button onClick={handleSend} disabled={!isConnected.value} style={buttonStyle(true)}>{t('tools.ws.sendBtn')}</button>
+                        <button onClick={handlePing} disabled={!isConnected.value} style={buttonStyle(false)}>{t('tools.ws.pingBtn')}</button>
+                    </div>
+                </div>
+            </div>
+        </Modal>
+    );
+}
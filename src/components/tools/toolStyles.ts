@@ -0,0 +1,35 @@
+// Shared look of the protocol and runner panels
+export const labelStyle = { fontSize: '0.8rem', color: 'var(--text-muted)' };
+
+export const inputStyle = {
+    padding: '6px 8px',
+    backgroundColor: 'var(--bg-input)',
+    border: '1px solid var(--border-color)',
+    borderRadius: 'var(--radius-sm)',
+    color: 'var(--text-primary)',
+    outline: 'none'
+};
+
+export const buttonStyle = (primary: boolean, danger = false) => ({
+    padding: '6px 14px',
+    backgroundColor: danger ? 'var(--error)' : primary ? 'var(--accent-primary)' : 'transparent',
+    color: primary || danger ? 'white' : 'var(--text-primary)',
+    border: primary || danger ? 'none' : '1px solid var(--border-color)',
+    borderRadius: 'var(--radius-sm)',
+    cursor: 'pointer',
+    fontWeight: 'bold' as const
+});
+
+export const logStyle = {
+    fontFamily: 'var(--font-mono)',
+    fontSize: '0.8rem',
+    backgroundColor: 'var(--bg-input)',
+    border: '1px solid var(--border-color)',
+    borderRadius: 'var(--radius-sm)',
+    padding: '8px',
+    minHeight: '160px',
+    maxHeight: '320px',
+    overflowY: 'auto' as const,
+    whiteSpace: 'pre-wrap' as const,
+    wordBreak: 'break-all' as const
+};
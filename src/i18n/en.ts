@@ -103,6 +103,7 @@ export const en = {
         addRequest: "Add Request",
         addFolder: "Add Folder",
         newExecution: "New Execution",
+        runInBackend: "Run All Executions",
         closeOthers: "Close Others",
         closeAll: "Close All"
     },
@@ -206,7 +207,10 @@ export const en = {
         defaultExecution: "Default Execution",
         noResults: "No results found",
         requestsMatched: "{{count}} requests matched",
-        escToClose: "ESC to close"
+        escToClose: "ESC to close",
+        retryTitle: "Retry Policy",
+        retryAttempts: "up to {{count}} attempts",
+        editRetryBtn: "Edit Retries"
     },
     requestEditor: {
         settingsTitle: "Request Settings",
@@ -217,7 +221,8 @@ export const en = {
             auth: "Auth",
             scripts: "Scripts",
             preRequest: "Pre-request",
-            postRequest: "Post-request"
+            postRequest: "Post-request",
+            retry: "Retry"
         },
         body: {
             types: {
@@ -229,7 +234,8 @@ export const en = {
                 multipart: "Multipart Form",
                 text: "Text",
                 javascript: "Javascript",
-                yaml: "YAML"
+                yaml: "YAML",
+                graphql: "GraphQL"
             },
             typeColumn: "Type",
             valuesColumn: "Values",
@@ -243,7 +249,11 @@ export const en = {
             addField: "+ Add Field",
             bodyOverridden: "Body overridden",
             enterBodyPlaceholder: "Enter {{type}} body...",
-            noBodyText: "This request has no body"
+            noBodyText: "This request has no body",
+            graphqlOperationName: "Operation name",
+            graphqlOperationNamePlaceholder: "Operation to run, when the query has several",
+            graphqlVariables: "Variables",
+            graphqlPersisted: "Persisted query (send the query hash first)"
         },
         headers: {
             keyPlaceholder: "Header Key",
@@ -265,6 +275,18 @@ export const en = {
             runOnStatus: "Run on Status:",
             runOnStatusPlaceholder: "e.g. 200, 201, 2xx, 4xx (Empty = Always)"
         },
+        retry: {
+            enabled: "Retry failed requests",
+            max_attempts: "Max attempts",
+            status_codes: "Retry on status",
+            error_kinds: "Retry on errors",
+            initial_delay_ms: "Initial delay (ms)",
+            multiplier: "Backoff multiplier",
+            max_delay_ms: "Max delay (ms)",
+            jitter: "Randomize delays (jitter)",
+            respect_retry_after: "Honor Retry-After",
+            idempotent_only: "Retry errors of idempotent methods only (connect errors always retry)"
+        },
         namePlaceholder: "Request Name",
         previewUrl: "Preview: {{url}}",
         cancelBtn: "Cancel",
@@ -304,7 +326,73 @@ export const en = {
         basedOn: "based on:",
         executionOverrides: "Execution Overrides",
         runBtn: "Run",
-        cancelBtn: "Cancel"
+        cancelBtn: "Cancel",
+        tools: {
+            sse: "SSE Stream",
+            ws: "WebSocket",
+            grpc: "gRPC",
+            load: "Load Test"
+        }
+    },
+    tools: {
+        sse: {
+            title: "Server-Sent Events",
+            openBtn: "Open Stream",
+            closeBtn: "Close Stream",
+            connecting: "Connecting...",
+            closed: "Stream ended (status {{status}}, closed by {{by}})",
+            interrupted: "Stream interrupted (status {{status}}): {{error}}",
+            noEvents: "No events received yet."
+        },
+        ws: {
+            title: "WebSocket",
+            connectBtn: "Connect",
+            disconnectBtn: "Disconnect",
+            connecting: "Connecting...",
+            connected: "Connected (status {{status}})",
+            closed: "Connection closed ({{code}}) {{reason}}",
+            messagePlaceholder: "Message to send",
+            sendBtn: "Send",
+            pingBtn: "Ping"
+        },
+        grpc: {
+            title: "gRPC",
+            loadServicesBtn: "Load Services",
+            protoFiles: "Proto files",
+            protoFilesPlaceholder: "Comma-separated .proto paths, empty to use server reflection",
+            selectMethod: "Select a method...",
+            loadingServices: "Loading services...",
+            servicesLoaded: "{{count}} services loaded",
+            calling: "Calling...",
+            callBtn: "Call"
+        },
+        load: {
+            title: "Load Test",
+            concurrency: "Concurrency",
+            totalRequests: "Total requests",
+            durationSecs: "Duration (s)",
+            rampUpSecs: "Ramp-up (s)",
+            unbounded: "No limit",
+            runBtn: "Start",
+            stopBtn: "Stop",
+            progress: "{{completed}} done, {{failed}} failed, {{workers}} workers, {{rps}} req/s",
+            requests: "Requests",
+            duration: "Duration",
+            throughput: "Throughput",
+            latency: "Latency",
+            statuses: "Statuses",
+            errors: "Errors",
+            cancelled: "stopped early"
+        },
+        batch: {
+            title: "Run {{name}}",
+            concurrency: "Concurrency",
+            stopOnFailure: "Stop on first failure",
+            runBtn: "Run {{count}} executions",
+            stopBtn: "Stop",
+            summary: "{{passed}} passed, {{failed}} failed, {{skipped}} skipped in {{seconds}} s",
+            noExecutions: "No executions in here."
+        }
     },
     codeEditor: {
         formatTooltip: "Format Code",
@@ -44,7 +44,8 @@ export const syncProjectManifest = async (projectName: string) => {
                             variable_name: er.variableName
                         })),
                         success_codes: s.successCodes,
-                        script: s.script
+                        script: s.script,
+                        retry: s.retry
                     }))
                 })),
                 openTabs: openTabs.peek(),
@@ -64,6 +65,10 @@ export const syncProjectManifest = async (projectName: string) => {
 
 export const openProject = async (name: string) => {
     try {
+        // Mock servers belong to the project being left
+        await invoke('stop_all_mock_servers', { remove: true }).catch(err => console.error('Failed to stop mock servers:', err));
+        externalMocks.value = externalMocks.peek().map(m => ({ ...m, serverStatus: 'stopped' as const, runningPort: undefined }));
+
         const manifest = await invoke<any>('get_project_manifest', { name });
         activeProjectName.value = manifest.name;
 
@@ -96,7 +101,8 @@ export const openProject = async (name: string) => {
                         variableName: er.variable_name || er.variableName
                     })),
                     successCodes: s.success_codes || s.successCodes || "2xx",
-                    script: s.script || ""
+                    script: s.script || "",
+                    retry: s.retry || undefined
                 }))
             }));
         });
@@ -190,6 +196,7 @@ export const saveExternalMockToDisk = async (id: string, saveAs: boolean = false
             const dataToSave = { ...mock };
             delete (dataToSave as any).path;
             delete (dataToSave as any).serverStatus;
+            delete (dataToSave as any).runningPort;
             
             await invoke('save_workspace', { path, data: JSON.stringify(dataToSave, null, 2) });
             
@@ -240,7 +247,7 @@ export const saveCollectionToDisk = async (collectionId: string, saveAs: boolean
                 environments: environments.value
             };
 
-            await invoke('save_workspace', { path, data: JSON.stringify(data, null, 2) });
+            await invoke('save_collection', { path, data: JSON.stringify(data, null, 2) });
 
             batch(() => {
                 collections.value = collections.value.map(c => c.id === collectionId ? { ...c, path: path! } : c);
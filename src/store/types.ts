@@ -11,12 +11,64 @@ export interface InheritedRow extends TableRow {
     sourceId?: string;
 }
 
-export type AuthType = 'none' | 'inherit' | 'basic' | 'bearer';
+export type AuthType = 'none' | 'inherit' | 'basic' | 'bearer' | 'oauth2' | 'digest' | 'aws_sigv4' | 'api_key' | 'hmac';
+
+export type OAuth2GrantType = 'client_credentials' | 'password' | 'authorization_code' | 'device_code' | 'refresh_token';
+
+// Field names match the Rust `OAuth2Config` since the object is forwarded to the backend as-is.
+export interface OAuth2Config {
+    grant_type: OAuth2GrantType;
+    token_url: string;
+    auth_url?: string;
+    device_auth_url?: string;
+    client_id: string;
+    client_secret?: string;
+    scope?: string;
+    username?: string;
+    password?: string;
+    refresh_token?: string;
+    redirect_port?: number;
+    client_auth?: 'body' | 'basic';
+    extra_params?: string[][];
+}
+
+export interface AwsSigV4Config {
+    access_key_id: string;
+    secret_access_key: string;
+    session_token?: string;
+    region: string;
+    service: string;
+    unsigned_payload?: boolean;
+}
+
+export interface ApiKeyConfig {
+    key: string;
+    value: string;
+    location?: 'header' | 'query';
+}
+
+export interface HmacConfig {
+    secret: string;
+    key_id?: string;
+    algorithm?: 'sha1' | 'sha256' | 'sha512';
+    encoding?: 'hex' | 'base64';
+    template?: string;
+    header?: string;
+    header_value?: string;
+    timestamp_header?: string;
+    timestamp_format?: 'unix' | 'unix_ms' | 'iso8601';
+    nonce_header?: string;
+}
 
 export interface AuthConfig {
     type: AuthType;
     basic?: { username: string; password: string };
     bearer?: { token: string };
+    oauth2?: OAuth2Config;
+    digest?: { username: string; password: string };
+    aws_sigv4?: AwsSigV4Config;
+    api_key?: ApiKeyConfig;
+    hmac?: HmacConfig;
 }
 
 export interface ScriptItem {
@@ -32,6 +84,32 @@ export interface MockResponse {
     headers: TableRow[];
     body: string;
     enabled: boolean;
+    /** Render `{{...}}` templates in the body and header values */
+    templated?: boolean;
+    rules?: MockResponseRule[];
+}
+
+/** Request conditions of a mock rule; all of them must hold. */
+export interface MockMatch {
+    /** Without `equals`/`regex` the header only has to be present */
+    headers?: { name: string; equals?: string; regex?: string }[];
+    /** Without `equals` the parameter only has to be present */
+    query?: { name: string; equals?: string }[];
+    json?: { path: string; equals: any }[];
+    bodyRegex?: string;
+}
+
+/** Alternative mock response, tried by descending priority before the default one. */
+export interface MockResponseRule {
+    name?: string;
+    priority?: number;
+    when: MockMatch;
+    response: {
+        statusCode: number;
+        headers: TableRow[];
+        body: string;
+        templated?: boolean;
+    };
 }
 
 export interface ResponseData {
@@ -40,6 +118,16 @@ export interface ResponseData {
     body: string;
     time?: number;
     size?: number;
+    wireSize?: number;
+    contentEncoding?: string;
+    /** Set when the body could not be decompressed and is shown as received */
+    decodeWarning?: string;
+    charset?: string;
+    httpVersion?: string;
+    /** Address actually connected to, e.g. "203.0.113.7:443" */
+    remoteAddress?: string;
+    /** Tries made under a retry policy */
+    attempts?: RetryAttempt[];
     requestRaw?: string;
     requestCurl?: string;
     requestUrl?: string;
@@ -48,6 +136,14 @@ export interface ResponseData {
 
 // --- Request/Folders ---
 
+/** Extra fields of the 'graphql' body type; the query itself lives in `body`. */
+export interface GraphQLOptions {
+    variables?: string;
+    operationName?: string;
+    /** Send the query hash first (automatic persisted queries), as GET for queries and POST for mutations */
+    persisted?: boolean;
+}
+
 export interface RequestItem {
     id: string;
     collectionId: string;
@@ -55,8 +151,9 @@ export interface RequestItem {
     method: string;
     url: string;
     headers: TableRow[];
-    bodyType?: 'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml';
+    bodyType?: 'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql';
     body?: string;
+    graphql?: GraphQLOptions;
     formData?: { key: string, type: 'text' | 'file', values: string[], contentTypes?: string[], enabled: boolean }[];
     preScripts?: ScriptItem[];
     postScripts?: ScriptItem[];
@@ -91,12 +188,14 @@ export interface ExecutionItem {
     headers?: TableRow[];
     queryParams?: TableRow[];
     pathParams?: Record<string, string>;
-    bodyType?: 'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml';
+    bodyType?: 'none' | 'json' | 'xml' | 'html' | 'form_urlencoded' | 'multipart' | 'text' | 'javascript' | 'yaml' | 'graphql';
     body?: string;
+    graphql?: GraphQLOptions;
     formData?: { key: string, type: 'text' | 'file', values: string[], contentTypes?: string[], enabled: boolean }[];
     auth?: AuthConfig;
     preScripts?: ScriptItem[];
     postScripts?: ScriptItem[];
+    retry?: RetryPolicy;
     lastResponse?: ResponseData;
     resultsVisible?: boolean;
     sortIndex?: number;
@@ -116,8 +215,13 @@ export interface Collection {
     name: string;
     path?: string;
     mockConfig?: {
+        /** 0 picks a free port */
         port: number;
         enabled: boolean;
+        /** Defaults to 127.0.0.1; 0.0.0.0 exposes the mock to the network */
+        bindAddress?: string;
+        /** Port the running server actually listens on */
+        runningPort?: number;
     };
 }
 
@@ -140,18 +244,127 @@ export interface ExternalMockEndpoint {
         headers: TableRow[];
         body: string;
         enabled: boolean;
+        /** Render `{{...}}` templates in the body and header values */
+        templated?: boolean;
     };
+    rules?: MockResponseRule[];
+    /** Responses returned one per call when no rule matches */
+    sequence?: MockSequenceResponse[];
+    /** 'stick' (default) repeats the last response, 'cycle' starts over */
+    sequenceMode?: 'stick' | 'cycle';
+    /** Scenario name; scenarios start in "Started" */
+    scenario?: string;
+    requiredState?: string;
+    newState?: string;
+    /** Overrides the mock's faults field by field */
+    faults?: MockFaults;
+}
+
+/** Latency and failures injected by a mock server */
+export interface MockFaults {
+    /** Fixed delay, or the lower bound when delayMaxMs is set */
+    delayMs?: number;
+    delayMaxMs?: number;
+    /** Percentage of responses replaced by errorStatus (default 500) */
+    errorRate?: number;
+    errorStatus?: number;
+    connectionFault?: 'reset' | 'empty';
+    /** Percentage of requests hit by connectionFault, default 100 */
+    connectionFaultRate?: number;
+    truncateBodyBytes?: number;
+    bandwidthBytesPerSec?: number;
+}
+
+export interface MockSequenceResponse {
+    statusCode: number;
+    headers: TableRow[];
+    body: string;
+    templated?: boolean;
 }
 
 export interface ExternalMock {
     id: string;
     name: string;
+    /** 0 picks a free port */
     port: number;
+    /** Defaults to 127.0.0.1; 0.0.0.0 exposes the mock to the network */
+    bindAddress?: string;
+    /** Port the running server actually listens on */
+    runningPort?: number;
     path?: string;
     endpoints: ExternalMockEndpoint[];
+    resources?: MockResource[];
+    faults?: MockFaults;
+    /** Forward unmatched requests to a real service and record them */
+    proxy?: MockProxyConfig;
+    /** Serve HTTPS; without certFile/keyFile the certificate comes from the local mock CA */
+    tls?: MockTlsConfig;
     serverStatus: 'running' | 'stopped';
 }
 
+export interface MockProxyConfig {
+    /** Base URL the request path is appended to */
+    upstream: string;
+    /** Defaults to true */
+    record?: boolean;
+}
+
+export interface MockTlsConfig {
+    enabled: boolean;
+    certFile?: string;
+    keyFile?: string;
+    /** Extra host names or IPs for the generated certificate */
+    hosts?: string[];
+}
+
+/** A mock server as returned by `list_mock_servers` */
+export interface MockServerSummary {
+    collection_id: string;
+    name: string | null;
+    /** "host:port" */
+    address: string;
+    https: boolean;
+    /** Milliseconds since the Unix epoch */
+    started_at: number;
+    /** 'crashed' when the server task ended without being stopped */
+    status: 'running' | 'stopped' | 'crashed';
+    uptime_ms: number;
+    requests: number;
+    matched: number;
+    unmatched: number;
+    faults: number;
+    last_error: string | null;
+}
+
+/** A request received by a running mock server, from `get_mock_journal` or `mock-request` events */
+export interface MockJournalEntry {
+    id: number;
+    collection_id: string;
+    /** Milliseconds since the Unix epoch */
+    timestamp: number;
+    method: string;
+    uri: string;
+    headers: string[][];
+    body: string;
+    /** Matched endpoint ("GET /pets/{id}") or resource; null for a 404 */
+    matched: string | null;
+    /** null when the connection was dropped by an injected fault */
+    status: number | null;
+    fault: string | null;
+    latency_ms: number;
+}
+
+/** In-memory CRUD collection served under `basePath` */
+export interface MockResource {
+    name?: string;
+    basePath: string;
+    /** Defaults to "id" */
+    idField?: string;
+    seed?: any[];
+    /** JSON file with an array of initial items */
+    seedFile?: string;
+}
+
 // --- Execution & UseCases ---
 
 export interface ExecutionStep {
@@ -179,12 +392,38 @@ export interface ExtractionRule {
     variableName: string;
 }
 
+/** Backend retry policy; omitted fields use the backend defaults. */
+export interface RetryPolicy {
+    max_attempts?: number;
+    /** e.g. "429,502,503,504" or "5xx" */
+    status_codes?: string;
+    /** "connect", "timeout", "reset", "request", "body" */
+    error_kinds?: string[];
+    initial_delay_ms?: number;
+    multiplier?: number;
+    max_delay_ms?: number;
+    jitter?: boolean;
+    respect_retry_after?: boolean;
+    /** Retry transport errors of POST/PATCH requests only when false (default true) */
+    idempotent_only?: boolean;
+}
+
+export interface RetryAttempt {
+    attempt: number;
+    status: number | null;
+    error: string | null;
+    error_kind: string | null;
+    time_ms: number;
+    delay_ms: number;
+}
+
 export interface UseCaseStep {
     id: string;
     executionId: string;
     extractionRules: ExtractionRule[];
     successCodes: string;
     script?: string;
+    retry?: RetryPolicy;
 }
 
 export interface UseCase {
@@ -25,6 +25,7 @@ export const expandedCollectionIds = signal<string[]>([]);
 export const expandedFolderIds = signal<string[]>([]);
 export const isExternalMocksExpanded = signal(false);
 export const isAboutOpen = signal<boolean>(false);
+export const batchRunTarget = signal<{ kind: 'folder' | 'collection', id: string, name: string } | null>(null);
 export const confirmationState = signal<{
     isOpen: boolean,
     title: string,
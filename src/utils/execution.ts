@@ -3,7 +3,7 @@ import {
     executions, requests, folders, environments, 
     activeEnvName, activeProjectName, 
     addLog, ExecutionProgressState, executionProgressMap,
-    ResponseData, resolveHeaders, resolveAuth, AuthConfig, ScriptItem, TableRow
+    ResponseData, resolveHeaders, resolveAuth, AuthConfig, ExecutionItem, GraphQLOptions, RequestItem, RetryAttempt, RetryPolicy, ScriptItem, TableRow
 } from '../store';
 
 /**
@@ -98,6 +98,18 @@ export const substituteVariables = (text: string, requestId: string, extraVars?:
     return result;
 };
 
+/**
+ * Substitute variables in every string field of a backend auth config (nested string arrays included)
+ */
+export const substituteAuthFields = <T extends object>(config: T, requestId: string, extraVars?: Record<string, string>): T => {
+    const substitute = (value: unknown): unknown => {
+        if (typeof value === 'string') return substituteVariables(value, requestId, extraVars);
+        if (Array.isArray(value)) return value.map(substitute);
+        return value;
+    };
+    return Object.fromEntries(Object.entries(config).map(([k, v]) => [k, substitute(v)])) as T;
+};
+
 /**
  * Execute a script in a restricted context
  */
@@ -127,8 +139,175 @@ export interface ExecutionOverrides {
     additionalPostScripts?: ScriptItem[];
     formData?: any[];
     pathParams?: Record<string, string>;
+    graphql?: GraphQLOptions;
+    retry?: RetryPolicy;
 }
 
+const overrideOr = (overrides: ExecutionOverrides | undefined, key: keyof ExecutionOverrides, fallback: any) =>
+    (overrides && (overrides as any)[key] !== undefined) ? (overrides as any)[key] : fallback;
+
+type RequestState = ReturnType<typeof initialRequestState>;
+
+/**
+ * Request state of an execution before scripts run: overrides, then the execution, then its request
+ */
+const initialRequestState = (execution: ExecutionItem, parentRequest: RequestItem, overrides?: ExecutionOverrides) => {
+    return {
+        method: String(overrideOr(overrides, 'method', (execution.method ?? parentRequest.method) || 'GET')),
+        url: String(overrideOr(overrides, 'url', (execution.url ?? parentRequest.url) || '')).split('?')[0],
+        headers: [...(overrideOr(overrides, 'headers', execution.headers ?? []) as any[])],
+        queryParams: [...(overrideOr(overrides, 'queryParams', execution.queryParams ?? []) as any[])],
+        pathParams: { ...(overrideOr(overrides, 'pathParams', execution.pathParams ?? {}) as Record<string, string>) },
+        body: String(overrideOr(overrides, 'body', execution.body ?? parentRequest.body ?? '')),
+        bodyType: String(overrideOr(overrides, 'bodyType', execution.bodyType ?? parentRequest.bodyType ?? 'none')),
+        auth: overrideOr(overrides, 'auth', execution.auth ?? parentRequest.auth ?? { type: 'inherit' }),
+        preScripts: overrideOr(overrides, 'preScripts', (execution.preScripts ?? parentRequest.preScripts ?? [])),
+        postScripts: overrideOr(overrides, 'postScripts', (execution.postScripts ?? parentRequest.postScripts ?? [])),
+        additionalPreScripts: overrides?.additionalPreScripts || [],
+        additionalPostScripts: overrides?.additionalPostScripts || [],
+        formData: [...(overrideOr(overrides, 'formData', execution.formData ?? parentRequest.formData ?? []) as any[])]
+    };
+};
+
+/**
+ * Backend `HttpRequestArgs` fields (without ids) of a request state, variables substituted
+ */
+export interface PreparedRequest {
+    method: string;
+    url: string;
+    headers: [string, string][];
+    body: string | null;
+    form_data: any;
+    graphql: any;
+    auth: AuthConfig | null;
+}
+
+/**
+ * Resolve inherited headers and auth, path and query params and the body of a request state
+ */
+const buildRequestArgs = (requestId: string, requestState: RequestState, graphqlOptions: GraphQLOptions, extraVars?: Record<string, string>): PreparedRequest => {
+    // Prep Headers
+    const parentHeaders = resolveHeaders(requestId);
+    const finalHeaders: [string, string][] = [];
+    parentHeaders.forEach(h => {
+        if (h.key && h.values) {
+            h.values.forEach(v => finalHeaders.push([h.key, substituteVariables(v, requestId, extraVars)]));
+        }
+    });
+    // Apply overrides (exec or passed)
+    requestState.headers.forEach(h => {
+        if (h.key && h.enabled) {
+            // Filter out parent headers with same key
+            const idxs = finalHeaders.reduce((acc, fh, i) => (fh[0].toLowerCase() === h.key.toLowerCase() ? [i, ...acc] : acc), [] as number[]);
+            idxs.forEach(i => finalHeaders.splice(i, 1));
+            h.values.forEach((v: string) => finalHeaders.push([h.key, substituteVariables(v, requestId, extraVars)]));
+        }
+    });
+
+    // Auth
+    let authConfig = requestState.auth;
+    if (authConfig.type === 'inherit') {
+        const resolved = resolveAuth(requestId);
+        if (resolved) authConfig = resolved.config;
+    }
+    if (authConfig.type === 'basic' && authConfig.basic) {
+        const token = btoa(`${substituteVariables(authConfig.basic.username, requestId, extraVars)}:${substituteVariables(authConfig.basic.password, requestId, extraVars)}`);
+        finalHeaders.push(['Authorization', `Basic ${token}`]);
+    } else if (authConfig.type === 'bearer' && authConfig.bearer) {
+        finalHeaders.push(['Authorization', `Bearer ${substituteVariables(authConfig.bearer.token, requestId, extraVars)}`]);
+    }
+    // Auth types resolved by the backend: forward the config with variables substituted
+    let backendAuth: AuthConfig | null = null;
+    if (authConfig.type === 'oauth2' && authConfig.oauth2) {
+        backendAuth = { type: 'oauth2', oauth2: substituteAuthFields(authConfig.oauth2, requestId, extraVars) };
+    } else if (authConfig.type === 'digest' && authConfig.digest) {
+        backendAuth = { type: 'digest', digest: substituteAuthFields(authConfig.digest, requestId, extraVars) };
+    } else if (authConfig.type === 'aws_sigv4' && authConfig.aws_sigv4) {
+        backendAuth = { type: 'aws_sigv4', aws_sigv4: substituteAuthFields(authConfig.aws_sigv4, requestId, extraVars) };
+    } else if (authConfig.type === 'api_key' && authConfig.api_key) {
+        backendAuth = { type: 'api_key', api_key: substituteAuthFields(authConfig.api_key, requestId, extraVars) };
+    } else if (authConfig.type === 'hmac' && authConfig.hmac) {
+        backendAuth = { type: 'hmac', hmac: substituteAuthFields(authConfig.hmac, requestId, extraVars) };
+    }
+
+    // Final URL with Query Params
+    let finalUrl = substituteVariables(requestState.url, requestId, extraVars);
+    // Substitute Path Params
+    Object.entries(requestState.pathParams).forEach(([k, v]) => {
+        finalUrl = finalUrl.replace(`{${k}}`, substituteVariables(String(v), requestId, extraVars));
+    });
+
+    const searchParams = new URLSearchParams();
+    requestState.queryParams.forEach(p => {
+        if (p.key && p.enabled) {
+            p.values.forEach((v: string) => searchParams.append(p.key, substituteVariables(v, requestId, extraVars)));
+        }
+    });
+    const qs = searchParams.toString();
+    if (qs) finalUrl += (finalUrl.includes('?') ? '&' : '?') + qs;
+
+    // Body
+    let finalBody = requestState.bodyType === 'none' ? null : substituteVariables(requestState.body, requestId, extraVars);
+    let formDataArgs: any = null;
+    let graphqlArgs: any = null;
+
+    if (requestState.bodyType === 'form_urlencoded') {
+        const params = new URLSearchParams();
+        requestState.formData.forEach((group: any) => {
+            group.values.forEach((v: string) => params.append(group.key, substituteVariables(v, requestId, extraVars)));
+        });
+        finalBody = params.toString();
+        if (!finalHeaders.find(fh => fh[0].toLowerCase() === 'content-type')) finalHeaders.push(['Content-Type', 'application/x-www-form-urlencoded']);
+    } else if (requestState.bodyType === 'multipart') {
+        formDataArgs = requestState.formData.flatMap((group: any) => group.values.map((v: string, idx: number) => ({
+            key: group.key,
+            value: substituteVariables(v, requestId, extraVars),
+            entry_type: group.type,
+            content_type: group.contentTypes ? group.contentTypes[idx] : undefined
+        })));
+        finalBody = null;
+    } else if (requestState.bodyType === 'graphql') {
+        // Encoded into a JSON body or the query string by the backend
+        graphqlArgs = {
+            query: finalBody ?? '',
+            variables: graphqlOptions.variables ? substituteVariables(graphqlOptions.variables, requestId, extraVars) : null,
+            operation_name: graphqlOptions.operationName || null,
+            persisted: !!graphqlOptions.persisted
+        };
+        finalBody = null;
+    } else {
+        if (!finalHeaders.find(fh => fh[0].toLowerCase() === 'content-type') && requestState.bodyType !== 'none') {
+            const map: Record<string, string> = { json: 'application/json', xml: 'application/xml', yaml: 'application/x-yaml' };
+            if (map[requestState.bodyType]) finalHeaders.push(['Content-Type', map[requestState.bodyType]]);
+        }
+    }
+
+    return {
+        method: String(requestState.method || 'GET'),
+        url: String(finalUrl || ''),
+        headers: finalHeaders,
+        body: finalBody,
+        form_data: formDataArgs,
+        graphql: graphqlArgs,
+        auth: backendAuth
+    };
+};
+
+/**
+ * Backend arguments of an execution without running its scripts, for tools that send it
+ * outside of runExecution
+ */
+export const prepareExecutionArgs = (executionId: string, overrides?: ExecutionOverrides, extraVars?: Record<string, string>): PreparedRequest | null => {
+    const execution = executions.peek().find(e => e.id === executionId);
+    if (!execution) return null;
+    const parentRequest = requests.peek().find(r => r.id === execution.requestId);
+    if (!parentRequest) return null;
+
+    const requestState = initialRequestState(execution, parentRequest, overrides);
+    const graphqlOptions: GraphQLOptions = overrideOr(overrides, 'graphql', execution.graphql ?? parentRequest.graphql ?? {});
+    return buildRequestArgs(parentRequest.id, requestState, graphqlOptions, extraVars);
+};
+
 /**
  * Main execution function
  */
@@ -202,24 +381,10 @@ export const runExecution = async (
     try {
         const activeEnv = environments.peek().find(e => e.name === activeEnvName.peek());
 
-        const getVal = (key: keyof ExecutionOverrides, fallback: any) => (overrides && (overrides as any)[key] !== undefined) ? (overrides as any)[key] : fallback;
+        const getVal = (key: keyof ExecutionOverrides, fallback: any) => overrideOr(overrides, key, fallback);
 
         // Mutable request state for scripts
-        const requestState = {
-            method: String(getVal('method', (execution.method ?? parentRequest.method) || 'GET')),
-            url: String(getVal('url', (execution.url ?? parentRequest.url) || '')).split('?')[0],
-            headers: [...(getVal('headers', execution.headers ?? []) as any[])],
-            queryParams: [...(getVal('queryParams', execution.queryParams ?? []) as any[])],
-            pathParams: { ...(getVal('pathParams', execution.pathParams ?? {}) as Record<string, string>) },
-            body: String(getVal('body', execution.body ?? parentRequest.body ?? '')),
-            bodyType: String(getVal('bodyType', execution.bodyType ?? parentRequest.bodyType ?? 'none')),
-            auth: getVal('auth', execution.auth ?? parentRequest.auth ?? { type: 'inherit' }),
-            preScripts: getVal('preScripts', (execution.preScripts ?? parentRequest.preScripts ?? [])),
-            postScripts: getVal('postScripts', (execution.postScripts ?? parentRequest.postScripts ?? [])),
-            additionalPreScripts: overrides?.additionalPreScripts || [],
-            additionalPostScripts: overrides?.additionalPostScripts || [],
-            formData: [...(getVal('formData', execution.formData ?? parentRequest.formData ?? []) as any[])]
-        };
+        const requestState = initialRequestState(execution, parentRequest, overrides);
 
         // Script Context
         const scriptContext = {
@@ -309,92 +474,15 @@ export const runExecution = async (
         const prepStartTime = Date.now();
         setStepStatus('prep', 'running', undefined, prepStartTime);
 
-        // Prep Headers
-        const parentHeaders = resolveHeaders(parentRequest.id);
-        const finalHeaders: [string, string][] = [];
-        parentHeaders.forEach(h => {
-            if (h.key && h.values) {
-                h.values.forEach(v => finalHeaders.push([h.key, substituteVariables(v, parentRequest.id, extraVars)]));
-            }
-        });
-        // Apply overrides (exec or passed)
-        requestState.headers.forEach(h => {
-            if (h.key && h.enabled) {
-                // Filter out parent headers with same key
-                const idxs = finalHeaders.reduce((acc, fh, i) => (fh[0].toLowerCase() === h.key.toLowerCase() ? [i, ...acc] : acc), [] as number[]);
-                idxs.forEach(i => finalHeaders.splice(i, 1));
-                h.values.forEach((v: string) => finalHeaders.push([h.key, substituteVariables(v, parentRequest.id, extraVars)]));
-            }
-        });
-
-        // Auth
-        let authConfig = requestState.auth;
-        if (authConfig.type === 'inherit') {
-            const resolved = resolveAuth(parentRequest.id);
-            if (resolved) authConfig = resolved.config;
-        }
-        if (authConfig.type === 'basic' && authConfig.basic) {
-            const token = btoa(`${substituteVariables(authConfig.basic.username, parentRequest.id, extraVars)}:${substituteVariables(authConfig.basic.password, parentRequest.id, extraVars)}`);
-            finalHeaders.push(['Authorization', `Basic ${token}`]);
-        } else if (authConfig.type === 'bearer' && authConfig.bearer) {
-            finalHeaders.push(['Authorization', `Bearer ${substituteVariables(authConfig.bearer.token, parentRequest.id, extraVars)}`]);
-        }
-
-        // Final URL with Query Params
-        let finalUrl = substituteVariables(requestState.url, parentRequest.id, extraVars);
-        // Substitute Path Params
-        Object.entries(requestState.pathParams).forEach(([k, v]) => {
-            finalUrl = finalUrl.replace(`{${k}}`, substituteVariables(String(v), parentRequest.id, extraVars));
-        });
-
-        const searchParams = new URLSearchParams();
-        requestState.queryParams.forEach(p => {
-            if (p.key && p.enabled) {
-                p.values.forEach((v: string) => searchParams.append(p.key, substituteVariables(v, parentRequest.id, extraVars)));
-            }
-        });
-        const qs = searchParams.toString();
-        if (qs) finalUrl += (finalUrl.includes('?') ? '&' : '?') + qs;
-
-        // Body
-        let finalBody = requestState.bodyType === 'none' ? null : substituteVariables(requestState.body, parentRequest.id, extraVars);
-        let formDataArgs: any = null;
-
-        if (requestState.bodyType === 'form_urlencoded') {
-            const params = new URLSearchParams();
-            requestState.formData.forEach((group: any) => {
-                group.values.forEach((v: string) => params.append(group.key, substituteVariables(v, parentRequest.id, extraVars)));
-            });
-            finalBody = params.toString();
-            if (!finalHeaders.find(fh => fh[0].toLowerCase() === 'content-type')) finalHeaders.push(['Content-Type', 'application/x-www-form-urlencoded']);
-        } else if (requestState.bodyType === 'multipart') {
-            formDataArgs = requestState.formData.flatMap((group: any) => group.values.map((v: string, idx: number) => ({
-                key: group.key,
-                value: substituteVariables(v, parentRequest.id, extraVars),
-                entry_type: group.type,
-                content_type: group.contentTypes ? group.contentTypes[idx] : undefined
-            })));
-            finalBody = null;
-        } else {
-            if (!finalHeaders.find(fh => fh[0].toLowerCase() === 'content-type') && requestState.bodyType !== 'none') {
-                const map: Record<string, string> = { json: 'application/json', xml: 'application/xml', yaml: 'application/x-yaml' };
-                if (map[requestState.bodyType]) finalHeaders.push(['Content-Type', map[requestState.bodyType]]);
-            }
-        }
+        const graphqlOptions: GraphQLOptions = getVal('graphql', execution.graphql ?? parentRequest.graphql ?? {});
+        const prepared = buildRequestArgs(parentRequest.id, requestState, graphqlOptions, extraVars);
 
         setStepStatus('prep', 'completed', undefined, Date.now() - prepStartTime);
 
         // Early Request Reconstruction for UI
         try {
             const [raw, curl] = await invoke<[string, string]>('reconstruct_request', {
-                args: {
-                    method: String(requestState.method || 'GET'),
-                    url: String(finalUrl || ''),
-                    headers: finalHeaders,
-                    body: finalBody,
-                    form_data: formDataArgs,
-                    project_name: activeProjectName.peek()
-                }
+                args: { ...prepared, auth: undefined, project_name: activeProjectName.peek() }
             });
             updateExecutionResponse({
                 status: 0,
@@ -412,13 +500,10 @@ export const runExecution = async (
         // 3. HTTP Request
         const httpStartTime = Date.now();
         setStepStatus('http', 'running', undefined, httpStartTime);
-        const res = await invoke<{ status: number, headers: string[][], body: string, time_taken: number, request_raw: string, request_curl: string }>('http_request', {
+        const res = await invoke<{ status: number, headers: string[][], body: string, time_taken: number, request_raw: string, request_curl: string, content_encoding: string | null, decode_warning: string | null, wire_size: number, decoded_size: number | null, charset: string, http_version: string, remote_ip: string | null, remote_port: number | null, attempts: RetryAttempt[] }>('http_request', {
             args: {
-                method: String(requestState.method || 'GET'),
-                url: String(finalUrl || ''),
-                headers: finalHeaders,
-                body: finalBody,
-                form_data: formDataArgs,
+                ...prepared,
+                retry: getVal('retry', execution.retry),
                 request_id: tauriRequestId,
                 project_name: activeProjectName.peek()
             }
@@ -430,7 +515,7 @@ export const runExecution = async (
         // Update Response
         updateProgress({
             lastResponseTime: finalHttpTime,
-            responseSize: res.body.length,
+            responseSize: res.decoded_size ?? res.wire_size,
             responseStatus: res.status
         });
 
@@ -439,8 +524,15 @@ export const runExecution = async (
             headers: res.headers,
             body: res.body,
             time: finalHttpTime,
-            size: res.body.length,
-            requestUrl: finalUrl,
+            size: res.decoded_size ?? res.wire_size,
+            wireSize: res.wire_size,
+            contentEncoding: res.content_encoding ?? undefined,
+            decodeWarning: res.decode_warning ?? undefined,
+            charset: res.charset,
+            httpVersion: res.http_version,
+            remoteAddress: res.remote_ip ? (res.remote_ip.includes(':') ? `[${res.remote_ip}]` : res.remote_ip) + `:${res.remote_port}` : undefined,
+            attempts: res.attempts?.length ? res.attempts : undefined,
+            requestUrl: prepared.url,
             requestMethod: requestState.method,
             requestRaw: res.request_raw,
             requestCurl: res.request_curl,
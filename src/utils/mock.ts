@@ -0,0 +1,88 @@
+import { ExternalMockEndpoint, MockFaults, MockResource, MockTlsConfig, MockResponseRule, TableRow } from '../store';
+
+const toBackendHeaders = (headers: TableRow[]) => headers.flatMap(h => h.values.map(v => [h.key, v]));
+
+/**
+ * Converts mock rules to the shape expected by `start_mock_server`
+ */
+export const toBackendRules = (rules?: MockResponseRule[]) => (rules || []).map(rule => ({
+    name: rule.name,
+    priority: rule.priority ?? 0,
+    when: {
+        headers: rule.when.headers || [],
+        query: rule.when.query || [],
+        json: rule.when.json || [],
+        body_regex: rule.when.bodyRegex || null
+    },
+    response: {
+        status_code: rule.response.statusCode,
+        headers: toBackendHeaders(rule.response.headers),
+        body: rule.response.body,
+        templated: rule.response.templated ?? false
+    }
+}));
+
+/**
+ * Converts CRUD resource mocks to the shape expected by `start_mock_server`
+ */
+export const toBackendResources = (resources?: MockResource[]) => (resources || []).map(resource => ({
+    name: resource.name || null,
+    base_path: resource.basePath,
+    id_field: resource.idField || 'id',
+    seed: resource.seed || [],
+    seed_file: resource.seedFile || null
+}));
+
+/**
+ * Converts the sequence and scenario settings of an endpoint for `start_mock_server`
+ */
+export const toBackendStateful = (ep: ExternalMockEndpoint) => ({
+    sequence: (ep.sequence || []).map(r => ({
+        status_code: r.statusCode,
+        headers: toBackendHeaders(r.headers),
+        body: r.body,
+        templated: r.templated ?? false
+    })),
+    sequence_mode: ep.sequenceMode || null,
+    scenario: ep.scenario || null,
+    required_state: ep.requiredState || null,
+    new_state: ep.newState || null
+});
+
+/**
+ * Converts fault injection settings for `start_mock_server`
+ */
+export const toBackendFaults = (faults?: MockFaults) => faults ? {
+    delay_ms: faults.delayMs ?? null,
+    delay_max_ms: faults.delayMaxMs ?? null,
+    error_rate: faults.errorRate ?? null,
+    error_status: faults.errorStatus ?? null,
+    connection_fault: faults.connectionFault || null,
+    connection_fault_rate: faults.connectionFaultRate ?? null,
+    truncate_body_bytes: faults.truncateBodyBytes ?? null,
+    bandwidth_bytes_per_sec: faults.bandwidthBytesPerSec ?? null
+} : null;
+
+/**
+ * Converts an endpoint returned by `promote_mock_request` into an editor endpoint
+ */
+export const fromBackendEndpoint = (def: { method: string; path: string; response: { status_code: number; headers: string[][]; body: string; templated: boolean } }): ExternalMockEndpoint => ({
+    method: def.method,
+    path: def.path,
+    response: {
+        statusCode: def.response.status_code,
+        headers: def.response.headers.map(([key, value]) => ({ key, values: [value], enabled: true })),
+        body: def.response.body,
+        templated: def.response.templated,
+        enabled: true
+    }
+});
+
+/**
+ * Converts the HTTPS settings for `start_mock_server`, null when TLS is off
+ */
+export const toBackendTls = (tls?: MockTlsConfig) => tls?.enabled ? {
+    cert_file: tls.certFile || null,
+    key_file: tls.keyFile || null,
+    hosts: tls.hosts || []
+} : null;
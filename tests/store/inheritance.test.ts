@@ -3,7 +3,7 @@ import { batch } from '@preact/signals';
 import { 
     collections, requests, folders, 
     createNewFolder, 
-    resolveHeaders 
+    resolveHeaders, resolveAuth
 } from '../../src/store/collections';
 
 describe('Header Inheritance', () => {
@@ -54,3 +54,50 @@ describe('Header Inheritance', () => {
         expect(headers[0].values[0]).toBe('child-val');
     });
 });
+
+describe('Auth Inheritance', () => {
+    beforeEach(() => {
+        batch(() => {
+            collections.value = [];
+            requests.value = [];
+            folders.value = [];
+        });
+    });
+
+    it('should inherit HMAC auth config from a parent folder', () => {
+        const collId = 'coll-1';
+        const folder1 = createNewFolder('Parent Folder', collId);
+        folder1.auth = { type: 'hmac', hmac: { secret: 's3cr3t', header: 'X-Signature' } };
+
+        folders.value = [folder1];
+
+        const folder2 = createNewFolder('Child Folder', collId, folder1.id);
+        folder2.auth = { type: 'inherit' };
+
+        folders.value = [...folders.value, folder2];
+
+        const resolved = resolveAuth(folder2.id);
+
+        expect(resolved?.source).toBe('Parent Folder');
+        expect(resolved?.config.type).toBe('hmac');
+        expect(resolved?.config.hmac?.header).toBe('X-Signature');
+    });
+
+    it('should use the closest folder auth when overridden', () => {
+        const collId = 'coll-1';
+        const folder1 = createNewFolder('Parent Folder', collId);
+        folder1.auth = { type: 'bearer', bearer: { token: 'parent-token' } };
+
+        folders.value = [folder1];
+
+        const folder2 = createNewFolder('Child Folder', collId, folder1.id);
+        folder2.auth = { type: 'api_key', api_key: { key: 'X-Api-Key', value: 'child-key', location: 'header' } };
+
+        folders.value = [...folders.value, folder2];
+
+        const resolved = resolveAuth(folder2.id);
+
+        expect(resolved?.source).toBe('Child Folder');
+        expect(resolved?.config.api_key?.value).toBe('child-key');
+    });
+});
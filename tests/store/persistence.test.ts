@@ -0,0 +1,62 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import { batch } from '@preact/signals';
+
+const { invoke } = vi.hoisted(() => ({ invoke: vi.fn() }));
+vi.mock('@tauri-apps/api/core', () => ({ invoke }));
+vi.mock('@tauri-apps/plugin-dialog', () => ({ save: vi.fn() }));
+
+import { collections, requests, folders, environments, createNewRequest } from '../../src/store/collections';
+import { executions } from '../../src/store/executions';
+import { unsavedItemIds } from '../../src/store/uiState';
+import { saveCollectionToDisk } from '../../src/store/persistence';
+
+describe('Collection persistence', () => {
+    beforeEach(() => {
+        invoke.mockReset();
+        batch(() => {
+            collections.value = [{ id: 'coll-1', name: 'Pets', path: '/tmp/pets.collection.json' }];
+            requests.value = [];
+            folders.value = [];
+            executions.value = [];
+            environments.value = [];
+            unsavedItemIds.value = new Set();
+        });
+    });
+
+    it('should save through the backend so saved responses are redacted', async () => {
+        const request = createNewRequest('List', 'coll-1');
+        batch(() => {
+            requests.value = [request];
+            executions.value = [{
+                id: 'exec-1', requestId: request.id, collectionId: 'coll-1', name: 'default',
+                lastResponse: { status: 200, headers: [['Authorization', 'Bearer abc']], body: '{}' }
+            }];
+            unsavedItemIds.value = new Set([request.id, 'exec-1']);
+        });
+
+        const result = await saveCollectionToDisk('coll-1');
+
+        expect(result.success).toBe(true);
+        expect(invoke).toHaveBeenCalledTimes(1);
+        const [cmd, args] = invoke.mock.calls[0];
+        expect(cmd).toBe('save_collection');
+        expect(args.path).toBe('/tmp/pets.collection.json');
+        expect(JSON.parse(args.data).executions[0].lastResponse.status).toBe(200);
+        expect(unsavedItemIds.value.size).toBe(0);
+    });
+
+    it('should report a backend failure without clearing unsaved items', async () => {
+        invoke.mockRejectedValue('disk full');
+        const request = createNewRequest('List', 'coll-1');
+        batch(() => {
+            requests.value = [request];
+            unsavedItemIds.value = new Set([request.id]);
+        });
+
+        const result = await saveCollectionToDisk('coll-1');
+
+        expect(result.success).toBe(false);
+        expect(result.message).toContain('disk full');
+        expect(unsavedItemIds.value.has(request.id)).toBe(true);
+    });
+});
@@ -0,0 +1,140 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import { batch } from '@preact/signals';
+import { collections, requests, folders, environments, createNewRequest, createNewFolder } from '../../src/store/collections';
+import { executions } from '../../src/store/executions';
+import { activeEnvName } from '../../src/store/uiState';
+import { prepareExecutionArgs } from '../../src/utils/execution';
+import { AuthConfig, RequestItem } from '../../src/store';
+
+const setup = (request: RequestItem) => {
+    batch(() => {
+        requests.value = [request];
+        executions.value = [{ id: 'exec-1', requestId: request.id, collectionId: request.collectionId, name: 'default' }];
+    });
+};
+
+describe('Backend auth forwarding', () => {
+    beforeEach(() => {
+        batch(() => {
+            collections.value = [];
+            requests.value = [];
+            folders.value = [];
+            executions.value = [];
+            environments.value = [
+                { name: 'Global', variables: [{ key: 'client_id', value: 'my-client' }, { key: 'secret', value: 's3cr3t' }] }
+            ];
+            activeEnvName.value = 'Global';
+        });
+    });
+
+    it('should forward OAuth2 configs with variables substituted', () => {
+        const request = createNewRequest('Token', 'coll-1');
+        request.auth = {
+            type: 'oauth2',
+            oauth2: {
+                grant_type: 'client_credentials',
+                token_url: 'https://auth.example.com/token',
+                client_id: '{{client_id}}',
+                client_secret: '{{secret}}',
+                extra_params: [['audience', '{{client_id}}']]
+            }
+        };
+        setup(request);
+
+        const args = prepareExecutionArgs('exec-1');
+
+        expect(args?.auth?.type).toBe('oauth2');
+        expect(args?.auth?.oauth2?.client_id).toBe('my-client');
+        expect(args?.auth?.oauth2?.client_secret).toBe('s3cr3t');
+        expect(args?.auth?.oauth2?.extra_params).toEqual([['audience', 'my-client']]);
+        // Obtained by the backend, so no Authorization header is added here
+        expect(args?.headers.find(([name]) => name.toLowerCase() === 'authorization')).toBeUndefined();
+    });
+
+    it.each<[string, AuthConfig]>([
+        ['digest', { type: 'digest', digest: { username: 'user', password: '{{secret}}' } }],
+        ['aws_sigv4', { type: 'aws_sigv4', aws_sigv4: { access_key_id: 'AKID', secret_access_key: '{{secret}}', region: 'eu-west-1', service: 's3' } }],
+        ['api_key', { type: 'api_key', api_key: { key: 'X-Api-Key', value: '{{secret}}', location: 'header' } }],
+        ['hmac', { type: 'hmac', hmac: { secret: '{{secret}}', header: 'X-Signature' } }]
+    ])('should forward %s configs to the backend', (type, auth) => {
+        const request = createNewRequest('Signed', 'coll-1');
+        request.auth = auth;
+        setup(request);
+
+        const args = prepareExecutionArgs('exec-1');
+
+        expect(args?.auth?.type).toBe(type);
+        expect(JSON.stringify(args?.auth)).toContain('s3cr3t');
+        expect(JSON.stringify(args?.auth)).not.toContain('{{secret}}');
+    });
+
+    it('should forward a backend auth type inherited from a folder', () => {
+        const folder = createNewFolder('Token Folder', 'coll-1');
+        folder.auth = { type: 'oauth2', oauth2: { grant_type: 'client_credentials', token_url: 'https://auth.example.com/token', client_id: 'folder-client' } };
+        folders.value = [folder];
+
+        const request = createNewRequest('Child', 'coll-1', folder.id);
+        setup(request);
+
+        expect(prepareExecutionArgs('exec-1')?.auth?.oauth2?.client_id).toBe('folder-client');
+    });
+
+    it('should keep basic auth as a plain header', () => {
+        const request = createNewRequest('Basic', 'coll-1');
+        request.auth = { type: 'basic', basic: { username: 'user', password: 'pass' } };
+        setup(request);
+
+        const args = prepareExecutionArgs('exec-1');
+
+        expect(args?.auth).toBeNull();
+        expect(args?.headers).toContainEqual(['Authorization', `Basic ${btoa('user:pass')}`]);
+    });
+});
+
+describe('Prepared request', () => {
+    beforeEach(() => {
+        batch(() => {
+            requests.value = [];
+            folders.value = [];
+            executions.value = [];
+            environments.value = [{ name: 'Global', variables: [{ key: 'id', value: '42' }] }];
+            activeEnvName.value = 'Global';
+        });
+    });
+
+    it('should hand GraphQL queries to the backend instead of a body', () => {
+        const request = createNewRequest('Query', 'coll-1');
+        request.bodyType = 'graphql';
+        request.body = 'query Pet($id: ID!) { pet(id: $id) { name } }';
+        request.graphql = { variables: '{"id": "{{id}}"}', operationName: 'Pet', persisted: true };
+        setup(request);
+
+        const args = prepareExecutionArgs('exec-1');
+
+        expect(args?.body).toBeNull();
+        expect(args?.graphql).toEqual({
+            query: request.body,
+            variables: '{"id": "42"}',
+            operation_name: 'Pet',
+            persisted: true
+        });
+    });
+
+    it('should apply unsaved editor overrides', () => {
+        const request = createNewRequest('Echo', 'coll-1');
+        request.url = 'https://api.example.com/pets/{{id}}';
+        setup(request);
+
+        const args = prepareExecutionArgs('exec-1', {
+            method: 'DELETE',
+            queryParams: [{ key: 'force', values: ['true'], enabled: true }]
+        });
+
+        expect(args?.method).toBe('DELETE');
+        expect(args?.url).toBe('https://api.example.com/pets/42?force=true');
+    });
+
+    it('should return null for an unknown execution', () => {
+        expect(prepareExecutionArgs('missing')).toBeNull();
+    });
+});
@@ -0,0 +1,138 @@
+import { describe, it, expect } from 'vitest';
+import {
+    fromBackendEndpoint, toBackendFaults, toBackendResources,
+    toBackendRules, toBackendStateful, toBackendTls
+} from '../../src/utils/mock';
+import { ExternalMockEndpoint } from '../../src/store';
+
+const endpoint = (extra: Partial<ExternalMockEndpoint> = {}): ExternalMockEndpoint => ({
+    method: 'GET',
+    path: '/pets/{id}',
+    response: { statusCode: 200, headers: [], body: '{}', enabled: true },
+    ...extra
+});
+
+describe('Mock rules', () => {
+    it('should convert rules with their conditions and templated responses', () => {
+        const rules = toBackendRules([{
+            name: 'premium',
+            when: {
+                headers: [{ name: 'X-Tier', equals: 'premium' }],
+                json: [{ path: '$.kind', equals: 'cat' }],
+                bodyRegex: 'whiskers'
+            },
+            response: {
+                statusCode: 201,
+                headers: [{ key: 'X-Id', values: ['{{request.path.id}}', 'b'], enabled: true }],
+                body: 'ok',
+                templated: true
+            }
+        }]);
+
+        expect(rules).toEqual([{
+            name: 'premium',
+            priority: 0,
+            when: {
+                headers: [{ name: 'X-Tier', equals: 'premium' }],
+                query: [],
+                json: [{ path: '$.kind', equals: 'cat' }],
+                body_regex: 'whiskers'
+            },
+            response: {
+                status_code: 201,
+                headers: [['X-Id', '{{request.path.id}}'], ['X-Id', 'b']],
+                body: 'ok',
+                templated: true
+            }
+        }]);
+    });
+
+    it('should send no rules when none are defined', () => {
+        expect(toBackendRules(undefined)).toEqual([]);
+    });
+});
+
+describe('Stateful mocks', () => {
+    it('should default the id field and seed of CRUD resources', () => {
+        expect(toBackendResources([{ basePath: '/pets' }])).toEqual([{
+            name: null,
+            base_path: '/pets',
+            id_field: 'id',
+            seed: [],
+            seed_file: null
+        }]);
+    });
+
+    it('should convert sequences and scenario states', () => {
+        const stateful = toBackendStateful(endpoint({
+            sequence: [{ statusCode: 503, headers: [], body: 'busy' }, { statusCode: 200, headers: [], body: 'done' }],
+            sequenceMode: 'cycle',
+            scenario: 'checkout',
+            requiredState: 'Started',
+            newState: 'Paid'
+        }));
+
+        expect(stateful.sequence.map(r => r.status_code)).toEqual([503, 200]);
+        expect(stateful.sequence_mode).toBe('cycle');
+        expect(stateful.scenario).toBe('checkout');
+        expect(stateful.required_state).toBe('Started');
+        expect(stateful.new_state).toBe('Paid');
+    });
+
+    it('should send null scenario fields for a plain endpoint', () => {
+        expect(toBackendStateful(endpoint())).toEqual({
+            sequence: [],
+            sequence_mode: null,
+            scenario: null,
+            required_state: null,
+            new_state: null
+        });
+    });
+});
+
+describe('Mock faults and transport', () => {
+    it('should convert fault settings and null the unset ones', () => {
+        expect(toBackendFaults({ delayMs: 100, delayMaxMs: 300, errorRate: 10, connectionFault: 'reset' })).toEqual({
+            delay_ms: 100,
+            delay_max_ms: 300,
+            error_rate: 10,
+            error_status: null,
+            connection_fault: 'reset',
+            connection_fault_rate: null,
+            truncate_body_bytes: null,
+            bandwidth_bytes_per_sec: null
+        });
+        expect(toBackendFaults(undefined)).toBeNull();
+    });
+
+    it('should only send TLS settings when HTTPS is enabled', () => {
+        expect(toBackendTls({ enabled: false, certFile: 'cert.pem' })).toBeNull();
+        expect(toBackendTls({ enabled: true, hosts: ['mock.local'] })).toEqual({
+            cert_file: null,
+            key_file: null,
+            hosts: ['mock.local']
+        });
+    });
+});
+
+describe('Mock journal', () => {
+    it('should turn a promoted request into an editor endpoint', () => {
+        const ep = fromBackendEndpoint({
+            method: 'POST',
+            path: '/orders',
+            response: { status_code: 200, headers: [['Content-Type', 'application/json']], body: '', templated: false }
+        });
+
+        expect(ep).toEqual({
+            method: 'POST',
+            path: '/orders',
+            response: {
+                statusCode: 200,
+                headers: [{ key: 'Content-Type', values: ['application/json'], enabled: true }],
+                body: '',
+                templated: false,
+                enabled: true
+            }
+        });
+    });
+});
@@ -0,0 +1,101 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import { batch } from '@preact/signals';
+
+const { invoke } = vi.hoisted(() => ({ invoke: vi.fn() }));
+vi.mock('@tauri-apps/api/core', () => ({ invoke }));
+
+import { requests, folders, environments, createNewRequest } from '../../src/store/collections';
+import { executions } from '../../src/store/executions';
+import { runExecution } from '../../src/utils/execution';
+
+/** Answers the backend commands used by runExecution, `http_request` with `response` */
+const mockBackend = (response: Record<string, unknown>) => {
+    invoke.mockImplementation(async (cmd: string) => {
+        if (cmd === 'reconstruct_request') return ['GET / HTTP/1.1', 'curl'];
+        if (cmd === 'http_request') {
+            return { status: 200, headers: [], body: 'hello', time_taken: 5, request_raw: '', request_curl: '', ...response };
+        }
+        return null;
+    });
+};
+
+describe('Response metadata', () => {
+    beforeEach(() => {
+        invoke.mockReset();
+        const request = createNewRequest('Get', 'coll-1');
+        request.url = 'https://api.example.com/';
+        batch(() => {
+            requests.value = [request];
+            folders.value = [];
+            environments.value = [];
+            executions.value = [{ id: 'exec-1', requestId: request.id, collectionId: 'coll-1', name: 'default' }];
+        });
+    });
+
+    it('should report decoded and wire sizes of compressed responses', async () => {
+        mockBackend({ content_encoding: 'gzip', decode_warning: null, wire_size: 25, decoded_size: 5, charset: 'utf-8' });
+
+        const response = await runExecution('exec-1');
+
+        expect(response?.size).toBe(5);
+        expect(response?.wireSize).toBe(25);
+        expect(response?.contentEncoding).toBe('gzip');
+        expect(response?.charset).toBe('utf-8');
+        expect(response?.decodeWarning).toBeUndefined();
+    });
+
+    it('should pass on decompression warnings', async () => {
+        mockBackend({ content_encoding: 'gzip', decode_warning: 'Failed to decompress gzip body', wire_size: 5, decoded_size: null, charset: 'utf-8' });
+
+        const response = await runExecution('exec-1');
+
+        expect(response?.body).toBe('hello');
+        // Shown as received, so sized as received
+        expect(response?.size).toBe(5);
+        expect(response?.decodeWarning).toBe('Failed to decompress gzip body');
+        expect(executions.value[0].lastResponse?.decodeWarning).toBe('Failed to decompress gzip body');
+    });
+
+    it('should keep the negotiated HTTP version', async () => {
+        mockBackend({ content_encoding: null, decode_warning: null, wire_size: 5, decoded_size: 5, charset: 'utf-8', http_version: 'HTTP/2' });
+
+        const response = await runExecution('exec-1');
+
+        expect(response?.httpVersion).toBe('HTTP/2');
+    });
+
+    it('should format the remote address, with brackets around IPv6', async () => {
+        mockBackend({ wire_size: 5, decoded_size: 5, charset: 'utf-8', remote_ip: '10.0.0.7', remote_port: 8443 });
+        expect((await runExecution('exec-1'))?.remoteAddress).toBe('10.0.0.7:8443');
+
+        mockBackend({ wire_size: 5, decoded_size: 5, charset: 'utf-8', remote_ip: '::1', remote_port: 443 });
+        expect((await runExecution('exec-1'))?.remoteAddress).toBe('[::1]:443');
+    });
+
+    it('should leave the remote address out over a Unix socket', async () => {
+        mockBackend({ wire_size: 5, decoded_size: 5, charset: 'utf-8', remote_ip: null, remote_port: null });
+
+        expect((await runExecution('exec-1'))?.remoteAddress).toBeUndefined();
+    });
+
+    it('should send the retry policy and keep the attempts made', async () => {
+        executions.value = [{ ...executions.value[0], retry: { max_attempts: 2, status_codes: '503' } }];
+        const attempts = [
+            { attempt: 1, status: 503, error: null, error_kind: null, time_ms: 4, delay_ms: 500 },
+            { attempt: 2, status: 200, error: null, error_kind: null, time_ms: 3, delay_ms: 0 }
+        ];
+        mockBackend({ wire_size: 5, decoded_size: 5, charset: 'utf-8', attempts });
+
+        const response = await runExecution('exec-1');
+
+        const call = invoke.mock.calls.find(([cmd]) => cmd === 'http_request');
+        expect(call?.[1].args.retry).toEqual({ max_attempts: 2, status_codes: '503' });
+        expect(response?.attempts).toEqual(attempts);
+    });
+
+    it('should leave attempts out without a retry policy', async () => {
+        mockBackend({ wire_size: 5, decoded_size: 5, charset: 'utf-8', attempts: [] });
+
+        expect((await runExecution('exec-1'))?.attempts).toBeUndefined();
+    });
+});